@@ -1,2 +1,56 @@
 pub const LIQUIDITY_POOL_SEED: &[u8] = b"pool";
 pub const MINIMUM_LIQUIDITY_WITHDRAWAL: u64 = 1000;
+pub const CONCENTRATED_POOL_SEED: &[u8] = b"concentrated_pool";
+pub const TICK_ARRAY_SEED: &[u8] = b"tick_array";
+pub const POSITION_SEED: &[u8] = b"position";
+// Upper bound on initialized ticks crossed by a single concentrated swap, keeps it within the compute budget
+pub const MAX_TICK_CROSSINGS_PER_SWAP: usize = 16;
+pub const BIN_POOL_SEED: &[u8] = b"bin_pool";
+pub const BIN_ARRAY_SEED: &[u8] = b"bin_array";
+// Upper bounds keeping bin walks within the compute budget
+pub const MAX_BINS_PER_SWAP: usize = 64;
+pub const MAX_BINS_PER_DEPOSIT: i32 = 128;
+// Bin arrays a withdrawal has to be paid out of in its first transaction, when the pool holds that many
+pub const MIN_BIN_ARRAYS_PER_WITHDRAWAL: u32 = 2;
+pub const MULTI_POOL_SEED: &[u8] = b"multi_pool";
+pub const GLOBAL_CONFIG_SEED: &[u8] = b"global_config";
+pub const TRADER_STATS_SEED: &[u8] = b"trader_stats";
+pub const REFERRER_SEED: &[u8] = b"referrer";
+pub const SECONDS_PER_DAY: i64 = 86_400;
+// Length of the rolling window trader volume tiers are based on
+pub const VOLUME_WINDOW_DAYS: usize = 30;
+pub const ORDER_BOOK_SEED: &[u8] = b"order_book";
+// Upper bound on limit orders filled by a single swap or crank, keeps it within the compute budget
+pub const MAX_ORDER_FILLS_PER_CALL: usize = 8;
+// Bisection steps searching the part of a limit order the pool can fill at its limit
+pub const PARTIAL_FILL_SEARCH_STEPS: u32 = 16;
+pub const TWAMM_SEED: &[u8] = b"twamm";
+pub const DCA_ORDER_SEED: &[u8] = b"dca_order";
+// Share of a DCA cycle or trigger order paid to the keeper executing it, in the input token
+pub const KEEPER_TIP_BPS: u64 = 10;
+// Shortest period the pool TWAP is averaged over
+pub const TWAP_WINDOW: i64 = 300;
+pub const TRIGGER_ORDER_SEED: &[u8] = b"trigger_order";
+pub const MARKET_MAKER_SEED: &[u8] = b"market_maker";
+pub const RFQ_NONCE_SEED: &[u8] = b"rfq_nonce";
+// Makers approve this address as delegate of the token accounts their quotes are paid from
+pub const RFQ_AUTHORITY_SEED: &[u8] = b"rfq_authority";
+pub const INTENT_NONCE_SEED: &[u8] = b"intent_nonce";
+// Users approve this address as delegate of the token accounts their intents sell from
+pub const INTENT_AUTHORITY_SEED: &[u8] = b"intent_authority";
+// Cap on the output share a signed intent may tip its relayer
+pub const MAX_RELAYER_TIP_BPS: u64 = 100;
+pub const BATCH_AUCTION_SEED: &[u8] = b"batch_auction";
+pub const MARKET_SEED: &[u8] = b"market";
+// Upper bound on book orders filled by a single swap, keeps it within the compute budget
+pub const MAX_MARKET_FILLS_PER_SWAP: usize = 8;
+pub const FARM_SEED: &[u8] = b"farm";
+pub const FARM_STAKE_SEED: &[u8] = b"farm_stake";
+pub const LP_POSITION_SEED: &[u8] = b"lp_position";
+// Token account of a pool holding the LP tokens of its positions
+pub const POSITION_LP_VAULT_SEED: &[u8] = b"position_lp_vault";
+// Token accounts of a pool holding the swap fees separated for its positions
+pub const FEE_VAULT_SEED: &[u8] = b"fee_vault";
+pub const LIQUIDITY_LOCK_SEED: &[u8] = b"liquidity_lock";
+// Token account of a liquidity lock holding its LP tokens, owned by the pool
+pub const LOCK_VAULT_SEED: &[u8] = b"lock_vault";
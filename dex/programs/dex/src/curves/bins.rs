@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::concentrated::Q64;
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor, mul_shr};
+use crate::state::{Bin, LiquidityShape};
+
+/// Price of token A in token B inside a bin: (1 + bin_step / 10_000) ^ bin_id, in Q64.64
+pub fn bin_price(bin_id: i32, bin_step: u16) -> Result<u128> {
+    let base = Q64 + Q64 * bin_step as u128 / 10_000;
+
+    let mut exponent = bin_id.unsigned_abs();
+    let mut factor = base;
+    let mut price = Q64;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            price = mul_shr(price, factor, 64).map_err(|_| DEXError::InvalidBinId)?;
+        }
+
+        exponent >>= 1;
+
+        if exponent > 0 {
+            factor = mul_shr(factor, factor, 64).map_err(|_| DEXError::InvalidBinId)?;
+        }
+    }
+
+    if bin_id < 0 {
+        mul_div_floor(Q64, Q64, price)
+    } else {
+        Ok(price)
+    }
+}
+
+pub struct BinSwap {
+    /// Input consumed by the bin, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// The bin ran out of the output token
+    pub exhausted: bool,
+}
+
+/// Constant-sum swap inside a single bin. The fee is taken from the input and stays in the bin.
+pub fn swap_in_bin(
+    bin: &Bin,
+    price: u128,
+    a_to_b: bool,
+    amount_remaining: u64,
+    fee_bps: u64,
+) -> Result<BinSwap> {
+    let reserve_out = if a_to_b { bin.amount_b } else { bin.amount_a } as u128;
+    let fee_bps = fee_bps as u128;
+
+    // Net input needed to empty the bin
+    let max_amount_in_net = if a_to_b {
+        mul_div_ceil(reserve_out, Q64, price)?
+    } else {
+        mul_div_ceil(reserve_out, price, Q64)?
+    };
+    let max_amount_in = mul_div_ceil(max_amount_in_net, 10_000, 10_000 - fee_bps)?;
+
+    if amount_remaining as u128 >= max_amount_in {
+        return Ok(BinSwap {
+            amount_in: u64::try_from(max_amount_in).map_err(|_| DEXError::MathOverflow)?,
+            amount_out: reserve_out as u64,
+            exhausted: true,
+        });
+    }
+
+    let amount_in_net = mul_div_floor(amount_remaining as u128, 10_000 - fee_bps, 10_000)?;
+    let amount_out = if a_to_b {
+        mul_div_floor(amount_in_net, price, Q64)?
+    } else {
+        mul_div_floor(amount_in_net, Q64, price)?
+    };
+
+    Ok(BinSwap {
+        amount_in: amount_remaining,
+        amount_out: amount_out.min(reserve_out) as u64,
+        exhausted: false,
+    })
+}
+
+/// Weights of every bin in [lower_bin_id, upper_bin_id] for the A side (bins at and above
+/// the active bin) and the B side (bins at and below it) of a deposit
+pub fn shape_weights(
+    shape: LiquidityShape,
+    lower_bin_id: i32,
+    upper_bin_id: i32,
+    active_bin_id: i32,
+) -> Vec<(i32, u64, u64)> {
+    let max_distance = (active_bin_id - lower_bin_id)
+        .unsigned_abs()
+        .max((upper_bin_id - active_bin_id).unsigned_abs()) as u64;
+
+    (lower_bin_id..=upper_bin_id)
+        .map(|bin_id| {
+            let distance = (bin_id - active_bin_id).unsigned_abs() as u64;
+
+            let weight = match shape {
+                LiquidityShape::Spot => 1,
+                LiquidityShape::Curve => max_distance + 1 - distance,
+                LiquidityShape::BidAsk => distance + 1,
+            };
+
+            let weight_a = if bin_id >= active_bin_id { weight } else { 0 };
+            let weight_b = if bin_id <= active_bin_id { weight } else { 0 };
+
+            (bin_id, weight_a, weight_b)
+        })
+        .collect()
+}
@@ -0,0 +1,265 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::{log2, mul_div_ceil, mul_div_floor, mul_shr, reciprocal_q64, ONE};
+
+// All sqrt prices are Q64.64: sqrt(price) * 2^64, price being token B per token A
+
+pub const Q64: u128 = 1 << 64;
+
+// Price range limited so that every sqrt price fits comfortably in a u128
+pub const MIN_TICK: i32 = -443_636;
+pub const MAX_TICK: i32 = 443_636;
+pub const MIN_SQRT_PRICE: u128 = 4_295_048_017;
+pub const MAX_SQRT_PRICE: u128 = 79_226_673_515_401_279_992_447_579_061;
+
+// log2(1.0001) scaled by ONE
+const LOG2_TICK_BASE: i128 = 144_262_291_094_554;
+
+// 2^128 / sqrt(1.0001)^(2^i), rounded up
+const TICK_RATIOS: [u128; 19] = [
+    0xfffcb933bd6fad37aa2d162d1a594001,
+    0xfff97272373d413259a46990580e213a,
+    0xfff2e50f5f656932ef12357cf3c7fdcc,
+    0xffe5caca7e10e4e61c3624eaa0941cd0,
+    0xffcb9843d60f6159c9db58835c926644,
+    0xff973b41fa98c081472e6896dfb254c0,
+    0xff2ea16466c96a3843ec78b326b52861,
+    0xfe5dee046a99a2a811c461f1969c3053,
+    0xfcbe86c7900a88aedcffc83b479aa3a4,
+    0xf987a7253ac413176f2b074cf7815e54,
+    0xf3392b0822b70005940c7a398e4b70f3,
+    0xe7159475a2c29b7443b29c7fa6e889d9,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e5,
+    0x70d869a156d2a1b890bb3df62baf32f7,
+    0x31be135f97d08fd981231505542fcfa6,
+    0x9aa508b5b7a84e1c677de54f3e99bc9,
+    0x5d6af8dedb81196699c329225ee604,
+    0x2216e584f5fa1ea926041bedfe98,
+];
+
+/// sqrt(1.0001^tick) in Q64.64
+pub fn sqrt_price_at_tick(tick: i32) -> Result<u128> {
+    require!(
+        (MIN_TICK..=MAX_TICK).contains(&tick),
+        DEXError::InvalidTickIndex
+    );
+
+    // Multiply together the factors of every set bit of |tick|, which gives
+    // sqrt(1.0001)^-|tick| as a Q128 fraction (u128::MAX standing in for 1.0)
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio = u128::MAX;
+
+    for (bit, factor) in TICK_RATIOS.iter().enumerate() {
+        if abs_tick & (1 << bit) != 0 {
+            ratio = mul_shr(ratio, *factor, 128)?;
+        }
+    }
+
+    if tick > 0 {
+        reciprocal_q64(ratio)
+    } else {
+        // Q128 -> Q64.64, rounding up so the result is never below the true price
+        Ok((ratio >> 64) + u128::from(ratio as u64 != 0))
+    }
+}
+
+/// Greatest tick whose sqrt price is lower or equal to `sqrt_price`
+pub fn tick_at_sqrt_price(sqrt_price: u128) -> Result<i32> {
+    require!(
+        (MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price),
+        DEXError::InvalidSqrtPrice
+    );
+
+    // tick = log_1.0001(price) = 2 * log2(sqrt_price) / log2(1.0001), estimated in
+    // fixed point and then corrected against the exact tick math
+    let sqrt_price_fixed = mul_div_floor(sqrt_price, ONE, Q64)?;
+    let log = log2(sqrt_price_fixed)?;
+    let estimate = (2 * log).div_euclid(LOG2_TICK_BASE);
+
+    let mut tick = (estimate as i32).clamp(MIN_TICK, MAX_TICK);
+
+    while tick > MIN_TICK && sqrt_price_at_tick(tick)? > sqrt_price {
+        tick -= 1;
+    }
+
+    while tick < MAX_TICK && sqrt_price_at_tick(tick + 1)? <= sqrt_price {
+        tick += 1;
+    }
+
+    Ok(tick)
+}
+
+/// Token A needed (or released) when moving liquidity between two sqrt prices:
+/// L * (upper - lower) / (upper * lower)
+pub fn amount_a_delta(
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u128> {
+    let (lower, upper) = ordered(sqrt_price_lower, sqrt_price_upper);
+    require!(lower > 0, DEXError::InvalidSqrtPrice);
+
+    if round_up {
+        let scaled = mul_div_ceil(liquidity, upper - lower, upper)?;
+        mul_div_ceil(scaled, Q64, lower)
+    } else {
+        let scaled = mul_div_floor(liquidity, upper - lower, upper)?;
+        mul_div_floor(scaled, Q64, lower)
+    }
+}
+
+/// Token B needed (or released) when moving liquidity between two sqrt prices:
+/// L * (upper - lower)
+pub fn amount_b_delta(
+    sqrt_price_lower: u128,
+    sqrt_price_upper: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<u128> {
+    let (lower, upper) = ordered(sqrt_price_lower, sqrt_price_upper);
+
+    if round_up {
+        mul_div_ceil(liquidity, upper - lower, Q64)
+    } else {
+        mul_div_floor(liquidity, upper - lower, Q64)
+    }
+}
+
+/// Token amounts backing `liquidity` in [tick_lower, tick_upper) at the current price
+pub fn amounts_for_liquidity(
+    sqrt_price: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<(u128, u128)> {
+    let sqrt_price_lower = sqrt_price_at_tick(tick_lower)?;
+    let sqrt_price_upper = sqrt_price_at_tick(tick_upper)?;
+
+    if sqrt_price <= sqrt_price_lower {
+        // Entirely in token A below the range
+        Ok((
+            amount_a_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?,
+            0,
+        ))
+    } else if sqrt_price < sqrt_price_upper {
+        Ok((
+            amount_a_delta(sqrt_price, sqrt_price_upper, liquidity, round_up)?,
+            amount_b_delta(sqrt_price_lower, sqrt_price, liquidity, round_up)?,
+        ))
+    } else {
+        // Entirely in token B above the range
+        Ok((
+            0,
+            amount_b_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?,
+        ))
+    }
+}
+
+pub struct SwapStep {
+    pub sqrt_price_next: u128,
+    pub amount_in: u128,
+    pub amount_out: u128,
+    pub fee_amount: u128,
+}
+
+/// Swaps as much of `amount_remaining` as possible without moving the price past
+/// `sqrt_price_target`. The fee is charged on top of `amount_in`.
+pub fn compute_swap_step(
+    sqrt_price_current: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u128,
+    fee_bps: u64,
+) -> Result<SwapStep> {
+    let a_to_b = sqrt_price_target <= sqrt_price_current;
+    let fee_bps = fee_bps as u128;
+
+    let amount_remaining_less_fee = mul_div_floor(amount_remaining, 10_000 - fee_bps, 10_000)?;
+
+    let max_amount_in = if a_to_b {
+        amount_a_delta(sqrt_price_target, sqrt_price_current, liquidity, true)?
+    } else {
+        amount_b_delta(sqrt_price_current, sqrt_price_target, liquidity, true)?
+    };
+
+    let sqrt_price_next = if amount_remaining_less_fee >= max_amount_in {
+        sqrt_price_target
+    } else if a_to_b {
+        next_sqrt_price_from_a(sqrt_price_current, liquidity, amount_remaining_less_fee)?
+    } else {
+        next_sqrt_price_from_b(sqrt_price_current, liquidity, amount_remaining_less_fee)?
+    };
+
+    let reached_target = sqrt_price_next == sqrt_price_target;
+
+    let (amount_in, amount_out) = if a_to_b {
+        (
+            if reached_target {
+                max_amount_in
+            } else {
+                amount_a_delta(sqrt_price_next, sqrt_price_current, liquidity, true)?
+            },
+            amount_b_delta(sqrt_price_next, sqrt_price_current, liquidity, false)?,
+        )
+    } else {
+        (
+            if reached_target {
+                max_amount_in
+            } else {
+                amount_b_delta(sqrt_price_current, sqrt_price_next, liquidity, true)?
+            },
+            amount_a_delta(sqrt_price_current, sqrt_price_next, liquidity, false)?,
+        )
+    };
+
+    let fee_amount = if reached_target {
+        mul_div_ceil(amount_in, fee_bps, 10_000 - fee_bps)?
+    } else {
+        // Whatever was not needed to move the price is kept as the fee
+        amount_remaining
+            .checked_sub(amount_in)
+            .ok_or(DEXError::MathOverflow)?
+    };
+
+    Ok(SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee_amount,
+    })
+}
+
+// Price after adding token A: L * P / (L + amount * P), rounded up
+fn next_sqrt_price_from_a(sqrt_price: u128, liquidity: u128, amount: u128) -> Result<u128> {
+    if amount == 0 {
+        return Ok(sqrt_price);
+    }
+
+    let product = mul_div_floor(amount, sqrt_price, Q64)?;
+    let denominator = liquidity
+        .checked_add(product)
+        .ok_or(DEXError::MathOverflow)?;
+
+    mul_div_ceil(liquidity, sqrt_price, denominator)
+}
+
+// Price after adding token B: P + amount / L, rounded down
+fn next_sqrt_price_from_b(sqrt_price: u128, liquidity: u128, amount: u128) -> Result<u128> {
+    let delta = mul_div_floor(amount, Q64, liquidity)?;
+
+    Ok(sqrt_price
+        .checked_add(delta)
+        .ok_or(DEXError::MathOverflow)?)
+}
+
+fn ordered(a: u128, b: u128) -> (u128, u128) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor};
+use crate::utils::i_sqrt;
+
+pub fn amount_out(amount_in: u128, reserve_in: u128, reserve_out: u128) -> Result<u128> {
+    //                          reserve_out * amount_in_net
+    // Formula is: delta_out = ------------------------------
+    //                          reserve_in + amount_in_net
+    //
+    // The formula is derived from xy = k -> (x + delta_x)(y - delta_y) = xy
+
+    let numerator = amount_in
+        .checked_mul(reserve_out)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let denominator = reserve_in
+        .checked_add(amount_in)
+        .ok_or(DEXError::MathOverflow)?;
+
+    Ok(numerator
+        .checked_div(denominator)
+        .ok_or(DEXError::MathOverflow)?)
+}
+
+/// Part of `sold_in` a batch has to swap through the pool so that it clears at a single
+/// price, given that `sold_out` of the other token is sold in the same batch. Zero when
+/// the other side sells more, or when both sides cross within the fee.
+pub fn batch_net_amount_in(
+    sold_in: u128,
+    sold_out: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    fee_bps: u64,
+) -> Result<u128> {
+    // Sellers of the input share sold_out + delta_out and sellers of the output share
+    // sold_in - delta_in, both at the same price when
+    //
+    //   delta_out * (sold_in - delta_in) = delta_in * sold_out
+    //
+    // With delta_out = reserve_out * g * delta_in / (reserve_in + g * delta_in), g being
+    // 1 - fee, that solves to
+    //
+    //                 sold_in * reserve_out      sold_out * reserve_in
+    //   delta_in = ------------------------- - -------------------------
+    //               reserve_out + sold_out      g * (reserve_out + sold_out)
+
+    let denominator = reserve_out
+        .checked_add(sold_out)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let matched = mul_div_floor(sold_in, reserve_out, denominator)?;
+    let crossed = mul_div_ceil(
+        sold_out.checked_mul(10_000).ok_or(DEXError::MathOverflow)?,
+        reserve_in,
+        denominator
+            .checked_mul((10_000 - fee_bps) as u128)
+            .ok_or(DEXError::MathOverflow)?,
+    )?;
+
+    Ok(matched.saturating_sub(crossed))
+}
+
+/// Input that brings the marginal price of the pool, output per unit of input after the
+/// fee, down to `price` (Q64.64). `None` when no representable amount gets there.
+pub fn amount_in_to_price(
+    reserve_in: u128,
+    reserve_out: u128,
+    fee_bps: u64,
+    price: u128,
+) -> Option<u128> {
+    // The marginal price after a net input of delta_in is
+    //
+    //   g * reserve_in * reserve_out / (reserve_in + delta_in)^2
+    //
+    // g being 1 - fee, so the price is reached at
+    //
+    //   delta_in = sqrt(reserve_in * reserve_out * g / price) - reserve_in
+    //
+    // and the fee comes on top of delta_in
+
+    let scaled = mul_div_floor(
+        reserve_out.checked_mul((10_000 - fee_bps) as u128)?,
+        1 << 64,
+        price.checked_mul(10_000)?,
+    )
+    .ok()?;
+
+    let net = i_sqrt(reserve_in.checked_mul(scaled)?).saturating_sub(reserve_in);
+
+    mul_div_floor(net, 10_000, (10_000 - fee_bps) as u128).ok()
+}
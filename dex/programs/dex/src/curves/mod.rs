@@ -0,0 +1,89 @@
+pub mod bins;
+pub mod concentrated;
+pub mod constant_product;
+pub mod multi;
+pub mod pegged;
+pub mod stable;
+pub mod weighted;
+
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::{Pool, PoolCurve};
+
+// Largest decimals difference between the two mints that can be normalized in a u128
+const MAX_DECIMALS_DIFFERENCE: u8 = 18;
+
+/// Scaling factors bringing both tokens to the same number of decimals
+pub fn decimal_multipliers(decimals_in: u8, decimals_out: u8) -> Result<(u128, u128)> {
+    require!(
+        decimals_in.abs_diff(decimals_out) <= MAX_DECIMALS_DIFFERENCE,
+        DEXError::InvalidCurveParameters
+    );
+
+    let decimals = decimals_in.max(decimals_out);
+
+    Ok((
+        10u128.pow((decimals - decimals_in) as u32),
+        10u128.pow((decimals - decimals_out) as u32),
+    ))
+}
+
+/// Amount of the output token paid for `amount_in` (already net of fees), according to the pool curve.
+/// `decimals` are the (input, output) mint decimals.
+pub fn amount_out(
+    pool: &Pool,
+    a_to_b: bool,
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    decimals: (u8, u8),
+    now: i64,
+) -> Result<u128> {
+    match pool.curve {
+        PoolCurve::ConstantProduct => {
+            constant_product::amount_out(amount_in, reserve_in, reserve_out)
+        }
+        PoolCurve::LiquidityBootstrapping {
+            start_weight_a_bps,
+            end_weight_a_bps,
+            start_ts,
+            end_ts,
+        } => {
+            let weight_a =
+                weighted::weight_at(start_weight_a_bps, end_weight_a_bps, start_ts, end_ts, now);
+            let weight_b = 10_000 - weight_a;
+
+            let (weight_in, weight_out) = if a_to_b {
+                (weight_a, weight_b)
+            } else {
+                (weight_b, weight_a)
+            };
+
+            weighted::amount_out(amount_in, reserve_in, reserve_out, weight_in, weight_out)
+        }
+        PoolCurve::Stable => {
+            let (decimals_in, decimals_out) = decimals;
+
+            stable::amount_out(
+                amount_in,
+                reserve_in,
+                reserve_out,
+                decimals_in,
+                decimals_out,
+            )
+        }
+        PoolCurve::Pegged { rate, .. } => {
+            let (decimals_in, decimals_out) = decimals;
+
+            pegged::amount_out(
+                a_to_b,
+                amount_in,
+                reserve_out,
+                rate,
+                decimals_in,
+                decimals_out,
+            )
+        }
+    }
+}
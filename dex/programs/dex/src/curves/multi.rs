@@ -0,0 +1,323 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::weighted::{self, POW_ERROR_DIVISOR};
+use crate::curves::MAX_DECIMALS_DIFFERENCE;
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor, pow, ONE};
+use crate::state::{MultiPool, MultiPoolCurve, PoolToken};
+
+pub const MAX_AMPLIFICATION: u64 = 1_000_000;
+
+// Newton's method converges in a handful of iterations, this only bounds the worst case
+const MAX_NEWTON_ITERATIONS: usize = 255;
+
+/// Scaling factors bringing every token of the pool to the same number of decimals
+pub fn multipliers(tokens: &[PoolToken]) -> Result<Vec<u128>> {
+    let decimals = tokens.iter().map(|token| token.decimals).max().unwrap_or(0);
+
+    tokens
+        .iter()
+        .map(|token| {
+            let difference = decimals - token.decimals;
+            require!(
+                difference <= MAX_DECIMALS_DIFFERENCE,
+                DEXError::InvalidCurveParameters
+            );
+
+            Ok(10u128.pow(difference as u32))
+        })
+        .collect()
+}
+
+/// Pool balances brought to the same decimals
+fn normalized_balances(tokens: &[PoolToken], multipliers: &[u128]) -> Result<Vec<u128>> {
+    tokens
+        .iter()
+        .zip(multipliers)
+        .map(|(token, multiplier)| {
+            (token.balance as u128)
+                .checked_mul(*multiplier)
+                .ok_or_else(|| error!(DEXError::MathOverflow))
+        })
+        .collect()
+}
+
+/// Part of `amount` that a single-token deposit or withdrawal implicitly swaps against
+/// the other tokens, which is what the swap fee is charged on
+pub fn fee_bearing_amount(pool: &MultiPool, index: usize, amount: u128) -> Result<u128> {
+    match pool.curve {
+        MultiPoolCurve::StableSwap { .. } => {
+            let balances = normalized_balances(&pool.tokens, &multipliers(&pool.tokens)?)?;
+            let total: u128 = balances.iter().sum();
+
+            if total == 0 {
+                return Ok(0);
+            }
+
+            mul_div_floor(amount, total - balances[index], total)
+        }
+        MultiPoolCurve::Weighted => {
+            let weight = pool.tokens[index].weight_bps as u128;
+
+            mul_div_floor(amount, 10_000 - weight, 10_000)
+        }
+    }
+}
+
+/// Amount of token `index_out` paid for `amount_in` of token `index_in` (already net of fees)
+pub fn swap_amount_out(
+    pool: &MultiPool,
+    index_in: usize,
+    index_out: usize,
+    amount_in: u128,
+) -> Result<u128> {
+    let token_in = &pool.tokens[index_in];
+    let token_out = &pool.tokens[index_out];
+
+    require!(
+        token_in.balance > 0 && token_out.balance > 0,
+        DEXError::EmptyPool
+    );
+
+    let amount_out = match pool.curve {
+        MultiPoolCurve::StableSwap { amplification } => {
+            let multipliers = multipliers(&pool.tokens)?;
+            let mut balances = normalized_balances(&pool.tokens, &multipliers)?;
+
+            let invariant = stable_invariant(&balances, amplification)?;
+
+            balances[index_in] = amount_in
+                .checked_mul(multipliers[index_in])
+                .and_then(|amount| amount.checked_add(balances[index_in]))
+                .ok_or(DEXError::MathOverflow)?;
+
+            let new_balance_out = stable_balance(index_out, &balances, amplification, invariant)?;
+
+            balances[index_out].saturating_sub(new_balance_out) / multipliers[index_out]
+        }
+        MultiPoolCurve::Weighted => weighted::amount_out(
+            amount_in,
+            token_in.balance as u128,
+            token_out.balance as u128,
+            token_in.weight_bps,
+            token_out.weight_bps,
+        )?,
+    };
+
+    require!(
+        amount_out < token_out.balance as u128,
+        DEXError::InsufficientReserves
+    );
+
+    Ok(amount_out)
+}
+
+/// LP tokens minted for depositing `amount_in` (already net of fees) of a single token
+pub fn single_token_deposit_liquidity(
+    pool: &MultiPool,
+    index: usize,
+    amount_in: u128,
+    total_lp_supply: u128,
+) -> Result<u128> {
+    let token = &pool.tokens[index];
+    require!(token.balance > 0, DEXError::EmptyPool);
+
+    match pool.curve {
+        MultiPoolCurve::StableSwap { amplification } => {
+            // The LP supply grows with the StableSwap invariant
+            let multipliers = multipliers(&pool.tokens)?;
+            let mut balances = normalized_balances(&pool.tokens, &multipliers)?;
+
+            let invariant_before = stable_invariant(&balances, amplification)?;
+
+            balances[index] = amount_in
+                .checked_mul(multipliers[index])
+                .and_then(|amount| amount.checked_add(balances[index]))
+                .ok_or(DEXError::MathOverflow)?;
+
+            let invariant_after = stable_invariant(&balances, amplification)?;
+
+            mul_div_floor(
+                total_lp_supply,
+                invariant_after.saturating_sub(invariant_before),
+                invariant_before,
+            )
+        }
+        MultiPoolCurve::Weighted => {
+            // Balancer's single asset join:
+            //
+            //   lp_out = supply * (((balance + amount_in) / balance) ^ weight - 1)
+            let balance = token.balance as u128;
+            let new_balance = balance
+                .checked_add(amount_in)
+                .ok_or(DEXError::MathOverflow)?;
+
+            let base = mul_div_floor(new_balance, ONE, balance)?;
+            let exponent = mul_div_floor(token.weight_bps as u128, ONE, 10_000)?;
+
+            let power = pow(base, exponent)?;
+            let power = power.saturating_sub(power / POW_ERROR_DIVISOR + 1);
+
+            mul_div_floor(total_lp_supply, power.saturating_sub(ONE), ONE)
+        }
+    }
+}
+
+/// Amount of a single token paid for burning `liquidity` LP tokens, before fees
+pub fn single_token_withdrawal_amount(
+    pool: &MultiPool,
+    index: usize,
+    liquidity: u128,
+    total_lp_supply: u128,
+) -> Result<u128> {
+    require!(
+        liquidity < total_lp_supply,
+        DEXError::InvalidAmountOfLiquidation
+    );
+
+    let token = &pool.tokens[index];
+
+    let amount_out = match pool.curve {
+        MultiPoolCurve::StableSwap { amplification } => {
+            // Shrink the invariant by the burnt share and solve for the token balance
+            let multipliers = multipliers(&pool.tokens)?;
+            let balances = normalized_balances(&pool.tokens, &multipliers)?;
+
+            let invariant = stable_invariant(&balances, amplification)?;
+            let new_invariant =
+                mul_div_ceil(invariant, total_lp_supply - liquidity, total_lp_supply)?;
+
+            let new_balance = stable_balance(index, &balances, amplification, new_invariant)?;
+
+            balances[index].saturating_sub(new_balance) / multipliers[index]
+        }
+        MultiPoolCurve::Weighted => {
+            // Balancer's single asset exit:
+            //
+            //   amount_out = balance * (1 - ((supply - lp_in) / supply) ^ (1 / weight))
+            let base = mul_div_ceil(total_lp_supply - liquidity, ONE, total_lp_supply)?;
+            let exponent = mul_div_floor(10_000, ONE, token.weight_bps as u128)?;
+
+            let power = pow(base, exponent)?;
+            let power = power
+                .checked_add(power / POW_ERROR_DIVISOR + 1)
+                .ok_or(DEXError::MathOverflow)?
+                .min(ONE);
+
+            mul_div_floor(token.balance as u128, ONE - power, ONE)?
+        }
+    };
+
+    require!(
+        amount_out < token.balance as u128,
+        DEXError::InsufficientReserves
+    );
+
+    Ok(amount_out)
+}
+
+/// StableSwap invariant D of normalized balances:
+///
+///   A * n^n * sum(x) + D = A * n^n * D + D^(n + 1) / (n^n * prod(x))
+pub fn stable_invariant(balances: &[u128], amplification: u64) -> Result<u128> {
+    let n = balances.len() as u128;
+    let sum = balances
+        .iter()
+        .try_fold(0u128, |sum, balance| sum.checked_add(*balance))
+        .ok_or(DEXError::MathOverflow)?;
+
+    if sum == 0 {
+        return Ok(0);
+    }
+
+    let ann = amplification_factor(amplification, n)?;
+    let ann_sum = ann.checked_mul(sum).ok_or(DEXError::MathOverflow)?;
+
+    let mut invariant = sum;
+
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        // D^(n + 1) / (n^n * prod(x)), one factor at a time
+        let mut d_p = invariant;
+        for balance in balances {
+            d_p = mul_div_floor(d_p, invariant, balance * n)?;
+        }
+
+        let previous = invariant;
+
+        let numerator = d_p
+            .checked_mul(n)
+            .and_then(|value| value.checked_add(ann_sum))
+            .ok_or(DEXError::MathOverflow)?;
+        let denominator = (ann - 1)
+            .checked_mul(invariant)
+            .and_then(|value| value.checked_add((n + 1).checked_mul(d_p)?))
+            .ok_or(DEXError::MathOverflow)?;
+
+        invariant = mul_div_floor(numerator, invariant, denominator)?;
+
+        if invariant.abs_diff(previous) <= 1 {
+            break;
+        }
+    }
+
+    Ok(invariant)
+}
+
+/// Balance of token `index` keeping the StableSwap invariant at `invariant` given every
+/// other balance, rounded up so that the pool never pays out the rounding
+fn stable_balance(
+    index: usize,
+    balances: &[u128],
+    amplification: u64,
+    invariant: u128,
+) -> Result<u128> {
+    let n = balances.len() as u128;
+    let ann = amplification_factor(amplification, n)?;
+
+    // Solves y^2 + (b - D) * y = c with
+    //   c = D^(n + 1) / (n^n * prod(other x) * A * n^n)
+    //   b = sum(other x) + D / (A * n^n)
+    let mut c = invariant;
+    let mut sum = 0u128;
+
+    for (other, balance) in balances.iter().enumerate() {
+        if other == index {
+            continue;
+        }
+
+        sum = sum.checked_add(*balance).ok_or(DEXError::MathOverflow)?;
+        c = mul_div_floor(c, invariant, balance * n)?;
+    }
+
+    c = mul_div_floor(c, invariant, ann * n)?;
+    let b = sum + invariant / ann;
+
+    let mut y = invariant;
+
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let previous = y;
+
+        // y = (y^2 + c) / (2y + b - D)
+        let denominator = (2 * y + b)
+            .checked_sub(invariant)
+            .filter(|denominator| *denominator > 0)
+            .ok_or(DEXError::MathOverflow)?;
+
+        y = mul_div_floor(y, y, denominator)? + c / denominator;
+
+        if y.abs_diff(previous) <= 1 {
+            break;
+        }
+    }
+
+    Ok(y + 1)
+}
+
+// A * n^n
+fn amplification_factor(amplification: u64, n: u128) -> Result<u128> {
+    require!(amplification > 0, DEXError::InvalidCurveParameters);
+
+    (amplification as u128)
+        .checked_mul(n.pow(n as u32))
+        .ok_or_else(|| error!(DEXError::MathOverflow))
+}
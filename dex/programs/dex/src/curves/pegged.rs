@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::decimal_multipliers;
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+
+/// `rate` of a pegged pool for a 1:1 peg
+pub const PEG_RATE_SCALE: u64 = 1_000_000_000;
+
+pub fn amount_out(
+    a_to_b: bool,
+    amount_in: u128,
+    reserve_out: u128,
+    rate: u64,
+    decimals_in: u8,
+    decimals_out: u8,
+) -> Result<u128> {
+    // Constant sum at a fixed rate, applied to amounts normalized to the same decimals:
+    //
+    //   A -> B: delta_out = amount_in * rate / PEG_RATE_SCALE
+    //   B -> A: delta_out = amount_in * PEG_RATE_SCALE / rate
+
+    require!(rate > 0, DEXError::InvalidCurveParameters);
+
+    let (multiplier_in, multiplier_out) = decimal_multipliers(decimals_in, decimals_out)?;
+
+    let normalized_in = amount_in
+        .checked_mul(multiplier_in)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let normalized_out = if a_to_b {
+        mul_div_floor(normalized_in, rate as u128, PEG_RATE_SCALE as u128)?
+    } else {
+        mul_div_floor(normalized_in, PEG_RATE_SCALE as u128, rate as u128)?
+    };
+
+    let amount_out = normalized_out / multiplier_out;
+
+    // Nothing bends the price of a constant sum pool, the swap has to stop before the vault runs dry
+    require!(amount_out < reserve_out, DEXError::InsufficientReserves);
+
+    Ok(amount_out)
+}
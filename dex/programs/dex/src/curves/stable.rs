@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::decimal_multipliers;
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor, ONE};
+
+// Newton's method converges in a handful of iterations, this only bounds the worst case
+const MAX_NEWTON_ITERATIONS: usize = 255;
+
+pub fn amount_out(
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    decimals_in: u8,
+    decimals_out: u8,
+) -> Result<u128> {
+    // Solidly's stable invariant:
+    //
+    //   k = x^3 * y + x * y^3
+    //
+    // with both reserves brought to the same decimals. The invariant is homogeneous
+    // (scaling both reserves by t scales k by t^4), so every value is divided by
+    // `scale` = new reserve in + reserve out, which keeps them below ONE and lets
+    // the whole computation fit in a u128.
+
+    require!(reserve_in > 0 && reserve_out > 0, DEXError::EmptyPool);
+
+    let (multiplier_in, multiplier_out) = decimal_multipliers(decimals_in, decimals_out)?;
+
+    let x0 = reserve_in
+        .checked_mul(multiplier_in)
+        .ok_or(DEXError::MathOverflow)?;
+    let y0 = reserve_out
+        .checked_mul(multiplier_out)
+        .ok_or(DEXError::MathOverflow)?;
+    let x1 = amount_in
+        .checked_mul(multiplier_in)
+        .and_then(|amount| amount.checked_add(x0))
+        .ok_or(DEXError::MathOverflow)?;
+
+    let scale = x1.checked_add(y0).ok_or(DEXError::MathOverflow)?;
+
+    let x0 = mul_div_floor(x0, ONE, scale)?;
+    let y0 = mul_div_floor(y0, ONE, scale)?;
+    let x1 = mul_div_floor(x1, ONE, scale)?;
+
+    let k = invariant(x0, y0);
+    let y1 = solve_y(x1, k, y0)?;
+
+    // Every normalized value carries up to one unit of rounding, worth `scale / ONE`
+    // in token terms. Twice that is kept by the pool so the trader never benefits from it.
+    let rounding = 2 * (scale / ONE + 1);
+    let amount_out = mul_div_floor(y0.saturating_sub(y1), scale, ONE)?.saturating_sub(rounding);
+
+    Ok(amount_out / multiplier_out)
+}
+
+// x^3 * y + x * y^3 = x * y * (x^2 + y^2), for values scaled by ONE
+fn invariant(x: u128, y: u128) -> u128 {
+    let xy = x * y / ONE;
+    let squares = x * x / ONE + y * y / ONE;
+
+    xy * squares / ONE
+}
+
+// d(invariant) / dy = x^3 + 3 * x * y^2
+fn derivative(x: u128, y: u128) -> u128 {
+    let x_cubed = x * x / ONE * x / ONE;
+
+    x_cubed + 3 * x * (y * y / ONE) / ONE
+}
+
+/// Smallest y with invariant(x, y) >= k, found with Newton's method starting from `y`
+fn solve_y(x: u128, k: u128, mut y: u128) -> Result<u128> {
+    require!(x > 0, DEXError::EmptyPool);
+
+    for _ in 0..MAX_NEWTON_ITERATIONS {
+        let current = invariant(x, y);
+        let slope = derivative(x, y);
+
+        let previous = y;
+
+        if current < k {
+            y += mul_div_ceil(k - current, ONE, slope)?;
+        } else {
+            y -= mul_div_floor(current - k, ONE, slope)?.min(y);
+        }
+
+        if y.abs_diff(previous) <= 1 {
+            break;
+        }
+    }
+
+    // Newton stops within a unit of the root, settle on the side that favours the pool
+    while invariant(x, y) < k {
+        y += 1;
+    }
+
+    Ok(y)
+}
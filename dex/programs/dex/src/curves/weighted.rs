@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor, pow, ONE};
+
+// `pow` is accurate to roughly 1e-10, the result is bumped by this fraction
+// so that rounding errors are always paid by the trader and never by the pool
+pub const POW_ERROR_DIVISOR: u128 = 1_000_000_000;
+
+/// Linearly interpolated weight (in bps) of a schedule at `now`, clamped to the schedule ends
+pub fn weight_at(start_weight: u64, end_weight: u64, start_ts: i64, end_ts: i64, now: i64) -> u64 {
+    if now <= start_ts {
+        return start_weight;
+    }
+
+    if now >= end_ts {
+        return end_weight;
+    }
+
+    let elapsed = (now - start_ts) as u128;
+    let duration = (end_ts - start_ts) as u128;
+
+    if end_weight >= start_weight {
+        let shift = (end_weight - start_weight) as u128 * elapsed / duration;
+        start_weight + shift as u64
+    } else {
+        let shift = (start_weight - end_weight) as u128 * elapsed / duration;
+        start_weight - shift as u64
+    }
+}
+
+pub fn amount_out(
+    amount_in: u128,
+    reserve_in: u128,
+    reserve_out: u128,
+    weight_in: u64,
+    weight_out: u64,
+) -> Result<u128> {
+    // Balancer's out-given-in:
+    //
+    //   delta_out = reserve_out * (1 - (reserve_in / (reserve_in + amount_in)) ^ (weight_in / weight_out))
+    //
+    // which reduces to the constant product formula for equal weights
+
+    require!(
+        weight_in > 0 && weight_out > 0,
+        DEXError::InvalidCurveParameters
+    );
+
+    let new_reserve_in = reserve_in
+        .checked_add(amount_in)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let base = mul_div_ceil(reserve_in, ONE, new_reserve_in)?;
+    let exponent = mul_div_floor(weight_in as u128, ONE, weight_out as u128)?;
+
+    let power = pow(base, exponent)?;
+    let power = power
+        .checked_add(power / POW_ERROR_DIVISOR + 1)
+        .ok_or(DEXError::MathOverflow)?
+        .min(ONE);
+
+    mul_div_floor(reserve_out, ONE - power, ONE)
+}
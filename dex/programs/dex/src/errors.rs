@@ -31,4 +31,211 @@ pub enum DEXError {
 
     #[msg("Slippage exceeded")]
     SlippageExceeded,
+
+    #[msg("Invalid curve parameters")]
+    InvalidCurveParameters,
+
+    #[msg("The mint is not part of this pool")]
+    MintNotInPool,
+
+    #[msg("Only the pool creator can do this")]
+    Unauthorized,
+
+    #[msg("The pool is not a liquidity bootstrapping pool")]
+    NotLiquidityBootstrappingPool,
+
+    #[msg("The bootstrapping sale has not ended yet")]
+    SaleNotEnded,
+
+    #[msg("The bootstrapping sale is already finalized")]
+    SaleAlreadyFinalized,
+
+    #[msg("Invalid tick index")]
+    InvalidTickIndex,
+
+    #[msg("Invalid sqrt price")]
+    InvalidSqrtPrice,
+
+    #[msg("The tick array does not belong to this pool, does not cover the tick or does not follow the previous one")]
+    InvalidTickArray,
+
+    #[msg("The position does not belong to this pool")]
+    InvalidPosition,
+
+    #[msg("Not enough liquidity in the position")]
+    InsufficientPositionLiquidity,
+
+    #[msg("Invalid bin id")]
+    InvalidBinId,
+
+    #[msg("The bin array does not belong to this pool or is missing")]
+    InvalidBinArray,
+
+    #[msg("A withdrawal still has to be paid out of the remaining bin arrays")]
+    BinWithdrawalPending,
+
+    #[msg("The bin pool has no withdrawal left to pay out")]
+    NoBinWithdrawal,
+
+    #[msg("The swap would empty the vault of the output token")]
+    InsufficientReserves,
+
+    #[msg("The swap exceeds what is left of the cap for this direction in this slot")]
+    SwapCapExceeded,
+
+    #[msg("The pool is not a pegged pool")]
+    NotPeggedPool,
+
+    #[msg("The token accounts do not match the pool tokens")]
+    InvalidTokenAccounts,
+
+    #[msg("Invalid dynamic fee configuration")]
+    InvalidFeeConfig,
+
+    #[msg("Only the config admin can do this")]
+    NotConfigAdmin,
+
+    #[msg("The referral fee share exceeds the global cap")]
+    ReferralShareTooHigh,
+
+    #[msg("Paying a referrer requires the global config account")]
+    MissingGlobalConfig,
+
+    #[msg("A trader cannot refer their own swaps")]
+    SelfReferral,
+
+    #[msg("Fee tiers must be sorted by volume with non-decreasing discounts")]
+    InvalidFeeTiers,
+
+    #[msg("The trader stats do not belong to this trader and pool")]
+    InvalidTraderStats,
+
+    #[msg("The order book has no free slot")]
+    OrderBookFull,
+
+    #[msg("No such order of this owner")]
+    OrderNotFound,
+
+    #[msg("The order book or its escrow accounts do not belong to this pool")]
+    InvalidOrderBook,
+
+    #[msg("Limit orders need a non-zero amount and price")]
+    InvalidLimitOrder,
+
+    #[msg("The TWAMM of the pool or its escrow accounts are missing or do not belong to it")]
+    InvalidTwamm,
+
+    #[msg("Long-term orders need a non-zero amount and a duration within the TWAMM horizon")]
+    InvalidLongTermOrder,
+
+    #[msg("Too many distinct long-term order expiries")]
+    TwammExpiriesFull,
+
+    #[msg("DCA orders need a non-zero deposit, amount per cycle and interval")]
+    InvalidDcaOrder,
+
+    #[msg("The next DCA cycle is not due yet")]
+    DcaCycleNotDue,
+
+    #[msg("The DCA order has nothing left to swap")]
+    DcaOrderCompleted,
+
+    #[msg("Trigger orders need a non-zero amount and trigger price")]
+    InvalidTriggerOrder,
+
+    #[msg("The pool price has not crossed the trigger price")]
+    TriggerNotReached,
+
+    #[msg("The pool has not recorded prices for a full TWAP window yet")]
+    TwapUnavailable,
+
+    #[msg("RFQ quotes need non-zero amounts and the mints of the traded accounts")]
+    InvalidRfqQuote,
+
+    #[msg("The RFQ quote has expired")]
+    RfqQuoteExpired,
+
+    #[msg("The message is not signed by the expected key in the preceding Ed25519 instruction")]
+    InvalidSignature,
+
+    #[msg("RFQ nonces can only be closed once their quote expired")]
+    RfqNonceInUse,
+
+    #[msg("The intent deadline has passed")]
+    IntentExpired,
+
+    #[msg("The relayer tip is above the cap")]
+    RelayerTipTooHigh,
+
+    #[msg("Intent nonces can only be closed once their deadline passed")]
+    IntentNonceInUse,
+
+    #[msg("Only constant product pools support this")]
+    ConstantProductOnly,
+
+    #[msg("The batch auction or its escrow accounts do not belong to this pool")]
+    InvalidBatchAuction,
+
+    #[msg("Swaps on batch auction pools only execute through settle_batch")]
+    BatchAuctionPool,
+
+    #[msg("The batch of a previous slot has to be settled first")]
+    BatchNotSettled,
+
+    #[msg("The batch of this slot is full")]
+    BatchFull,
+
+    #[msg("The batch can only be settled once its slot is over")]
+    BatchSlotNotOver,
+
+    #[msg("There are no queued orders to settle")]
+    EmptyBatch,
+
+    #[msg("Market orders need a non-zero amount and price")]
+    InvalidMarketOrder,
+
+    #[msg("Market orders may not cross the other side of the book")]
+    MarketOrderCrosses,
+
+    #[msg("This side of the market has no free slot")]
+    MarketFull,
+
+    #[msg("The farm vaults, reward or stake do not belong to this farm")]
+    InvalidFarm,
+
+    #[msg("Staked and unstaked amounts must be greater than zero")]
+    ZeroStakeAmount,
+
+    #[msg("The farm has no free reward slot")]
+    FarmRewardsFull,
+
+    #[msg("Reward schedules must end after they start and in the future, and can only be extended at the same or a higher rate")]
+    InvalidRewardSchedule,
+
+    #[msg("LP positions are only available on constant product pools")]
+    LpPositionUnsupported,
+
+    #[msg("Only the holder of the position NFT may act on the position")]
+    NotPositionHolder,
+
+    #[msg("The position has separated fees left to collect")]
+    UncollectedLpFees,
+
+    #[msg("Liquidity must be locked for a positive amount until a future time")]
+    InvalidLiquidityLock,
+
+    #[msg("No locked liquidity is unlocked yet")]
+    NothingToUnlock,
+
+    #[msg("The first transaction of a bin withdrawal has to be paid out of more bin arrays")]
+    BinWithdrawalTooFewArrays,
+
+    #[msg("Referral fees are only paid to token accounts of registered referrers")]
+    UnregisteredReferrer,
+
+    #[msg("The long-term order has not expired yet")]
+    LongTermOrderActive,
+
+    #[msg("The batch order is below the minimum amount of its input token")]
+    BatchOrderTooSmall,
 }
@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    /// Input taken from the trader, fee included
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee_amount: u64,
+    /// Fee rate actually charged: the pool's fee for the swap direction, raised by dynamic
+    /// or impact fees and lowered by volume tier discounts
+    pub fee_bps: u64,
+}
+
+#[event]
+pub struct ReferralFeeEvent {
+    pub pool: Pubkey,
+    /// Owner of the token account the referral fee was paid to
+    pub referrer: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct LimitOrderFilledEvent {
+    pub pool: Pubkey,
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct DcaCycleExecutedEvent {
+    pub pool: Pubkey,
+    pub dca_order: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Paid to the keeper in the input token, on top of `amount_in`
+    pub keeper_tip: u64,
+}
+
+#[event]
+pub struct TriggerOrderExecutedEvent {
+    pub pool: Pubkey,
+    pub trigger_order: Pubkey,
+    pub owner: Pubkey,
+    pub keeper: Pubkey,
+    /// Spot or TWAP price that crossed the trigger, token B per token A in Q64.64
+    pub price: u128,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    /// Paid to the keeper in the input token, on top of `amount_in`
+    pub keeper_tip: u64,
+}
+
+#[event]
+pub struct RfqFilledEvent {
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub nonce: u64,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    /// Filled by the maker, the rest of the taker input went to the pool or was not sold
+    pub amount_in: u64,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct IntentExecutedEvent {
+    pub owner: Pubkey,
+    pub relayer: Pubkey,
+    pub nonce: u64,
+    pub amount_in: u64,
+    /// Received by the owner, after the relayer tip
+    pub amount_out: u64,
+    /// Paid to the relayer in the output token
+    pub relayer_tip: u64,
+}
+
+#[event]
+pub struct BatchOrderQueuedEvent {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub slot: u64,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+}
+
+#[event]
+pub struct BatchSettledEvent {
+    pub pool: Pubkey,
+    pub slot: u64,
+    /// Token B per token A in Q64.64, shared by every filled order
+    pub clearing_price: u128,
+    pub amount_a_in: u64,
+    pub amount_b_in: u64,
+    pub fee_amount: u64,
+    pub orders_filled: u8,
+    /// Orders whose minimum output the clearing price missed, refunded instead
+    pub orders_refunded: u8,
+}
+
+#[event]
+pub struct MarketOrderFilledEvent {
+    pub pool: Pubkey,
+    pub order_id: u64,
+    pub owner: Pubkey,
+    pub bid: bool,
+    /// Paid by the taker to the maker
+    pub amount_in: u64,
+    /// Paid by the maker to the taker
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct LpStakedEvent {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct LpUnstakedEvent {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct FarmRewardsClaimedEvent {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub reward_mint: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FarmRewardFundedEvent {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub funder: Pubkey,
+    pub amount: u64,
+    pub end_ts: i64,
+}
+
+#[event]
+pub struct FarmRewardExtendedEvent {
+    pub farm: Pubkey,
+    pub reward_mint: Pubkey,
+    pub end_ts: i64,
+    pub reward_per_second: u64,
+}
+
+#[event]
+pub struct LpPositionOpenedEvent {
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct LpPositionClosedEvent {
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    pub owner: Pubkey,
+    pub liquidity: u64,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Part of the amounts earned as swap fees
+    pub fees_a: u64,
+    pub fees_b: u64,
+}
+
+#[event]
+pub struct LpFeesCollectedEvent {
+    pub pool: Pubkey,
+    pub position_mint: Pubkey,
+    pub owner: Pubkey,
+    pub fees_a: u64,
+    pub fees_b: u64,
+}
+
+#[event]
+pub struct LiquidityLockedEvent {
+    pub pool: Pubkey,
+    pub liquidity_lock: Pubkey,
+    pub owner: Pubkey,
+    pub lp_amount: u64,
+    pub unlock_ts: i64,
+    pub linear_vesting: bool,
+}
+
+#[event]
+pub struct LiquidityUnlockedEvent {
+    pub pool: Pubkey,
+    pub liquidity_lock: Pubkey,
+    pub owner: Pubkey,
+    pub lp_amount: u64,
+    /// LP tokens still locked
+    pub remaining: u64,
+}
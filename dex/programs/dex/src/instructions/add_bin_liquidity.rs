@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, transfer, MintTo, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::{BIN_POOL_SEED, MAX_BINS_PER_DEPOSIT};
+use crate::curves::bins::{bin_price, shape_weights};
+use crate::errors::DEXError;
+use crate::math::{mul_div_floor, mul_shr};
+use crate::state::{BinArray, BinPool, LiquidityShape};
+use crate::utils::get_bin_pool_signer_seeds;
+
+/// Spreads the deposit over [lower_bin_id, upper_bin_id] following `shape`. The bin arrays
+/// covering the range are passed as remaining accounts. LP tokens are minted for the value
+/// of the deposit at the active bin price, relative to the value of the whole pool.
+pub fn add_bin_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddBinLiquidity<'info>>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+    lower_bin_id: i32,
+    upper_bin_id: i32,
+    shape: LiquidityShape,
+) -> Result<()> {
+    require!(
+        lower_bin_id <= upper_bin_id && upper_bin_id - lower_bin_id < MAX_BINS_PER_DEPOSIT,
+        DEXError::InvalidBinId
+    );
+
+    let pool = &mut ctx.accounts.bin_pool;
+    require!(!pool.withdrawal_pending(), DEXError::BinWithdrawalPending);
+
+    let pool_key = pool.key();
+    let active_bin_id = pool.active_bin_id;
+
+    let weights = shape_weights(shape, lower_bin_id, upper_bin_id, active_bin_id);
+    let total_weight_a: u64 = weights.iter().map(|(_, weight_a, _)| weight_a).sum();
+    let total_weight_b: u64 = weights.iter().map(|(_, _, weight_b)| weight_b).sum();
+
+    let loaders = ctx
+        .remaining_accounts
+        .iter()
+        .map(AccountLoader::<BinArray>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut bin_arrays = Vec::with_capacity(loaders.len());
+    for loader in loaders.iter() {
+        let bin_array = loader.load_mut()?;
+        require_keys_eq!(bin_array.pool, pool_key, DEXError::InvalidBinArray);
+        bin_arrays.push(bin_array);
+    }
+
+    let mut deposited_a = 0u64;
+    let mut deposited_b = 0u64;
+
+    for (bin_id, weight_a, weight_b) in weights {
+        let amount_a = if weight_a > 0 {
+            mul_div_floor(
+                token_a_amount as u128,
+                weight_a as u128,
+                total_weight_a as u128,
+            )? as u64
+        } else {
+            0
+        };
+        let amount_b = if weight_b > 0 {
+            mul_div_floor(
+                token_b_amount as u128,
+                weight_b as u128,
+                total_weight_b as u128,
+            )? as u64
+        } else {
+            0
+        };
+
+        if amount_a == 0 && amount_b == 0 {
+            continue;
+        }
+
+        let bin_array = bin_arrays
+            .iter_mut()
+            .find(|bin_array| bin_array.contains(bin_id))
+            .ok_or(DEXError::InvalidBinArray)?;
+
+        pool.bin_array_bitmap |= BinPool::bin_array_bit(bin_array.index)?;
+
+        let bin = bin_array.bin_mut(bin_id)?;
+        bin.amount_a = bin
+            .amount_a
+            .checked_add(amount_a)
+            .ok_or(DEXError::MathOverflow)?;
+        bin.amount_b = bin
+            .amount_b
+            .checked_add(amount_b)
+            .ok_or(DEXError::MathOverflow)?;
+
+        deposited_a += amount_a;
+        deposited_b += amount_b;
+    }
+
+    drop(bin_arrays);
+
+    // Everything is valued in token B at the active bin price
+    let price = bin_price(active_bin_id, pool.bin_step)?;
+    let deposit_value = mul_shr(deposited_a as u128, price, 64)?
+        .checked_add(deposited_b as u128)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+    let liquidity = if total_lp_supply == 0 {
+        deposit_value
+    } else {
+        let pool_value = mul_shr(ctx.accounts.vault_a.amount as u128, price, 64)?
+            .checked_add(ctx.accounts.vault_b.amount as u128)
+            .ok_or(DEXError::MathOverflow)?;
+
+        mul_div_floor(deposit_value, total_lp_supply as u128, pool_value)?
+    };
+
+    require!(liquidity > 0, DEXError::InvalidAmountOfLiquidation);
+
+    let signer_seeds = get_bin_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    if deposited_a > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_a_account.to_account_info(),
+                    to: ctx.accounts.vault_a.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            deposited_a,
+        )?;
+    }
+
+    if deposited_b > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_token_b_account.to_account_info(),
+                    to: ctx.accounts.vault_b.to_account_info(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            deposited_b,
+        )?;
+    }
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_tokens_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        u64::try_from(liquidity).map_err(|_| DEXError::MathOverflow)?,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddBinLiquidity<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BIN_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        has_one = vault_a,
+        has_one = vault_b,
+        has_one = lp_mint
+    )]
+    pub bin_pool: Account<'info, BinPool>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer
+    )]
+    pub user_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = signer
+    )]
+    pub user_token_a_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = signer
+    )]
+    pub user_token_b_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::FARM_SEED;
+use crate::errors::DEXError;
+use crate::state::{Farm, FarmReward, Pool, MAX_FARM_REWARDS};
+
+/// Schedules a new reward on the farm, emitting `reward_per_second` of `reward_mint` over
+/// the unix times [start_ts, end_ts) once `funder` deposits it through `fund_reward`
+pub fn add_farm_reward(
+    ctx: Context<AddFarmReward>,
+    funder: Pubkey,
+    reward_per_second: u64,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        start_ts < end_ts && now < end_ts,
+        DEXError::InvalidRewardSchedule
+    );
+
+    let farm = &mut ctx.accounts.farm;
+    require!(
+        farm.rewards.len() < MAX_FARM_REWARDS,
+        DEXError::FarmRewardsFull
+    );
+
+    farm.update(now)?;
+    farm.rewards.push(FarmReward {
+        mint: ctx.accounts.reward_mint.key(),
+        vault: ctx.accounts.reward_vault.key(),
+        funder,
+        reward_per_second,
+        start_ts,
+        end_ts,
+        reward_per_share: 0,
+        amount_funded: 0,
+        amount_emitted: 0,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddFarmReward<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, liquidity_pool.key().as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        constraint = reward_mint.key() != farm.lp_mint @ DEXError::InvalidFarm
+    )]
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    /// Fails to be created for a mint the farm already rewards
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm,
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
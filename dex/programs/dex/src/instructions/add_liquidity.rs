@@ -6,8 +6,8 @@ use anchor_spl::token_interface::{Mint, TokenAccount};
 use crate::errors::DEXError;
 use crate::{
     constants::LIQUIDITY_POOL_SEED,
-    state::Pool,
-    utils::{get_pool_signer_seeds, i_sqrt},
+    state::{Pool, Twamm},
+    utils::{calculate_deposit_amounts, get_pool_signer_seeds, settle_twamm},
 };
 
 pub fn add_liquidity_to_pool(
@@ -15,68 +15,39 @@ pub fn add_liquidity_to_pool(
     token_a_amount: u64,
     token_b_amount: u64,
 ) -> Result<()> {
+    if ctx.accounts.liquidity_pool.is_creator_only_liquidity() {
+        require_keys_eq!(
+            ctx.accounts.signer.key(),
+            ctx.accounts.liquidity_pool.admin,
+            DEXError::Unauthorized
+        );
+    }
+
+    // Long-term orders are executed up to now before the deposit is priced
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        (&mut ctx.accounts.vault_a, &mut ctx.accounts.vault_b),
+        &ctx.accounts.token_program.to_account_info(),
+        (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals),
+        Clock::get()?.unix_timestamp,
+    )?;
+
     let total_lp_supply = ctx.accounts.lp_mint.supply;
-    let total_a = ctx.accounts.vault_a.amount;
-    let total_b = ctx.accounts.vault_b.amount;
-    let mut is_initial = true;
-
-    let (a_amount, b_amount) = if total_lp_supply != 0 {
-        is_initial = false;
-
-        let mut token_a_final_amount = token_a_amount as u128;
-        let mut token_b_final_amount = token_b_amount as u128;
-
-        let proportional_b = token_a_final_amount
-            .checked_mul(total_b as u128)
-            .ok_or(DEXError::MathOverflow)?
-            .checked_div(total_a as u128)
-            .ok_or(DEXError::MathOverflow)?;
-
-        if proportional_b < token_b_final_amount {
-            let proportional_a = token_b_final_amount
-                .checked_mul(total_a as u128)
-                .ok_or(DEXError::MathOverflow)?
-                .checked_div(total_b as u128)
-                .ok_or(DEXError::MathOverflow)?;
-
-            if proportional_a < token_a_final_amount {
-                return err!(DEXError::InvalidAmountOfLiquidation);
-            }
-
-            token_a_final_amount = proportional_a;
-        } else {
-            token_b_final_amount = proportional_b;
-        }
-
-        (token_a_final_amount, token_b_final_amount)
-    } else {
-        // initial deposit does not require checking
-        (token_a_amount as u128, token_b_amount as u128)
-    };
-
-    let product = a_amount
-        .checked_mul(b_amount)
-        .ok_or(DEXError::MathOverflow)?;
-
-    // NOTE: Minimum liquidity sent to an unusable address (like the system's address)
-    // to prevent inflation attacks
-    let liquidity = if is_initial {
-        i_sqrt(product)
-    } else {
-        let a_anchored = a_amount
-            .checked_mul(total_lp_supply as u128)
-            .ok_or(DEXError::MathOverflow)?
-            .checked_div(ctx.accounts.vault_a.amount as u128)
-            .ok_or(DEXError::MathOverflow)?;
-
-        let b_anchored = b_amount
-            .checked_mul(total_lp_supply as u128)
-            .ok_or(DEXError::MathOverflow)?
-            .checked_div(ctx.accounts.vault_b.amount as u128)
-            .ok_or(DEXError::MathOverflow)?;
-
-        a_anchored.min(b_anchored)
-    };
+    let pool = &ctx.accounts.liquidity_pool;
+    let total_a = pool.curve_reserve(true, ctx.accounts.vault_a.amount);
+    let total_b = pool.curve_reserve(false, ctx.accounts.vault_b.amount);
+    let (a_amount, b_amount, liquidity) = calculate_deposit_amounts(
+        token_a_amount,
+        token_b_amount,
+        total_lp_supply,
+        total_a,
+        total_b,
+    )?;
 
     let mint_a_key = ctx.accounts.mint_a.key();
     let mint_b_key = ctx.accounts.mint_b.key();
@@ -136,6 +107,7 @@ pub struct AddLiquidityToPool<'info> {
     pub mint_b: InterfaceAccount<'info, Mint>,
 
     #[account(
+        mut,
         seeds = [LIQUIDITY_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
         bump,
         has_one = vault_a,
@@ -187,6 +159,17 @@ pub struct AddLiquidityToPool<'info> {
     )]
     pub user_token_b_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
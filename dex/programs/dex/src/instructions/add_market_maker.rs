@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{GLOBAL_CONFIG_SEED, MARKET_MAKER_SEED};
+use crate::errors::DEXError;
+use crate::state::{GlobalConfig, MarketMaker};
+
+/// Whitelists `maker` to fill RFQ quotes it signed
+pub fn add_market_maker(ctx: Context<AddMarketMaker>, maker: Pubkey) -> Result<()> {
+    let market_maker = &mut ctx.accounts.market_maker;
+    market_maker.maker = maker;
+    market_maker.bump = ctx.bumps.market_maker;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(maker: Pubkey)]
+pub struct AddMarketMaker<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        constraint = global_config.admin == admin.key() @ DEXError::NotConfigAdmin
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = MarketMaker::MAX_SIZE,
+        seeds = [MARKET_MAKER_SEED, maker.as_ref()],
+        bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    pub system_program: Program<'info, System>,
+}
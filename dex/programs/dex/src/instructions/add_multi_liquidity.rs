@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, transfer, MintTo, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::curves::multi::multipliers;
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor};
+use crate::state::MultiPool;
+use crate::utils::{get_multi_pool_signer_seeds, multi_pool_token_accounts};
+
+/// Proportional deposit of every pool token. `max_amounts` bounds what is taken of each
+/// token (in pool order), the (user token account, vault) pairs are passed as remaining
+/// accounts. The first deposit sets the pool balances and has to include every token.
+pub fn add_multi_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, AddMultiLiquidity<'info>>,
+    max_amounts: Vec<u64>,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.multi_pool;
+
+    require!(
+        max_amounts.len() == pool.tokens.len(),
+        DEXError::InvalidAmountOfLiquidation
+    );
+
+    let token_accounts =
+        multi_pool_token_accounts(pool, &ctx.accounts.signer.key(), ctx.remaining_accounts)?;
+
+    let total_lp_supply = ctx.accounts.lp_mint.supply as u128;
+
+    let (liquidity, amounts) = if total_lp_supply == 0 {
+        require!(
+            max_amounts.iter().all(|amount| *amount > 0),
+            DEXError::InvalidAmountOfLiquidation
+        );
+
+        // The initial LP supply is the sum of the normalized amounts,
+        // which is also the StableSwap invariant of a balanced pool
+        let liquidity = max_amounts
+            .iter()
+            .zip(multipliers(&pool.tokens)?)
+            .try_fold(0u128, |sum, (amount, multiplier)| {
+                sum.checked_add((*amount as u128).checked_mul(multiplier)?)
+            })
+            .ok_or(DEXError::MathOverflow)?;
+
+        (liquidity, max_amounts.clone())
+    } else {
+        // The scarcest token relative to the pool sets the share, the rest is taken pro rata
+        let mut liquidity = u128::MAX;
+        for (token, amount) in pool.tokens.iter().zip(&max_amounts) {
+            liquidity = liquidity.min(mul_div_floor(
+                *amount as u128,
+                total_lp_supply,
+                token.balance as u128,
+            )?);
+        }
+
+        let amounts = pool
+            .tokens
+            .iter()
+            .map(|token| {
+                Ok(mul_div_ceil(token.balance as u128, liquidity, total_lp_supply)? as u64)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        (liquidity, amounts)
+    };
+
+    require!(liquidity > 0, DEXError::InvalidAmountOfLiquidation);
+
+    for (index, amount) in amounts.iter().enumerate() {
+        require!(
+            *amount <= max_amounts[index],
+            DEXError::InvalidAmountOfLiquidation
+        );
+
+        let (user_token_account, vault) = token_accounts[index];
+
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: user_token_account.clone(),
+                    to: vault.clone(),
+                    authority: ctx.accounts.signer.to_account_info(),
+                },
+            ),
+            *amount,
+        )?;
+
+        pool.tokens[index].balance = pool.tokens[index]
+            .balance
+            .checked_add(*amount)
+            .ok_or(DEXError::MathOverflow)?;
+    }
+
+    let signer_seeds = get_multi_pool_signer_seeds(&pool.lp_mint, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_tokens_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        u64::try_from(liquidity).map_err(|_| DEXError::MathOverflow)?,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddMultiLiquidity<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = lp_mint
+    )]
+    pub multi_pool: Account<'info, MultiPool>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer
+    )]
+    pub user_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{mint_to, transfer, MintTo, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::curves::multi::{fee_bearing_amount, single_token_deposit_liquidity};
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::MultiPool;
+use crate::utils::get_multi_pool_signer_seeds;
+
+/// Deposits a single token of a multi-asset pool. The part of the deposit that is
+/// implicitly swapped against the other tokens pays the swap fee.
+pub fn add_single_token_liquidity(
+    ctx: Context<AddSingleTokenLiquidity>,
+    amount_in: u64,
+    min_lp_tokens_amount: u64,
+) -> Result<()> {
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+    require!(total_lp_supply > 0, DEXError::EmptyPool);
+
+    let pool = &mut ctx.accounts.multi_pool;
+    let index = pool.token_index(&ctx.accounts.mint.key())?;
+
+    require_keys_eq!(
+        ctx.accounts.vault.key(),
+        pool.tokens[index].vault,
+        DEXError::WrongVaultSpecified
+    );
+
+    let fee_amount = mul_div_floor(
+        fee_bearing_amount(pool, index, amount_in as u128)?,
+        pool.fee_bps as u128,
+        10_000,
+    )?;
+    let amount_in_net = amount_in as u128 - fee_amount;
+
+    let liquidity =
+        single_token_deposit_liquidity(pool, index, amount_in_net, total_lp_supply as u128)?;
+    let liquidity = u64::try_from(liquidity).map_err(|_| DEXError::MathOverflow)?;
+
+    require!(liquidity > 0, DEXError::InvalidAmountOfLiquidation);
+    require!(
+        liquidity >= min_lp_tokens_amount,
+        DEXError::SlippageExceeded
+    );
+
+    pool.tokens[index].balance = pool.tokens[index]
+        .balance
+        .checked_add(amount_in)
+        .ok_or(DEXError::MathOverflow)?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_token_account.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    let signer_seeds = get_multi_pool_signer_seeds(&pool.lp_mint, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.user_lp_tokens_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        liquidity,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AddSingleTokenLiquidity<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = lp_mint
+    )]
+    pub multi_pool: Account<'info, MultiPool>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = multi_pool
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer
+    )]
+    pub user_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TWAMM_SEED;
+use crate::errors::DEXError;
+use crate::state::{LongTermOrder, Pool, Twamm};
+use crate::utils::{execute_twamm, get_twamm_signer_seeds, pay_out_long_term_order};
+
+/// Stops a long-term order, paying out its proceeds so far along with the unsold input
+pub fn cancel_long_term_order(ctx: Context<CancelLongTermOrder>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let decimals = (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals);
+
+    execute_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        &ctx.accounts.twamm,
+        (&ctx.accounts.vault_a, &ctx.accounts.vault_b),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let ((proceeds, unsold), bump) = {
+        let mut twamm = ctx.accounts.twamm.load_mut()?;
+        (
+            twamm.remove_order(&ctx.accounts.long_term_order)?,
+            twamm.bump,
+        )
+    };
+
+    let pool_key = ctx.accounts.liquidity_pool.key();
+    let signer_seeds = get_twamm_signer_seeds(&pool_key, &bump);
+
+    pay_out_long_term_order(
+        (&ctx.accounts.twamm.to_account_info(), &signer_seeds),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        (
+            &ctx.accounts.owner_token_account_a,
+            &ctx.accounts.owner_token_account_b,
+        ),
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.long_term_order.a_to_b,
+        (proceeds, unsold),
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelLongTermOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub twamm: AccountLoader<'info, Twamm>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = twamm
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = twamm
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = owner @ DEXError::OrderNotFound,
+        has_one = twamm @ DEXError::OrderNotFound,
+        close = owner,
+    )]
+    pub long_term_order: Account<'info, LongTermOrder>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::ORDER_BOOK_SEED;
+use crate::errors::DEXError;
+use crate::state::{LimitOrder, OrderBook, Pool};
+use crate::utils::get_order_book_signer_seeds;
+
+/// Closes an order, returning its unfilled input along with any unclaimed fills
+pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+    let pool = &ctx.accounts.liquidity_pool;
+
+    let (order, bump) = {
+        let mut order_book = ctx.accounts.order_book.load_mut()?;
+        let order = order_book.order_mut(order_id, &ctx.accounts.owner.key())?;
+        let closed = *order;
+        *order = LimitOrder::default();
+
+        (closed, order_book.bump)
+    };
+
+    let (mint_from, mint_to) = if order.is_a_to_b() {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        mint_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(ctx.accounts.mint_to.key(), mint_to, DEXError::MintNotInPool);
+
+    let pool_key = pool.key();
+    let signer_seeds = get_order_book_signer_seeds(&pool_key, &bump);
+    let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
+
+    let refunds = [
+        (
+            &ctx.accounts.escrow_from,
+            &ctx.accounts.owner_token_account_from,
+            order.amount_in,
+        ),
+        (
+            &ctx.accounts.escrow_to,
+            &ctx.accounts.owner_token_account_to,
+            order.amount_filled,
+        ),
+    ];
+
+    for (escrow, owner_token_account, amount) in refunds {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: escrow.to_account_info(),
+                    to: owner_token_account.to_account_info(),
+                    authority: ctx.accounts.order_book.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    pub owner: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = owner,
+        associated_token::mint = mint_from,
+    )]
+    pub owner_token_account_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = owner,
+        associated_token::mint = mint_to,
+    )]
+    pub owner_token_account_to: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = order_book,
+        associated_token::mint = mint_from,
+    )]
+    pub escrow_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = order_book,
+        associated_token::mint = mint_to,
+    )]
+    pub escrow_to: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::state::TriggerOrder;
+use crate::utils::{close_order_escrow, get_trigger_order_signer_seeds};
+
+/// Refunds the input of a trigger order that has not fired and closes it along with its escrow
+pub fn cancel_trigger_order(ctx: Context<CancelTriggerOrder>) -> Result<()> {
+    let order = &ctx.accounts.trigger_order;
+    let id_bytes = order.id.to_le_bytes();
+    let order_seeds =
+        get_trigger_order_signer_seeds(&order.pool, &order.owner, &id_bytes, &order.bump);
+
+    close_order_escrow(
+        (&order.to_account_info(), &order_seeds),
+        &ctx.accounts.escrow,
+        &ctx.accounts.owner.to_account_info(),
+        &ctx.accounts.token_program.to_account_info(),
+        Some((
+            &ctx.accounts.owner_token_account_in.to_account_info(),
+            ctx.accounts.escrow.amount,
+        )),
+    )
+}
+
+#[derive(Accounts)]
+pub struct CancelTriggerOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    #[account(address = trigger_order.mint_in)]
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        address = trigger_order.escrow
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
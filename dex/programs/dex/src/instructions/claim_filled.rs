@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::ORDER_BOOK_SEED;
+use crate::errors::DEXError;
+use crate::state::{LimitOrder, OrderBook, Pool};
+use crate::utils::get_order_book_signer_seeds;
+
+/// Pays out the filled output of an order, freeing its slot once nothing is left to fill
+pub fn claim_filled(ctx: Context<ClaimFilled>, order_id: u64) -> Result<()> {
+    let pool = &ctx.accounts.liquidity_pool;
+
+    let (amount, a_to_b, bump) = {
+        let mut order_book = ctx.accounts.order_book.load_mut()?;
+        let bump = order_book.bump;
+        let order = order_book.order_mut(order_id, &ctx.accounts.owner.key())?;
+
+        let claimed = (order.amount_filled, order.is_a_to_b(), bump);
+        order.amount_filled = 0;
+
+        if order.amount_in == 0 {
+            *order = LimitOrder::default();
+        }
+
+        claimed
+    };
+
+    let mint_to = if a_to_b { pool.mint_b } else { pool.mint_a };
+    require_keys_eq!(ctx.accounts.mint_to.key(), mint_to, DEXError::MintNotInPool);
+
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let pool_key = pool.key();
+    let signer_seeds = get_order_book_signer_seeds(&pool_key, &bump);
+    let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.escrow_to.to_account_info(),
+                to: ctx.accounts.owner_token_account_to.to_account_info(),
+                authority: ctx.accounts.order_book.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimFilled<'info> {
+    pub owner: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = owner,
+        associated_token::mint = mint_to,
+    )]
+    pub owner_token_account_to: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = order_book,
+        associated_token::mint = mint_to,
+    )]
+    pub escrow_to: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{FARM_SEED, FARM_STAKE_SEED};
+use crate::errors::DEXError;
+use crate::events::FarmRewardsClaimedEvent;
+use crate::state::{Farm, FarmStake};
+use crate::utils::get_farm_signer_seeds;
+
+/// Pays out everything the stake has earned of one farm reward up to now
+pub fn claim_rewards(ctx: Context<ClaimRewards>, reward_index: u8) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+    farm.update(Clock::get()?.unix_timestamp)?;
+
+    let stake = &mut ctx.accounts.farm_stake;
+    stake.update_rewards(&farm.rewards)?;
+
+    let reward = *farm.reward_mut(reward_index)?;
+
+    require_keys_eq!(
+        reward.mint,
+        ctx.accounts.reward_mint.key(),
+        DEXError::InvalidFarm
+    );
+    require_keys_eq!(
+        reward.vault,
+        ctx.accounts.reward_vault.key(),
+        DEXError::InvalidFarm
+    );
+
+    let amount = stake.rewards_owed[reward_index as usize];
+    stake.rewards_owed[reward_index as usize] = 0;
+
+    if amount > 0 {
+        let signer_seeds = get_farm_signer_seeds(&farm.pool, &farm.bump);
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.reward_vault.to_account_info(),
+                    to: ctx.accounts.owner_reward_account.to_account_info(),
+                    authority: farm.to_account_info(),
+                },
+                &[&signer_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    emit!(FarmRewardsClaimedEvent {
+        farm: farm.key(),
+        owner: stake.owner,
+        reward_mint: reward.mint,
+        amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        seeds = [FARM_STAKE_SEED, farm.key().as_ref(), owner.key().as_ref()],
+        bump = farm_stake.bump
+    )]
+    pub farm_stake: Account<'info, FarmStake>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = reward_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_reward_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::IntentNonce;
+
+/// Returns the rent of a used intent nonce to the relayer once the intent deadline passed
+pub fn close_intent_nonce(ctx: Context<CloseIntentNonce>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp > ctx.accounts.intent_nonce.deadline,
+        DEXError::IntentNonceInUse
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseIntentNonce<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = payer,
+        close = payer
+    )]
+    pub intent_nonce: Account<'info, IntentNonce>,
+}
@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, transfer, Token, Transfer},
+    token_2022::{self, CloseAccount, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{LIQUIDITY_POOL_SEED, LP_POSITION_SEED, POSITION_LP_VAULT_SEED};
+use crate::errors::DEXError;
+use crate::events::LpPositionClosedEvent;
+use crate::state::{LpPosition, Pool, Twamm};
+use crate::utils::{get_pool_signer_seeds, settle_twamm};
+
+/// Withdraws the whole liquidity of a position to the holder of its NFT, burning the NFT
+pub fn close_lp_position(ctx: Context<CloseLpPosition>) -> Result<()> {
+    // Long-term orders are executed up to now before the position is valued
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_deref(),
+            ctx.accounts.twamm_escrow_b.as_deref(),
+        ),
+        (&mut *ctx.accounts.vault_a, &mut *ctx.accounts.vault_b),
+        &ctx.accounts.token_program.to_account_info(),
+        (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let pool = &ctx.accounts.liquidity_pool;
+    let position = &ctx.accounts.position;
+
+    // Separated fees are paid by `collect_fees`, the position must not leave any behind
+    require!(
+        position.uncollected_fees(pool)? == (0, 0),
+        DEXError::UncollectedLpFees
+    );
+
+    let value = position.value(
+        pool,
+        pool.curve_reserve(true, ctx.accounts.vault_a.amount),
+        pool.curve_reserve(false, ctx.accounts.vault_b.amount),
+        ctx.accounts.lp_mint.supply,
+    )?;
+
+    let signer_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    token::burn(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.position_lp_vault.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        position.liquidity,
+    )?;
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.vault_a,
+            &ctx.accounts.owner_token_a_account,
+            value.amount_a,
+        ),
+        (
+            &ctx.accounts.vault_b,
+            &ctx.accounts.owner_token_b_account,
+            value.amount_b,
+        ),
+    ] {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount,
+        )?;
+    }
+
+    token_2022::burn(
+        CpiContext::new(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_2022::Burn {
+                mint: ctx.accounts.position_mint.to_account_info(),
+                from: ctx.accounts.owner_position_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        1,
+    )?;
+
+    token_2022::close_account(CpiContext::new(
+        ctx.accounts.token_2022_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.owner_position_account.to_account_info(),
+            destination: ctx.accounts.owner.to_account_info(),
+            authority: ctx.accounts.owner.to_account_info(),
+        },
+    ))?;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+    pool.position_liquidity -= position.liquidity;
+
+    emit!(LpPositionClosedEvent {
+        pool: pool.key(),
+        position_mint: position.position_mint,
+        owner: ctx.accounts.owner.key(),
+        liquidity: position.liquidity,
+        amount_a: value.amount_a,
+        amount_b: value.amount_b,
+        fees_a: value.fees_a,
+        fees_b: value.fees_b,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseLpPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = vault_a,
+        has_one = vault_b,
+        has_one = lp_mint
+    )]
+    pub liquidity_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [POSITION_LP_VAULT_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub position_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        mint::token_program = token_2022_program,
+    )]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_2022_program,
+        constraint = owner_position_account.amount == 1 @ DEXError::NotPositionHolder
+    )]
+    pub owner_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        close = owner,
+        seeds = [
+            LP_POSITION_SEED,
+            liquidity_pool.key().as_ref(),
+            position_mint.key().as_ref()
+        ],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+
+    // Positions change hands, the holder may not have token accounts of the pool yet
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
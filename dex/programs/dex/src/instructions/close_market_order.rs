@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::MARKET_SEED;
+use crate::errors::DEXError;
+use crate::state::{Market, Pool};
+use crate::utils::get_pool_signer_seeds;
+
+/// Takes an order off the pool market, paying out its fills and refunding its unfilled input
+pub fn close_market_order(ctx: Context<CloseMarketOrder>, order_id: u64) -> Result<()> {
+    let (bid, order) = ctx
+        .accounts
+        .market
+        .load_mut()?
+        .remove(order_id, &ctx.accounts.owner.key())?;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    // (token A, token B) owed to the maker
+    let (amount_a, amount_b) = if bid {
+        (order.amount_filled, order.amount_in)
+    } else {
+        (order.amount_in, order.amount_filled)
+    };
+
+    pool.book_a = pool
+        .book_a
+        .checked_sub(amount_a)
+        .ok_or(DEXError::MathOverflow)?;
+    pool.book_b = pool
+        .book_b
+        .checked_sub(amount_b)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let signer_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.vault_a,
+            &ctx.accounts.owner_token_account_a,
+            amount_a,
+        ),
+        (
+            &ctx.accounts.vault_b,
+            &ctx.accounts.owner_token_account_b,
+            amount_b,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&signer_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseMarketOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
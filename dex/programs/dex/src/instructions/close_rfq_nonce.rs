@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::RfqNonce;
+
+/// Returns the rent of a used quote nonce to the taker once the quote expired
+pub fn close_rfq_nonce(ctx: Context<CloseRfqNonce>) -> Result<()> {
+    require!(
+        Clock::get()?.unix_timestamp > ctx.accounts.rfq_nonce.expiry,
+        DEXError::RfqNonceInUse
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CloseRfqNonce<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = payer,
+        close = payer
+    )]
+    pub rfq_nonce: Account<'info, RfqNonce>,
+}
@@ -0,0 +1,197 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Token, Transfer},
+    token_2022::Token2022,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{FEE_VAULT_SEED, LIQUIDITY_POOL_SEED, LP_POSITION_SEED};
+use crate::errors::DEXError;
+use crate::events::LpFeesCollectedEvent;
+use crate::state::{LpPosition, Pool};
+use crate::utils::get_pool_signer_seeds;
+
+/// Pays the holder of a position NFT the separated fees the position earned since its last
+/// collection. Fees separated by swaps sit in the pool vaults until a collection sweeps them
+/// into the fee vaults, which keeps the swap paths free of extra accounts.
+pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+    let mint_a_key = ctx.accounts.mint_a.key();
+    let mint_b_key = ctx.accounts.mint_b.key();
+    let bump = ctx.accounts.liquidity_pool.bump;
+    let signer_seeds = get_pool_signer_seeds(&mint_a_key, &mint_b_key, &bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.vault_a,
+            &ctx.accounts.fee_vault_a,
+            pool.fees_a,
+        ),
+        (
+            &ctx.accounts.vault_b,
+            &ctx.accounts.fee_vault_b,
+            pool.fees_b,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount,
+        )?;
+    }
+
+    pool.fees_a = 0;
+    pool.fees_b = 0;
+
+    let position = &mut ctx.accounts.position;
+    let (fees_a, fees_b) = position.collect_fees(pool)?;
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.fee_vault_a,
+            &ctx.accounts.owner_token_a_account,
+            fees_a,
+        ),
+        (
+            &ctx.accounts.fee_vault_b,
+            &ctx.accounts.owner_token_b_account,
+            fees_b,
+        ),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount,
+        )?;
+    }
+
+    emit!(LpFeesCollectedEvent {
+        pool: pool.key(),
+        position_mint: position.position_mint,
+        owner: ctx.accounts.owner.key(),
+        fees_a,
+        fees_b,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CollectFees<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = vault_a,
+        has_one = vault_b
+    )]
+    pub liquidity_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, liquidity_pool.key().as_ref(), mint_a.key().as_ref()],
+        bump
+    )]
+    pub fee_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [FEE_VAULT_SEED, liquidity_pool.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub fee_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mint::token_program = token_2022_program)]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_2022_program,
+        constraint = owner_position_account.amount == 1 @ DEXError::NotPositionHolder
+    )]
+    pub owner_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_POSITION_SEED,
+            liquidity_pool.key().as_ref(),
+            position_mint.key().as_ref()
+        ],
+        bump = position.bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+
+    // Positions change hands, the holder may not have token accounts of the pool yet
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
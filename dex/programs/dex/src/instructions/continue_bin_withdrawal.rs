@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{transfer, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::BIN_POOL_SEED;
+use crate::errors::DEXError;
+use crate::state::{BinArray, BinPool};
+use crate::utils::get_bin_pool_signer_seeds;
+
+/// Pays the pending withdrawal of the bin pool out of the bin arrays passed as remaining
+/// accounts. Anyone can do this, so an unfinished withdrawal never blocks the pool. The
+/// token accounts of the owner are created at the expense of the signer if needed.
+pub fn continue_bin_withdrawal<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ContinueBinWithdrawal<'info>>,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.bin_pool;
+    require!(pool.withdrawal_pending(), DEXError::NoBinWithdrawal);
+
+    let pool_key = pool.key();
+
+    let mut amount_a = 0u64;
+    let mut amount_b = 0u64;
+
+    for account_info in ctx.remaining_accounts.iter() {
+        let loader = AccountLoader::<BinArray>::try_from(account_info)?;
+        let mut bin_array = loader.load_mut()?;
+        require_keys_eq!(bin_array.pool, pool_key, DEXError::InvalidBinArray);
+
+        let (share_a, share_b) = pool.withdraw_from(&mut bin_array)?;
+        amount_a += share_a;
+        amount_b += share_b;
+    }
+
+    let signer_seeds = get_bin_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    if amount_a > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_a.to_account_info(),
+                    to: ctx.accounts.owner_token_a_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount_a,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_b.to_account_info(),
+                    to: ctx.accounts.owner_token_b_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount_b,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ContinueBinWithdrawal<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BIN_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        has_one = vault_a,
+        has_one = vault_b
+    )]
+    pub bin_pool: Account<'info, BinPool>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Only receives the withdrawn tokens, pinned to the pending withdrawal
+    #[account(address = bin_pool.pending_withdrawal.owner @ DEXError::InvalidBinArray)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_a_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_b_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
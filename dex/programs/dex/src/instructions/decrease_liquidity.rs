@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer, Token, Transfer};
+use anchor_spl::token_interface::TokenAccount;
+
+use crate::curves::concentrated::amounts_for_liquidity;
+use crate::errors::DEXError;
+use crate::state::{ConcentratedPool, Position, TickArray};
+use crate::utils::{get_concentrated_pool_signer_seeds, modify_position_liquidity};
+
+pub fn decrease_liquidity(
+    ctx: Context<DecreaseLiquidity>,
+    liquidity: u128,
+    min_token_a_amount: u64,
+    min_token_b_amount: u64,
+) -> Result<()> {
+    require!(liquidity > 0, DEXError::InvalidAmountOfLiquidation);
+    require!(
+        liquidity <= ctx.accounts.position.liquidity,
+        DEXError::InsufficientPositionLiquidity
+    );
+
+    let liquidity_delta = i128::try_from(liquidity).map_err(|_| DEXError::MathOverflow)?;
+
+    let pool = &mut ctx.accounts.concentrated_pool;
+    let position = &mut ctx.accounts.position;
+
+    modify_position_liquidity(
+        pool,
+        position,
+        &ctx.accounts.tick_array_lower,
+        &ctx.accounts.tick_array_upper,
+        -liquidity_delta,
+    )?;
+
+    let (amount_a, amount_b) = amounts_for_liquidity(
+        pool.sqrt_price,
+        position.tick_lower,
+        position.tick_upper,
+        liquidity,
+        false,
+    )?;
+
+    let amount_a = u64::try_from(amount_a).map_err(|_| DEXError::MathOverflow)?;
+    let amount_b = u64::try_from(amount_b).map_err(|_| DEXError::MathOverflow)?;
+
+    require!(
+        amount_a >= min_token_a_amount && amount_b >= min_token_b_amount,
+        DEXError::SlippageExceeded
+    );
+
+    let signer_seeds = get_concentrated_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
+
+    if amount_a > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_a.to_account_info(),
+                    to: ctx.accounts.owner_token_a_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_a,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_b.to_account_info(),
+                    to: ctx.accounts.owner_token_b_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_b,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct DecreaseLiquidity<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = vault_a,
+        has_one = vault_b
+    )]
+    pub concentrated_pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        constraint = position.pool == concentrated_pool.key() @ DEXError::InvalidPosition
+    )]
+    pub position: Account<'info, Position>,
+
+    #[account(
+        mut,
+        constraint = tick_array_lower.load()?.pool == concentrated_pool.key() @ DEXError::InvalidTickArray
+    )]
+    pub tick_array_lower: AccountLoader<'info, TickArray>,
+
+    #[account(
+        mut,
+        constraint = tick_array_upper.load()?.pool == concentrated_pool.key() @ DEXError::InvalidTickArray
+    )]
+    pub tick_array_upper: AccountLoader<'info, TickArray>,
+
+    #[account(mut)]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = concentrated_pool.mint_a,
+        token::authority = owner
+    )]
+    pub owner_token_a_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        token::mint = concentrated_pool.mint_b,
+        token::authority = owner
+    )]
+    pub owner_token_b_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
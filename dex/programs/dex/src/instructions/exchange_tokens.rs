@@ -4,58 +4,155 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount},
 };
 
-use crate::{errors::DEXError, state::Pool, utils::get_pool_signer_seeds};
+use crate::{
+    curves,
+    errors::DEXError,
+    events::{BatchOrderQueuedEvent, ReferralFeeEvent, SwapEvent},
+    state::{
+        BatchAuction, BatchOrder, GlobalConfig, OrderBook, Pool, Referrer, TraderStats, Twamm,
+    },
+    utils::{fill_limit_orders, get_pool_signer_seeds, settle_twamm},
+};
 
 pub fn exchange_tokens(
     ctx: Context<ExchangeTokens>,
     amount_to_exchange: u64,
     min_receive_amount: u64,
 ) -> Result<()> {
+    // Batch auction pools only queue the swap, it executes when its slot gets settled
+    if ctx.accounts.liquidity_pool.batch_auction {
+        return queue_batch_order(ctx, amount_to_exchange, min_receive_amount);
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+
+    // Long-term orders are executed up to now before the swap is priced
+    let from_a = ctx.accounts.mint_from.key() == ctx.accounts.liquidity_pool.mint_a;
+    let (vaults, decimals) = if from_a {
+        (
+            (&mut ctx.accounts.vault_to, &mut ctx.accounts.vault_from),
+            (
+                ctx.accounts.mint_from.decimals,
+                ctx.accounts.mint_to.decimals,
+            ),
+        )
+    } else {
+        (
+            (&mut ctx.accounts.vault_from, &mut ctx.accounts.vault_to),
+            (
+                ctx.accounts.mint_to.decimals,
+                ctx.accounts.mint_from.decimals,
+            ),
+        )
+    };
+
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        vaults,
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
     let buyer = &ctx.accounts.buyer;
     let vault_from = &ctx.accounts.vault_from;
     let vault_to = &ctx.accounts.vault_to;
     let user_token_account_to = &ctx.accounts.buyer_token_account_to;
     let user_token_account_from = &ctx.accounts.buyer_token_account_from;
-    let pool = &ctx.accounts.liquidity_pool;
+    let pool = &mut ctx.accounts.liquidity_pool;
     let token_program = &ctx.accounts.token_program;
 
-    // Fee is taken from the input amount (amount_to_exchange)
-    let fee_amount = (amount_to_exchange as u128)
-        .checked_mul(pool.fee_bps as u128)
-        .unwrap()
-        .checked_div(10000)
-        .unwrap();
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
 
-    let amount_in_net = (amount_to_exchange as u128)
-        .checked_sub(fee_amount)
-        .ok_or(DEXError::MathOverflow)?;
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
 
-    let reserve_in = vault_to.amount as u128;
-    let reserve_out = vault_from.amount as u128;
+    let decimals = (
+        ctx.accounts.mint_from.decimals,
+        ctx.accounts.mint_to.decimals,
+    );
 
-    //                          reserve_out * amount_in_net
-    // Formula is: delta_out = ------------------------------
-    //                          reserve_in + amount_in_net
-    //
-    // The formula is derived from xy = k -> (x + delta_x)(y - delta_y) = xy
+    // Without stats the swap is neither discounted nor counted towards a tier
+    let fee_discount_bps = trader_fee_discount_bps(
+        ctx.accounts.trader_stats.as_deref(),
+        ctx.accounts.global_config.as_deref(),
+        now,
+    );
 
-    let numerator = amount_in_net
-        .checked_mul(reserve_out)
-        .ok_or(DEXError::MathOverflow)?;
+    let (reserve_in, reserve_out) = (
+        pool.curve_reserve(a_to_b, vault_to.amount),
+        pool.curve_reserve(!a_to_b, vault_from.amount),
+    );
 
-    let denominator = reserve_in
-        .checked_add(amount_in_net)
-        .ok_or(DEXError::MathOverflow)?;
+    let quote = quote_swap(
+        pool,
+        a_to_b,
+        amount_to_exchange,
+        (reserve_in, reserve_out),
+        decimals,
+        fee_discount_bps,
+        (now, Clock::get()?.slot),
+    )?;
+    let tokens_to_give = quote.amount_out;
 
-    let tokens_to_give = numerator
-        .checked_div(denominator)
-        .ok_or(DEXError::MathOverflow)? as u64;
+    // Volume is counted in token A so that both directions add up
+    if let Some(trader_stats) = ctx.accounts.trader_stats.as_mut() {
+        let volume = if a_to_b {
+            TraderStats::normalized_volume(amount_to_exchange, decimals.0)?
+        } else {
+            TraderStats::normalized_volume(tokens_to_give, decimals.1)?
+        };
+        trader_stats.record_swap(volume, now);
+    }
 
     require!(
         tokens_to_give >= min_receive_amount,
         DEXError::SlippageExceeded
     );
 
+    // The referral share of the fee goes straight to the referrer instead of the vault
+    let referral_fee = match ctx.accounts.referrer_token_account.as_ref() {
+        Some(referrer_token_account) => {
+            let referrer = ctx
+                .accounts
+                .referrer
+                .as_ref()
+                .ok_or(DEXError::UnregisteredReferrer)?;
+            require_keys_eq!(
+                referrer.owner,
+                referrer_token_account.owner,
+                DEXError::UnregisteredReferrer
+            );
+
+            let global_config = ctx
+                .accounts
+                .global_config
+                .as_ref()
+                .ok_or(DEXError::MissingGlobalConfig)?;
+
+            referral_fee_amount(quote.fee_amount, pool.referral_fee_share_bps, global_config)?
+        }
+        None => 0,
+    };
+
     transfer(
         CpiContext::new(
             token_program.to_account_info(),
@@ -65,9 +162,33 @@ pub fn exchange_tokens(
                 authority: buyer.to_account_info(),
             },
         ),
-        amount_to_exchange, // Send full amount (fee is implicit in the reduced output)
+        // Full amount minus the referral fee (the rest of the fee is implicit in the reduced output)
+        amount_to_exchange - referral_fee,
     )?;
 
+    if let Some(referrer_token_account) = ctx.accounts.referrer_token_account.as_ref() {
+        if referral_fee > 0 {
+            transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: user_token_account_from.to_account_info(),
+                        to: referrer_token_account.to_account_info(),
+                        authority: buyer.to_account_info(),
+                    },
+                ),
+                referral_fee,
+            )?;
+
+            emit!(ReferralFeeEvent {
+                pool: pool.key(),
+                referrer: referrer_token_account.owner,
+                mint: ctx.accounts.mint_from.key(),
+                amount: referral_fee,
+            });
+        }
+    }
+
     let signer_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
     let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
 
@@ -79,19 +200,269 @@ pub fn exchange_tokens(
                 to: user_token_account_to.to_account_info(),
                 authority: pool.to_account_info(),
             },
-            &signer_seeds,
+            signer_seeds,
         ),
         tokens_to_give,
     )?;
 
+    emit!(SwapEvent {
+        pool: pool.key(),
+        trader: buyer.key(),
+        mint_in: ctx.accounts.mint_from.key(),
+        mint_out: ctx.accounts.mint_to.key(),
+        amount_in: amount_to_exchange,
+        amount_out: tokens_to_give,
+        fee_amount: quote.fee_amount,
+        fee_bps: quote.fee_bps,
+    });
+
+    // The referral fee never reaches the vault, a separated fee does not reach the curve
+    let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount - referral_fee)?;
+    let reserve_in = reserve_in + amount_to_exchange - referral_fee - separated_fee;
+    let reserve_out = reserve_out - tokens_to_give;
+    if a_to_b {
+        pool.record_price(reserve_in, reserve_out, now);
+    } else {
+        pool.record_price(reserve_out, reserve_in, now);
+    }
+
+    // The swap moved the price, resting limit orders it crossed get filled right away
+    if let Some(order_book) = ctx.accounts.order_book.as_ref() {
+        let (Some(escrow_a), Some(escrow_b)) = (
+            ctx.accounts.order_escrow_a.as_ref(),
+            ctx.accounts.order_escrow_b.as_ref(),
+        ) else {
+            return err!(DEXError::InvalidOrderBook);
+        };
+
+        ctx.accounts.vault_to.reload()?;
+        ctx.accounts.vault_from.reload()?;
+
+        let (vaults, decimals) = if a_to_b {
+            ((&ctx.accounts.vault_to, &ctx.accounts.vault_from), decimals)
+        } else {
+            (
+                (&ctx.accounts.vault_from, &ctx.accounts.vault_to),
+                (decimals.1, decimals.0),
+            )
+        };
+
+        fill_limit_orders(
+            &mut ctx.accounts.liquidity_pool,
+            order_book,
+            vaults,
+            (escrow_a, escrow_b),
+            &ctx.accounts.token_program.to_account_info(),
+            decimals,
+            now,
+        )?;
+    }
+
     Ok(())
 }
 
+/// Escrows the swap input in the batch of the current slot, `min_receive_amount` becoming
+/// the limit the clearing price has to meet. Volume discounts and referral fees do not
+/// apply to batched swaps.
+fn queue_batch_order(
+    ctx: Context<ExchangeTokens>,
+    amount_to_exchange: u64,
+    min_receive_amount: u64,
+) -> Result<()> {
+    let pool = &ctx.accounts.liquidity_pool;
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
+
+    let (Some(batch_auction), Some(escrow)) = (
+        ctx.accounts.batch_auction.as_mut(),
+        ctx.accounts.batch_escrow.as_ref(),
+    ) else {
+        return err!(DEXError::InvalidBatchAuction);
+    };
+
+    require_keys_eq!(
+        batch_auction.pool,
+        pool.key(),
+        DEXError::InvalidBatchAuction
+    );
+    require_keys_eq!(
+        escrow.key(),
+        if a_to_b {
+            batch_auction.escrow_a
+        } else {
+            batch_auction.escrow_b
+        },
+        DEXError::InvalidBatchAuction
+    );
+
+    let slot = Clock::get()?.slot;
+
+    batch_auction.push(
+        BatchOrder {
+            trader: ctx.accounts.buyer.key(),
+            token_account_in: ctx.accounts.buyer_token_account_from.key(),
+            token_account_out: ctx.accounts.buyer_token_account_to.key(),
+            a_to_b,
+            amount_in: amount_to_exchange,
+            min_amount_out: min_receive_amount,
+        },
+        slot,
+    )?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_token_account_from.to_account_info(),
+                to: escrow.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        amount_to_exchange,
+    )?;
+
+    emit!(BatchOrderQueuedEvent {
+        pool: pool.key(),
+        trader: ctx.accounts.buyer.key(),
+        slot,
+        a_to_b,
+        amount_in: amount_to_exchange,
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct SwapQuote {
+    pub amount_out: u64,
+    pub fee_amount: u64,
+    /// Fee rate applied: the pool's fee for the swap direction, raised by dynamic or impact
+    /// fees and lowered by volume tier discounts
+    pub fee_bps: u64,
+}
+
+/// Discount of the trader's volume tier, none without their stats or the global config
+pub fn trader_fee_discount_bps(
+    trader_stats: Option<&TraderStats>,
+    global_config: Option<&GlobalConfig>,
+    now: i64,
+) -> u64 {
+    match (trader_stats, global_config) {
+        (Some(trader_stats), Some(global_config)) => {
+            global_config.fee_discount_bps(trader_stats.volume_at(now))
+        }
+        _ => 0,
+    }
+}
+
+/// Prices a swap of `amount_to_exchange` against the pool reserves `(in, out)`, moving the
+/// dynamic fee state and the swap cap usage of `pool` the way the swap would at `now` and
+/// `slot`
+pub fn quote_swap(
+    pool: &mut Pool,
+    a_to_b: bool,
+    amount_to_exchange: u64,
+    (reserve_in, reserve_out): (u64, u64),
+    decimals: (u8, u8),
+    fee_discount_bps: u64,
+    (now, slot): (i64, u64),
+) -> Result<SwapQuote> {
+    require!(!pool.batch_auction, DEXError::BatchAuctionPool);
+
+    pool.use_swap_cap(a_to_b, amount_to_exchange, slot)?;
+
+    let (reserve_a, reserve_b) = if a_to_b {
+        (reserve_in, reserve_out)
+    } else {
+        (reserve_out, reserve_in)
+    };
+
+    let fee_bps = pool.swap_fee_bps(
+        a_to_b,
+        Pool::spot_price(reserve_a, reserve_b),
+        amount_to_exchange,
+        reserve_in,
+        now,
+    );
+    let fee_bps = fee_bps - fee_bps * fee_discount_bps / 10_000;
+
+    // Fee is taken from the input amount (amount_to_exchange)
+    let fee_amount = (amount_to_exchange as u128)
+        .checked_mul(fee_bps as u128)
+        .ok_or(DEXError::MathOverflow)?
+        .checked_div(10000)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let amount_in_net = (amount_to_exchange as u128)
+        .checked_sub(fee_amount)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let amount_out = curves::amount_out(
+        pool,
+        a_to_b,
+        amount_in_net,
+        reserve_in as u128,
+        reserve_out as u128,
+        decimals,
+        now,
+    )? as u64;
+
+    if let Some(dynamic_fee) = pool.dynamic_fee.as_mut() {
+        let new_reserve_in = reserve_in.saturating_add(amount_to_exchange);
+        let new_reserve_out = reserve_out.saturating_sub(amount_out);
+
+        let price = if a_to_b {
+            Pool::spot_price(new_reserve_in, new_reserve_out)
+        } else {
+            Pool::spot_price(new_reserve_out, new_reserve_in)
+        };
+
+        dynamic_fee.record_swap(price, now);
+    }
+
+    Ok(SwapQuote {
+        amount_out,
+        fee_amount: fee_amount as u64,
+        fee_bps,
+    })
+}
+
+/// Part of `fee_amount` owed to a referrer, the pool share being capped by the global config
+fn referral_fee_amount(
+    fee_amount: u64,
+    referral_fee_share_bps: u64,
+    global_config: &GlobalConfig,
+) -> Result<u64> {
+    let share_bps = referral_fee_share_bps.min(global_config.max_referral_fee_share_bps);
+
+    Ok((fee_amount as u128)
+        .checked_mul(share_bps as u128)
+        .ok_or(DEXError::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(DEXError::MathOverflow)? as u64)
+}
+
 #[derive(Accounts)]
 pub struct ExchangeTokens<'info> {
     #[account(mut)]
     pub buyer: Signer<'info>,
 
+    #[account(mut)]
     pub liquidity_pool: Account<'info, Pool>,
 
     #[account(mut)]
@@ -104,12 +475,14 @@ pub struct ExchangeTokens<'info> {
     pub mint_to: InterfaceAccount<'info, Mint>,
 
     #[account(
+        mut,
         associated_token::authority = buyer,
         associated_token::mint = mint_from,
     )]
     pub buyer_token_account_from: InterfaceAccount<'info, TokenAccount>,
 
     #[account(
+        mut,
         associated_token::authority = buyer,
         associated_token::mint = mint_to,
     )]
@@ -133,5 +506,60 @@ pub struct ExchangeTokens<'info> {
     )]
     pub vault_from: InterfaceAccount<'info, TokenAccount>,
 
+    /// Receives the referral share of the fee, in the input token. Owned by someone else
+    /// than the buyer, or the share would be a fee rebate
+    #[account(
+        mut,
+        token::mint = mint_from,
+        constraint = referrer_token_account.owner != buyer.key() @ DEXError::SelfReferral
+    )]
+    pub referrer_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    // Registration of the owner of the referrer token account. Referrer accounts can only
+    // be created by `register_referrer`, so the owner and discriminator checks pin it down
+    pub referrer: Option<Account<'info, Referrer>>,
+
+    /// Rolling volume of the buyer, opened by `initialize_trader_stats`. Swaps without it
+    /// get no volume discount
+    #[account(
+        mut,
+        constraint = trader_stats.trader == buyer.key()
+            && trader_stats.pool == liquidity_pool.key() @ DEXError::InvalidTraderStats
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    // Needed to pay a referrer or get a volume discount. Config accounts can only be
+    // created at the GLOBAL_CONFIG_SEED address, so the owner and discriminator checks
+    // pin it down
+    pub global_config: Option<Account<'info, GlobalConfig>>,
+
+    /// Limit orders of the pool to fill after the swap, along with their escrows
+    #[account(mut)]
+    pub order_book: Option<AccountLoader<'info, OrderBook>>,
+
+    #[account(mut)]
+    pub order_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub order_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Long-term orders of the pool to execute before the swap, along with their escrows.
+    /// Required once the pool has a TWAMM
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Queue of a batch auction pool, along with its escrow of the input token
+    #[account(mut)]
+    pub batch_auction: Option<Account<'info, BatchAuction>>,
+
+    #[account(mut)]
+    pub batch_escrow: Option<InterfaceAccount<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
 }
@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::MAX_BINS_PER_SWAP;
+use crate::curves::bins::{bin_price, swap_in_bin};
+use crate::errors::DEXError;
+use crate::state::{BinArray, BinPool};
+use crate::utils::get_bin_pool_signer_seeds;
+
+/// Swaps an exact input amount bin by bin, starting at the active bin and moving down
+/// (A to B) or up (B to A) as bins run dry. The bin arrays are passed as remaining
+/// accounts in the swap direction. The swap stops early once it runs out of bin arrays
+/// or visits `MAX_BINS_PER_SWAP` bins, only the consumed input is charged.
+pub fn exchange_tokens_bin_pool<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExchangeTokensBinPool<'info>>,
+    amount_to_exchange: u64,
+    min_receive_amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.bin_pool;
+    let pool_key = pool.key();
+
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
+
+    let loaders = ctx
+        .remaining_accounts
+        .iter()
+        .map(AccountLoader::<BinArray>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut bin_arrays = Vec::with_capacity(loaders.len());
+    for loader in loaders.iter() {
+        let bin_array = loader.load_mut()?;
+        require_keys_eq!(bin_array.pool, pool_key, DEXError::InvalidBinArray);
+        bin_arrays.push(bin_array);
+    }
+
+    require!(
+        bin_arrays
+            .first()
+            .is_some_and(|first| first.contains(pool.active_bin_id)),
+        DEXError::InvalidBinArray
+    );
+
+    let mut amount_remaining = amount_to_exchange;
+    let mut amount_out = 0u64;
+    let mut active_bin_id = pool.active_bin_id;
+
+    for _ in 0..MAX_BINS_PER_SWAP {
+        if amount_remaining == 0 {
+            break;
+        }
+
+        let Some(bin_array) = bin_arrays
+            .iter_mut()
+            .find(|bin_array| bin_array.contains(active_bin_id))
+        else {
+            break;
+        };
+
+        let price = bin_price(active_bin_id, pool.bin_step)?;
+        let bin = bin_array.bin_mut(active_bin_id)?;
+        let step = swap_in_bin(bin, price, a_to_b, amount_remaining, pool.fee_bps)?;
+
+        if a_to_b {
+            bin.amount_a = bin
+                .amount_a
+                .checked_add(step.amount_in)
+                .ok_or(DEXError::MathOverflow)?;
+            bin.amount_b -= step.amount_out;
+        } else {
+            bin.amount_b = bin
+                .amount_b
+                .checked_add(step.amount_in)
+                .ok_or(DEXError::MathOverflow)?;
+            bin.amount_a -= step.amount_out;
+        }
+
+        amount_remaining -= step.amount_in;
+        amount_out = amount_out
+            .checked_add(step.amount_out)
+            .ok_or(DEXError::MathOverflow)?;
+
+        if !step.exhausted {
+            break;
+        }
+
+        let next_bin_id = if a_to_b {
+            active_bin_id - 1
+        } else {
+            active_bin_id + 1
+        };
+
+        // Never leave the range covered by the bitmap
+        if BinPool::bin_array_bit(BinArray::index_for(next_bin_id)).is_err() {
+            break;
+        }
+
+        active_bin_id = next_bin_id;
+    }
+
+    drop(bin_arrays);
+
+    let amount_in = amount_to_exchange - amount_remaining;
+
+    require!(amount_out >= min_receive_amount, DEXError::SlippageExceeded);
+
+    pool.active_bin_id = active_bin_id;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_token_account_from.to_account_info(),
+                to: ctx.accounts.vault_in.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    let signer_seeds = get_bin_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_out.to_account_info(),
+                to: ctx.accounts.buyer_token_account_to.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_out,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExchangeTokensBinPool<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub bin_pool: Account<'info, BinPool>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_from.key() != mint_to.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = buyer,
+        associated_token::mint = mint_from,
+    )]
+    pub buyer_token_account_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = buyer,
+        associated_token::mint = mint_to,
+    )]
+    pub buyer_token_account_to: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_from,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_to,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
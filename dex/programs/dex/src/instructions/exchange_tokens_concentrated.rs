@@ -0,0 +1,283 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::MAX_TICK_CROSSINGS_PER_SWAP;
+use crate::curves::concentrated::{
+    compute_swap_step, sqrt_price_at_tick, tick_at_sqrt_price, MAX_SQRT_PRICE, MAX_TICK,
+    MIN_SQRT_PRICE, MIN_TICK, Q64,
+};
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::{ConcentratedPool, TickArray};
+use crate::utils::get_concentrated_pool_signer_seeds;
+
+/// Swaps an exact input amount, walking the tick arrays passed as remaining accounts
+/// (consecutive in the swap direction, the first one containing the current tick).
+/// The swap stops early once it runs out of tick arrays or crosses
+/// `MAX_TICK_CROSSINGS_PER_SWAP` initialized ticks, only the consumed input is charged.
+pub fn exchange_tokens_concentrated<'info>(
+    ctx: Context<'_, '_, 'info, 'info, ExchangeTokensConcentrated<'info>>,
+    amount_to_exchange: u64,
+    min_receive_amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.concentrated_pool;
+    let pool_key = pool.key();
+    let tick_spacing = pool.tick_spacing;
+
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
+
+    let loaders = ctx
+        .remaining_accounts
+        .iter()
+        .map(AccountLoader::<TickArray>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut tick_arrays = Vec::with_capacity(loaders.len());
+    for loader in loaders.iter() {
+        let tick_array = loader.load_mut()?;
+        require_keys_eq!(tick_array.pool, pool_key, DEXError::InvalidTickArray);
+        tick_arrays.push(tick_array);
+    }
+
+    require!(
+        tick_arrays
+            .first()
+            .is_some_and(|first| first.contains(pool.tick_current, tick_spacing)),
+        DEXError::InvalidTickArray
+    );
+
+    // Each array has to follow the previous one in the swap direction, or the initialized
+    // ticks of a skipped array would never be crossed
+    let array_step = if a_to_b {
+        -TickArray::ticks_in_array(tick_spacing)
+    } else {
+        TickArray::ticks_in_array(tick_spacing)
+    };
+    for pair in tick_arrays.windows(2) {
+        require!(
+            pair[1].start_tick_index == pair[0].start_tick_index + array_step,
+            DEXError::InvalidTickArray
+        );
+    }
+
+    let sqrt_price_limit = if a_to_b {
+        MIN_SQRT_PRICE
+    } else {
+        MAX_SQRT_PRICE
+    };
+
+    let mut amount_remaining = amount_to_exchange as u128;
+    let mut amount_out = 0u128;
+    let mut sqrt_price = pool.sqrt_price;
+    let mut tick_current = pool.tick_current;
+    let mut liquidity = pool.liquidity;
+    let mut fee_growth_global_a = pool.fee_growth_global_a;
+    let mut fee_growth_global_b = pool.fee_growth_global_b;
+    let mut crossings = 0;
+
+    while amount_remaining > 0
+        && sqrt_price != sqrt_price_limit
+        && crossings < MAX_TICK_CROSSINGS_PER_SWAP
+    {
+        let next_initialized = tick_arrays.iter().enumerate().find_map(|(index, array)| {
+            array
+                .next_initialized_tick(tick_current, tick_spacing, a_to_b)
+                .map(|tick| (tick, index))
+        });
+
+        // Without an initialized tick ahead the price may move up to the edge of the last array
+        let (next_tick, array_index) = match next_initialized {
+            Some((tick, index)) => (tick, Some(index)),
+            None => {
+                let last = tick_arrays.last().unwrap();
+                let edge = if a_to_b {
+                    last.start_tick_index
+                } else {
+                    last.start_tick_index + TickArray::ticks_in_array(tick_spacing)
+                };
+
+                let out_of_arrays = if a_to_b {
+                    tick_current < edge
+                } else {
+                    tick_current >= edge
+                };
+
+                if out_of_arrays {
+                    break;
+                }
+
+                (edge, None)
+            }
+        };
+
+        let next_tick = next_tick.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_next_tick = sqrt_price_at_tick(next_tick)?;
+        let sqrt_price_target = if a_to_b {
+            sqrt_price_next_tick.max(sqrt_price_limit)
+        } else {
+            sqrt_price_next_tick.min(sqrt_price_limit)
+        };
+
+        let step = compute_swap_step(
+            sqrt_price,
+            sqrt_price_target,
+            liquidity,
+            amount_remaining,
+            pool.fee_bps,
+        )?;
+
+        amount_remaining = amount_remaining
+            .checked_sub(step.amount_in + step.fee_amount)
+            .ok_or(DEXError::MathOverflow)?;
+        amount_out = amount_out
+            .checked_add(step.amount_out)
+            .ok_or(DEXError::MathOverflow)?;
+
+        // Fees are earned in the input token by the liquidity that was active for this step
+        if liquidity > 0 {
+            let fee_growth = mul_div_floor(step.fee_amount, Q64, liquidity)?;
+
+            if a_to_b {
+                fee_growth_global_a = fee_growth_global_a.wrapping_add(fee_growth);
+            } else {
+                fee_growth_global_b = fee_growth_global_b.wrapping_add(fee_growth);
+            }
+        }
+
+        sqrt_price = step.sqrt_price_next;
+
+        if sqrt_price == sqrt_price_next_tick {
+            if let Some(index) = array_index {
+                let tick_array = &mut tick_arrays[index];
+                let mut tick = tick_array.get_tick(next_tick, tick_spacing)?;
+
+                tick.cross(fee_growth_global_a, fee_growth_global_b);
+                tick_array.set_tick(next_tick, tick_spacing, tick)?;
+
+                let liquidity_net = if a_to_b {
+                    -tick.liquidity_net
+                } else {
+                    tick.liquidity_net
+                };
+
+                liquidity = liquidity
+                    .checked_add_signed(liquidity_net)
+                    .ok_or(DEXError::MathOverflow)?;
+                crossings += 1;
+            }
+
+            tick_current = if a_to_b { next_tick - 1 } else { next_tick };
+        } else {
+            tick_current = tick_at_sqrt_price(sqrt_price)?;
+        }
+    }
+
+    drop(tick_arrays);
+
+    let amount_in = amount_to_exchange - amount_remaining as u64;
+    let amount_out = u64::try_from(amount_out).map_err(|_| DEXError::MathOverflow)?;
+
+    require!(amount_out >= min_receive_amount, DEXError::SlippageExceeded);
+
+    pool.sqrt_price = sqrt_price;
+    pool.tick_current = tick_current;
+    pool.liquidity = liquidity;
+    pool.fee_growth_global_a = fee_growth_global_a;
+    pool.fee_growth_global_b = fee_growth_global_b;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_token_account_from.to_account_info(),
+                to: ctx.accounts.vault_in.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    let signer_seeds = get_concentrated_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_out.to_account_info(),
+                to: ctx.accounts.buyer_token_account_to.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_out,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExchangeTokensConcentrated<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub concentrated_pool: Account<'info, ConcentratedPool>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_from.key() != mint_to.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = buyer,
+        associated_token::mint = mint_from,
+    )]
+    pub buyer_token_account_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = buyer,
+        associated_token::mint = mint_to,
+    )]
+    pub buyer_token_account_to: InterfaceAccount<'info, TokenAccount>,
+
+    // Unlike `ExchangeTokens`, the vaults are named after the direction of the funds
+    #[account(
+        mut,
+        associated_token::mint = mint_from,
+        associated_token::authority = concentrated_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_to,
+        associated_token::authority = concentrated_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
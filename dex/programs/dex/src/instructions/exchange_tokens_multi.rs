@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::curves::multi::swap_amount_out;
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::MultiPool;
+use crate::utils::get_multi_pool_signer_seeds;
+
+pub fn exchange_tokens_multi(
+    ctx: Context<ExchangeTokensMulti>,
+    amount_to_exchange: u64,
+    min_receive_amount: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.multi_pool;
+
+    let index_in = pool.token_index(&ctx.accounts.mint_from.key())?;
+    let index_out = pool.token_index(&ctx.accounts.mint_to.key())?;
+
+    require_keys_eq!(
+        ctx.accounts.vault_in.key(),
+        pool.tokens[index_in].vault,
+        DEXError::WrongVaultSpecified
+    );
+    require_keys_eq!(
+        ctx.accounts.vault_out.key(),
+        pool.tokens[index_out].vault,
+        DEXError::WrongVaultSpecified
+    );
+
+    // Fee is taken from the input amount and stays in the pool
+    let fee_amount = mul_div_floor(amount_to_exchange as u128, pool.fee_bps as u128, 10_000)?;
+    let amount_in_net = amount_to_exchange as u128 - fee_amount;
+
+    let amount_out = swap_amount_out(pool, index_in, index_out, amount_in_net)? as u64;
+
+    require!(amount_out >= min_receive_amount, DEXError::SlippageExceeded);
+
+    pool.tokens[index_in].balance = pool.tokens[index_in]
+        .balance
+        .checked_add(amount_to_exchange)
+        .ok_or(DEXError::MathOverflow)?;
+    pool.tokens[index_out].balance -= amount_out;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.buyer_token_account_from.to_account_info(),
+                to: ctx.accounts.vault_in.to_account_info(),
+                authority: ctx.accounts.buyer.to_account_info(),
+            },
+        ),
+        amount_to_exchange,
+    )?;
+
+    let signer_seeds = get_multi_pool_signer_seeds(&pool.lp_mint, &pool.bump);
+    let signer_seeds: &[&[&[u8]]] = &[&signer_seeds];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_out.to_account_info(),
+                to: ctx.accounts.buyer_token_account_to.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        amount_out,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExchangeTokensMulti<'info> {
+    #[account(mut)]
+    pub buyer: Signer<'info>,
+
+    #[account(mut)]
+    pub multi_pool: Account<'info, MultiPool>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_from.key() != mint_to.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = buyer,
+        associated_token::mint = mint_from,
+    )]
+    pub buyer_token_account_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = buyer,
+        associated_token::mint = mint_to,
+    )]
+    pub buyer_token_account_to: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_from,
+        associated_token::authority = multi_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_to,
+        associated_token::authority = multi_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
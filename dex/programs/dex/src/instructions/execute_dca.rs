@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::errors::DEXError;
+use crate::events::{DcaCycleExecutedEvent, SwapEvent};
+use crate::instructions::exchange_tokens::quote_swap;
+use crate::state::{DcaOrder, Pool, Twamm};
+use crate::utils::{get_dca_order_signer_seeds, settle_escrowed_swap, settle_twamm};
+
+/// Permissionless keeper crank swapping the next due cycle of a DCA order through the pool,
+/// priced the same way as `exchange_tokens`. The keeper is tipped out of the cycle input.
+pub fn execute_dca(ctx: Context<ExecuteDca>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let order = &mut ctx.accounts.dca_order;
+
+    require!(now >= order.next_cycle_ts, DEXError::DcaCycleNotDue);
+
+    let remaining = ctx.accounts.escrow.amount;
+    require!(remaining > 0, DEXError::DcaOrderCompleted);
+
+    let (amount_in, keeper_tip, min_amount_out) = order.next_cycle(remaining)?;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+    let a_to_b = order.mint_in == pool.mint_a;
+
+    // Long-term orders are executed up to now before the cycle is priced
+    let (vaults, decimals) = if a_to_b {
+        (
+            (&mut ctx.accounts.vault_in, &mut ctx.accounts.vault_out),
+            (
+                ctx.accounts.mint_in.decimals,
+                ctx.accounts.mint_out.decimals,
+            ),
+        )
+    } else {
+        (
+            (&mut ctx.accounts.vault_out, &mut ctx.accounts.vault_in),
+            (
+                ctx.accounts.mint_out.decimals,
+                ctx.accounts.mint_in.decimals,
+            ),
+        )
+    };
+    settle_twamm(
+        pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        vaults,
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let (reserve_in, reserve_out) = (
+        pool.curve_reserve(a_to_b, ctx.accounts.vault_in.amount),
+        pool.curve_reserve(!a_to_b, ctx.accounts.vault_out.amount),
+    );
+
+    let quote = quote_swap(
+        pool,
+        a_to_b,
+        amount_in,
+        (reserve_in, reserve_out),
+        (
+            ctx.accounts.mint_in.decimals,
+            ctx.accounts.mint_out.decimals,
+        ),
+        0,
+        (now, Clock::get()?.slot),
+    )?;
+
+    require!(
+        quote.amount_out >= min_amount_out,
+        DEXError::SlippageExceeded
+    );
+
+    order.next_cycle_ts = now
+        .checked_add(order.cycle_interval)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let id_bytes = order.id.to_le_bytes();
+    let order_seeds = get_dca_order_signer_seeds(&order.pool, &order.owner, &id_bytes, &order.bump);
+
+    settle_escrowed_swap(
+        pool,
+        (&order.to_account_info(), &order_seeds),
+        &ctx.accounts.escrow,
+        (&ctx.accounts.vault_in, &ctx.accounts.vault_out),
+        (
+            &ctx.accounts.keeper_token_account,
+            &ctx.accounts.owner_token_account_out,
+        ),
+        &ctx.accounts.token_program.to_account_info(),
+        (amount_in, keeper_tip, quote.amount_out),
+    )?;
+
+    let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount)?;
+    let (reserve_in, reserve_out) = (
+        reserve_in + amount_in - separated_fee,
+        reserve_out - quote.amount_out,
+    );
+    if a_to_b {
+        pool.record_price(reserve_in, reserve_out, now);
+    } else {
+        pool.record_price(reserve_out, reserve_in, now);
+    }
+
+    emit!(SwapEvent {
+        pool: pool.key(),
+        trader: order.owner,
+        mint_in: order.mint_in,
+        mint_out: order.mint_out,
+        amount_in,
+        amount_out: quote.amount_out,
+        fee_amount: quote.fee_amount,
+        fee_bps: quote.fee_bps,
+    });
+
+    emit!(DcaCycleExecutedEvent {
+        pool: pool.key(),
+        dca_order: order.key(),
+        owner: order.owner,
+        keeper: ctx.accounts.keeper.key(),
+        amount_in,
+        amount_out: quote.amount_out,
+        keeper_tip,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteDca<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        address = dca_order.pool
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(mut)]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    #[account(address = dca_order.mint_in)]
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(address = dca_order.mint_out)]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = dca_order.escrow
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = dca_order.owner
+    )]
+    pub owner_token_account_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the keeper tip, in the input token
+    #[account(
+        mut,
+        token::mint = mint_in,
+    )]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{INTENT_AUTHORITY_SEED, INTENT_NONCE_SEED, MAX_RELAYER_TIP_BPS};
+use crate::errors::DEXError;
+use crate::events::{IntentExecutedEvent, SwapEvent};
+use crate::instructions::exchange_tokens::quote_swap;
+use crate::state::{IntentNonce, Pool, SwapIntent, Twamm};
+use crate::utils::{
+    get_intent_authority_signer_seeds, get_pool_signer_seeds, settle_twamm,
+    verify_ed25519_signature,
+};
+
+/// Swaps for the owner of a signed `intent` on behalf of a relayer, who pays the transaction
+/// and gets tipped in the output token. The owner signature is verified by an Ed25519
+/// program instruction placed right before this one, and the input is taken through the
+/// delegation the owner gave to the intent authority.
+pub fn execute_intent(ctx: Context<ExecuteIntent>, intent: SwapIntent) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now <= intent.deadline, DEXError::IntentExpired);
+    require!(
+        intent.relayer_tip_bps <= MAX_RELAYER_TIP_BPS,
+        DEXError::RelayerTipTooHigh
+    );
+
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        &intent.owner,
+        &intent.try_to_vec()?,
+    )?;
+
+    let nonce = &mut ctx.accounts.intent_nonce;
+    nonce.payer = ctx.accounts.relayer.key();
+    nonce.deadline = intent.deadline;
+    nonce.bump = ctx.bumps.intent_nonce;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+    let a_to_b = intent.mint_in == pool.mint_a;
+    let expected_mint_out = if a_to_b { pool.mint_b } else { pool.mint_a };
+
+    require!(
+        a_to_b || intent.mint_in == pool.mint_b,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_out.key(),
+        expected_mint_out,
+        DEXError::MintNotInPool
+    );
+
+    // Long-term orders are executed up to now before the swap is priced
+    let (vaults, decimals) = if a_to_b {
+        (
+            (&mut ctx.accounts.vault_in, &mut ctx.accounts.vault_out),
+            (
+                ctx.accounts.mint_in.decimals,
+                ctx.accounts.mint_out.decimals,
+            ),
+        )
+    } else {
+        (
+            (&mut ctx.accounts.vault_out, &mut ctx.accounts.vault_in),
+            (
+                ctx.accounts.mint_out.decimals,
+                ctx.accounts.mint_in.decimals,
+            ),
+        )
+    };
+    settle_twamm(
+        pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        vaults,
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let (reserve_in, reserve_out) = (
+        pool.curve_reserve(a_to_b, ctx.accounts.vault_in.amount),
+        pool.curve_reserve(!a_to_b, ctx.accounts.vault_out.amount),
+    );
+
+    let quote = quote_swap(
+        pool,
+        a_to_b,
+        intent.amount_in,
+        (reserve_in, reserve_out),
+        (
+            ctx.accounts.mint_in.decimals,
+            ctx.accounts.mint_out.decimals,
+        ),
+        0,
+        (now, Clock::get()?.slot),
+    )?;
+
+    let relayer_tip = (quote.amount_out as u128)
+        .checked_mul(intent.relayer_tip_bps as u128)
+        .ok_or(DEXError::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(DEXError::MathOverflow)? as u64;
+    let amount_out = quote.amount_out - relayer_tip;
+
+    require!(
+        amount_out >= intent.min_amount_out,
+        DEXError::SlippageExceeded
+    );
+
+    let token_program = &ctx.accounts.token_program;
+    let authority_seeds = get_intent_authority_signer_seeds(&ctx.bumps.intent_authority);
+
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_token_account_in.to_account_info(),
+                to: ctx.accounts.vault_in.to_account_info(),
+                authority: ctx.accounts.intent_authority.to_account_info(),
+            },
+            &[&authority_seeds],
+        ),
+        intent.amount_in,
+    )?;
+
+    let pool_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+    for (to, amount) in [
+        (&ctx.accounts.owner_token_account_out, amount_out),
+        (&ctx.accounts.relayer_token_account, relayer_tip),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_out.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                &[&pool_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount)?;
+    let (reserve_in, reserve_out) = (
+        reserve_in + intent.amount_in - separated_fee,
+        reserve_out - quote.amount_out,
+    );
+    if a_to_b {
+        pool.record_price(reserve_in, reserve_out, now);
+    } else {
+        pool.record_price(reserve_out, reserve_in, now);
+    }
+
+    emit!(SwapEvent {
+        pool: pool.key(),
+        trader: intent.owner,
+        mint_in: intent.mint_in,
+        mint_out: expected_mint_out,
+        amount_in: intent.amount_in,
+        amount_out: quote.amount_out,
+        fee_amount: quote.fee_amount,
+        fee_bps: quote.fee_bps,
+    });
+
+    emit!(IntentExecutedEvent {
+        owner: intent.owner,
+        relayer: ctx.accounts.relayer.key(),
+        nonce: intent.nonce,
+        amount_in: intent.amount_in,
+        amount_out,
+        relayer_tip,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(intent: SwapIntent)]
+pub struct ExecuteIntent<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(
+        mut,
+        address = intent.pool @ DEXError::MintNotInPool
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    /// Created once per intent, so an intent cannot be executed twice
+    #[account(
+        init,
+        payer = relayer,
+        space = IntentNonce::MAX_SIZE,
+        seeds = [INTENT_NONCE_SEED, intent.owner.as_ref(), &intent.nonce.to_le_bytes()],
+        bump
+    )]
+    pub intent_nonce: Account<'info, IntentNonce>,
+
+    #[account(address = intent.mint_in @ DEXError::MintNotInPool)]
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = intent.owner
+    )]
+    pub owner_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = intent.owner
+    )]
+    pub owner_token_account_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the relayer tip, in the output token
+    #[account(
+        mut,
+        token::mint = mint_out,
+    )]
+    pub relayer_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Signing PDA users delegate their intent input to
+    #[account(
+        seeds = [INTENT_AUTHORITY_SEED],
+        bump
+    )]
+    pub intent_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The instructions sysvar, read to find the Ed25519 signature check
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TWAMM_SEED;
+use crate::errors::DEXError;
+use crate::state::{Pool, Twamm};
+use crate::utils::execute_twamm;
+
+/// Permissionless crank executing the long-term orders up to now
+pub fn execute_long_term_orders(ctx: Context<ExecuteLongTermOrders>) -> Result<()> {
+    let decimals = (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals);
+
+    execute_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        &ctx.accounts.twamm,
+        (&ctx.accounts.vault_a, &ctx.accounts.vault_b),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        Clock::get()?.unix_timestamp,
+    )
+}
+
+#[derive(Accounts)]
+pub struct ExecuteLongTermOrders<'info> {
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub twamm: AccountLoader<'info, Twamm>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = twamm
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = twamm
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,230 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::errors::DEXError;
+use crate::events::{SwapEvent, TriggerOrderExecutedEvent};
+use crate::instructions::exchange_tokens::quote_swap;
+use crate::state::{Pool, TriggerOrder, TriggerPriceSource, Twamm};
+use crate::utils::{
+    close_order_escrow, get_trigger_order_signer_seeds, keeper_tip, settle_escrowed_swap,
+    settle_twamm,
+};
+
+/// Permissionless keeper crank swapping a trigger order out through the pool once its
+/// trigger price is crossed, priced the same way as `exchange_tokens`. The keeper is tipped
+/// out of the order input and the order is closed.
+pub fn execute_trigger_order(ctx: Context<ExecuteTriggerOrder>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let order = &ctx.accounts.trigger_order;
+    let pool = &mut ctx.accounts.liquidity_pool;
+    let a_to_b = order.mint_in == pool.mint_a;
+
+    // Long-term orders are executed up to now before the trigger is checked
+    let (vaults, decimals) = if a_to_b {
+        (
+            (&mut ctx.accounts.vault_in, &mut ctx.accounts.vault_out),
+            (
+                ctx.accounts.mint_in.decimals,
+                ctx.accounts.mint_out.decimals,
+            ),
+        )
+    } else {
+        (
+            (&mut ctx.accounts.vault_out, &mut ctx.accounts.vault_in),
+            (
+                ctx.accounts.mint_out.decimals,
+                ctx.accounts.mint_in.decimals,
+            ),
+        )
+    };
+    settle_twamm(
+        pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        vaults,
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let (reserve_in, reserve_out) = (
+        pool.curve_reserve(a_to_b, ctx.accounts.vault_in.amount),
+        pool.curve_reserve(!a_to_b, ctx.accounts.vault_out.amount),
+    );
+
+    let price = match order.price_source {
+        TriggerPriceSource::Spot if a_to_b => Pool::spot_price(reserve_in, reserve_out),
+        TriggerPriceSource::Spot => Pool::spot_price(reserve_out, reserve_in),
+        TriggerPriceSource::Twap => pool
+            .price_oracle
+            .twap(now)
+            .ok_or(DEXError::TwapUnavailable)?,
+    };
+
+    require!(order.is_triggered(price), DEXError::TriggerNotReached);
+
+    // The whole escrow is sold, so nothing is left behind when it gets closed
+    let amount = ctx.accounts.escrow.amount;
+    let keeper_tip = keeper_tip(amount)?;
+    let amount_in = amount - keeper_tip;
+
+    let quote = quote_swap(
+        pool,
+        a_to_b,
+        amount_in,
+        (reserve_in, reserve_out),
+        (
+            ctx.accounts.mint_in.decimals,
+            ctx.accounts.mint_out.decimals,
+        ),
+        0,
+        (now, Clock::get()?.slot),
+    )?;
+
+    require!(
+        quote.amount_out >= order.min_amount_out,
+        DEXError::SlippageExceeded
+    );
+
+    let id_bytes = order.id.to_le_bytes();
+    let order_seeds =
+        get_trigger_order_signer_seeds(&order.pool, &order.owner, &id_bytes, &order.bump);
+    let token_program = ctx.accounts.token_program.to_account_info();
+
+    settle_escrowed_swap(
+        pool,
+        (&order.to_account_info(), &order_seeds),
+        &ctx.accounts.escrow,
+        (&ctx.accounts.vault_in, &ctx.accounts.vault_out),
+        (
+            &ctx.accounts.keeper_token_account,
+            &ctx.accounts.owner_token_account_out,
+        ),
+        &token_program,
+        (amount_in, keeper_tip, quote.amount_out),
+    )?;
+
+    close_order_escrow(
+        (&order.to_account_info(), &order_seeds),
+        &ctx.accounts.escrow,
+        &ctx.accounts.owner.to_account_info(),
+        &token_program,
+        None,
+    )?;
+
+    let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount)?;
+    let (reserve_in, reserve_out) = (
+        reserve_in + amount_in - separated_fee,
+        reserve_out - quote.amount_out,
+    );
+    if a_to_b {
+        pool.record_price(reserve_in, reserve_out, now);
+    } else {
+        pool.record_price(reserve_out, reserve_in, now);
+    }
+
+    emit!(SwapEvent {
+        pool: pool.key(),
+        trader: order.owner,
+        mint_in: order.mint_in,
+        mint_out: order.mint_out,
+        amount_in,
+        amount_out: quote.amount_out,
+        fee_amount: quote.fee_amount,
+        fee_bps: quote.fee_bps,
+    });
+
+    emit!(TriggerOrderExecutedEvent {
+        pool: pool.key(),
+        trigger_order: order.key(),
+        owner: order.owner,
+        keeper: ctx.accounts.keeper.key(),
+        price,
+        amount_in,
+        amount_out: quote.amount_out,
+        keeper_tip,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTriggerOrder<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(
+        mut,
+        address = trigger_order.pool
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    /// CHECK: Only receives the rent of the order and its escrow, pinned by `has_one`
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(address = trigger_order.mint_in)]
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(address = trigger_order.mint_out)]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = trigger_order.escrow
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// Receives the keeper tip, in the input token
+    #[account(
+        mut,
+        token::mint = mint_in,
+    )]
+    pub keeper_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::FARM_SEED;
+use crate::errors::DEXError;
+use crate::events::FarmRewardExtendedEvent;
+use crate::state::Farm;
+
+/// Moves the end of a farm reward to `end_ts` and sets its emission rate from now on.
+/// Schedules can only be extended: a reward never ends earlier or emits slower than
+/// announced.
+pub fn extend_reward(
+    ctx: Context<ExtendReward>,
+    reward_index: u8,
+    end_ts: i64,
+    reward_per_second: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let farm = &mut ctx.accounts.farm;
+
+    // The time up to now is emitted at the previous rate
+    farm.update(now)?;
+
+    let farm_key = farm.key();
+    let reward = farm.reward_mut(reward_index)?;
+
+    require_keys_eq!(
+        reward.funder,
+        ctx.accounts.funder.key(),
+        DEXError::Unauthorized
+    );
+    require!(
+        end_ts >= reward.end_ts && now < end_ts && reward_per_second >= reward.reward_per_second,
+        DEXError::InvalidRewardSchedule
+    );
+
+    reward.end_ts = end_ts;
+    reward.reward_per_second = reward_per_second;
+
+    emit!(FarmRewardExtendedEvent {
+        farm: farm_key,
+        reward_mint: reward.mint,
+        end_ts,
+        reward_per_second,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ExtendReward<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+}
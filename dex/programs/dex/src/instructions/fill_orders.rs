@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::ORDER_BOOK_SEED;
+use crate::errors::DEXError;
+use crate::state::{OrderBook, Pool, Twamm};
+use crate::utils::{fill_limit_orders, settle_twamm};
+
+/// Permissionless crank filling the resting orders the pool price has crossed
+pub fn fill_orders(ctx: Context<FillOrders>) -> Result<()> {
+    // Long-term orders are executed up to now before the orders are priced
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        (&mut ctx.accounts.vault_a, &mut ctx.accounts.vault_b),
+        &ctx.accounts.token_program.to_account_info(),
+        (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let decimals = (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals);
+
+    fill_limit_orders(
+        &mut ctx.accounts.liquidity_pool,
+        &ctx.accounts.order_book,
+        (&ctx.accounts.vault_a, &ctx.accounts.vault_b),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FillOrders<'info> {
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = order_book
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = order_book
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
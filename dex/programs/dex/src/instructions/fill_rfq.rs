@@ -0,0 +1,319 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{MARKET_MAKER_SEED, RFQ_AUTHORITY_SEED, RFQ_NONCE_SEED};
+use crate::errors::DEXError;
+use crate::events::{RfqFilledEvent, SwapEvent};
+use crate::instructions::exchange_tokens::quote_swap;
+use crate::state::{MarketMaker, Pool, RfqNonce, RfqQuote, Twamm};
+use crate::utils::{
+    get_pool_signer_seeds, get_rfq_authority_signer_seeds, settle_twamm, verify_ed25519_signature,
+};
+
+/// Sells `amount_in` to a whitelisted market maker at the price of its signed `quote`, the
+/// maker signature being verified by an Ed25519 program instruction placed right before
+/// this one. Input beyond the quoted size is swapped through the pool when it is passed in,
+/// otherwise it is left with the taker.
+pub fn fill_rfq(
+    ctx: Context<FillRfq>,
+    quote: RfqQuote,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now <= quote.expiry, DEXError::RfqQuoteExpired);
+    require!(
+        quote.amount_in > 0 && quote.amount_out > 0 && amount_in > 0,
+        DEXError::InvalidRfqQuote
+    );
+
+    verify_ed25519_signature(
+        &ctx.accounts.instructions_sysvar,
+        &quote.maker,
+        &quote.try_to_vec()?,
+    )?;
+
+    let nonce = &mut ctx.accounts.rfq_nonce;
+    nonce.payer = ctx.accounts.taker.key();
+    nonce.expiry = quote.expiry;
+    nonce.bump = ctx.bumps.rfq_nonce;
+
+    let filled_in = amount_in.min(quote.amount_in);
+    let filled_out = quote.amount_out_for(filled_in);
+    let token_program = &ctx.accounts.token_program;
+
+    transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.taker_token_account_in.to_account_info(),
+                to: ctx.accounts.maker_token_account_in.to_account_info(),
+                authority: ctx.accounts.taker.to_account_info(),
+            },
+        ),
+        filled_in,
+    )?;
+
+    // The maker account is debited through the delegation it gave to the RFQ authority
+    let authority_seeds = get_rfq_authority_signer_seeds(&ctx.bumps.rfq_authority);
+
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.maker_token_account_out.to_account_info(),
+                to: ctx.accounts.taker_token_account_out.to_account_info(),
+                authority: ctx.accounts.rfq_authority.to_account_info(),
+            },
+            &[&authority_seeds],
+        ),
+        filled_out,
+    )?;
+
+    emit!(RfqFilledEvent {
+        maker: quote.maker,
+        taker: ctx.accounts.taker.key(),
+        nonce: quote.nonce,
+        mint_in: quote.mint_in,
+        mint_out: quote.mint_out,
+        amount_in: filled_in,
+        amount_out: filled_out,
+    });
+
+    let remainder = amount_in - filled_in;
+    let pool_amount_out = match ctx.accounts.liquidity_pool.as_mut() {
+        Some(pool) if remainder > 0 => {
+            let (Some(vault_in), Some(vault_out)) = (
+                ctx.accounts.pool_vault_in.as_mut(),
+                ctx.accounts.pool_vault_out.as_mut(),
+            ) else {
+                return err!(DEXError::WrongVaultSpecified);
+            };
+
+            require!(
+                (quote.mint_in == pool.mint_a && quote.mint_out == pool.mint_b)
+                    || (quote.mint_in == pool.mint_b && quote.mint_out == pool.mint_a),
+                DEXError::MintNotInPool
+            );
+
+            let a_to_b = quote.mint_in == pool.mint_a;
+            let (expected_vault_in, expected_vault_out) = if a_to_b {
+                (pool.vault_a, pool.vault_b)
+            } else {
+                (pool.vault_b, pool.vault_a)
+            };
+            require_keys_eq!(
+                vault_in.key(),
+                expected_vault_in,
+                DEXError::WrongVaultSpecified
+            );
+            require_keys_eq!(
+                vault_out.key(),
+                expected_vault_out,
+                DEXError::WrongVaultSpecified
+            );
+
+            // Long-term orders are executed up to now before the remainder is priced
+            let (vaults, decimals) = if a_to_b {
+                (
+                    (&mut *vault_in, &mut *vault_out),
+                    (
+                        ctx.accounts.mint_in.decimals,
+                        ctx.accounts.mint_out.decimals,
+                    ),
+                )
+            } else {
+                (
+                    (&mut *vault_out, &mut *vault_in),
+                    (
+                        ctx.accounts.mint_out.decimals,
+                        ctx.accounts.mint_in.decimals,
+                    ),
+                )
+            };
+            settle_twamm(
+                pool,
+                ctx.accounts.twamm.as_ref(),
+                (
+                    ctx.accounts.twamm_escrow_a.as_ref(),
+                    ctx.accounts.twamm_escrow_b.as_ref(),
+                ),
+                vaults,
+                &token_program.to_account_info(),
+                decimals,
+                now,
+            )?;
+
+            let (reserve_in, reserve_out) = (
+                pool.curve_reserve(a_to_b, vault_in.amount),
+                pool.curve_reserve(!a_to_b, vault_out.amount),
+            );
+
+            let swap = quote_swap(
+                pool,
+                a_to_b,
+                remainder,
+                (reserve_in, reserve_out),
+                (
+                    ctx.accounts.mint_in.decimals,
+                    ctx.accounts.mint_out.decimals,
+                ),
+                0,
+                (now, Clock::get()?.slot),
+            )?;
+
+            transfer(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.taker_token_account_in.to_account_info(),
+                        to: vault_in.to_account_info(),
+                        authority: ctx.accounts.taker.to_account_info(),
+                    },
+                ),
+                remainder,
+            )?;
+
+            let pool_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+            transfer(
+                CpiContext::new_with_signer(
+                    token_program.to_account_info(),
+                    Transfer {
+                        from: vault_out.to_account_info(),
+                        to: ctx.accounts.taker_token_account_out.to_account_info(),
+                        authority: pool.to_account_info(),
+                    },
+                    &[&pool_seeds],
+                ),
+                swap.amount_out,
+            )?;
+
+            let separated_fee = pool.separate_fee(a_to_b, swap.fee_amount)?;
+            let reserve_in = reserve_in + remainder - separated_fee;
+            let reserve_out = reserve_out - swap.amount_out;
+            if a_to_b {
+                pool.record_price(reserve_in, reserve_out, now);
+            } else {
+                pool.record_price(reserve_out, reserve_in, now);
+            }
+
+            emit!(SwapEvent {
+                pool: pool.key(),
+                trader: ctx.accounts.taker.key(),
+                mint_in: quote.mint_in,
+                mint_out: quote.mint_out,
+                amount_in: remainder,
+                amount_out: swap.amount_out,
+                fee_amount: swap.fee_amount,
+                fee_bps: swap.fee_bps,
+            });
+
+            swap.amount_out
+        }
+        _ => 0,
+    };
+
+    require!(
+        filled_out + pool_amount_out >= min_amount_out,
+        DEXError::SlippageExceeded
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(quote: RfqQuote)]
+pub struct FillRfq<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+
+    #[account(
+        seeds = [MARKET_MAKER_SEED, quote.maker.as_ref()],
+        bump = market_maker.bump
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+
+    /// Created once per quote, so a quote cannot be filled twice
+    #[account(
+        init,
+        payer = taker,
+        space = RfqNonce::MAX_SIZE,
+        seeds = [RFQ_NONCE_SEED, quote.maker.as_ref(), &quote.nonce.to_le_bytes()],
+        bump
+    )]
+    pub rfq_nonce: Account<'info, RfqNonce>,
+
+    #[account(address = quote.mint_in @ DEXError::InvalidRfqQuote)]
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(address = quote.mint_out @ DEXError::InvalidRfqQuote)]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = taker
+    )]
+    pub taker_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = taker
+    )]
+    pub taker_token_account_out: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = quote.maker
+    )]
+    pub maker_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = quote.maker
+    )]
+    pub maker_token_account_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// CHECK: Signing PDA makers delegate their quoted output to
+    #[account(
+        seeds = [RFQ_AUTHORITY_SEED],
+        bump
+    )]
+    pub rfq_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The instructions sysvar, read to find the Ed25519 signature check
+    #[account(address = solana_sdk_ids::sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// Pool the remainder of the input is swapped through, along with its vaults
+    #[account(mut)]
+    pub liquidity_pool: Option<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub pool_vault_in: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub pool_vault_out: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
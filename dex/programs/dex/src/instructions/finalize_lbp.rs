@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::LIQUIDITY_POOL_SEED;
+use crate::errors::DEXError;
+use crate::state::{Pool, PoolCurve, Twamm};
+use crate::utils::{calculate_withdrawal_amounts, get_pool_signer_seeds, settle_twamm};
+
+pub fn finalize_lbp(ctx: Context<FinalizeLbp>) -> Result<()> {
+    let PoolCurve::LiquidityBootstrapping { end_ts, .. } = ctx.accounts.liquidity_pool.curve else {
+        return err!(DEXError::NotLiquidityBootstrappingPool);
+    };
+
+    require!(
+        !ctx.accounts.liquidity_pool.lbp_finalized,
+        DEXError::SaleAlreadyFinalized
+    );
+    require!(
+        Clock::get()?.unix_timestamp >= end_ts,
+        DEXError::SaleNotEnded
+    );
+
+    // Long-term orders are executed up to now before the creator withdraws
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        (&mut ctx.accounts.vault_a, &mut ctx.accounts.vault_b),
+        &ctx.accounts.token_program.to_account_info(),
+        (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    // The creator pulls everything it provided, the pool then continues as a
+    // regular weighted pool at the end weights and is open to all providers
+    let lp_tokens_amount = ctx.accounts.creator_lp_tokens_account.amount;
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+
+    if lp_tokens_amount > 0 {
+        let (amount_a, amount_b) = calculate_withdrawal_amounts(
+            lp_tokens_amount,
+            total_lp_supply,
+            ctx.accounts.vault_a.amount,
+            ctx.accounts.vault_b.amount,
+        )?;
+
+        let mint_a_key = ctx.accounts.mint_a.key();
+        let mint_b_key = ctx.accounts.mint_b.key();
+
+        let signer_seeds =
+            get_pool_signer_seeds(&mint_a_key, &mint_b_key, &ctx.bumps.liquidity_pool);
+        let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    from: ctx.accounts.creator_lp_tokens_account.to_account_info(),
+                    authority: ctx.accounts.creator.to_account_info(),
+                },
+            ),
+            lp_tokens_amount,
+        )?;
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_a.to_account_info(),
+                    to: ctx.accounts.creator_token_a_account.to_account_info(),
+                    authority: ctx.accounts.liquidity_pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount_a,
+        )?;
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_b.to_account_info(),
+                    to: ctx.accounts.creator_token_b_account.to_account_info(),
+                    authority: ctx.accounts.liquidity_pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount_b,
+        )?;
+    }
+
+    ctx.accounts.liquidity_pool.lbp_finalized = true;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FinalizeLbp<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        has_one = vault_a,
+        has_one = vault_b,
+        has_one = mint_a,
+        has_one = mint_b,
+        has_one = lp_mint,
+        constraint = liquidity_pool.admin == creator.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = creator
+    )]
+    pub creator_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = creator
+    )]
+    pub creator_token_a_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = creator
+    )]
+    pub creator_token_b_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::FARM_SEED;
+use crate::errors::DEXError;
+use crate::events::FarmRewardFundedEvent;
+use crate::state::Farm;
+
+/// Deposits `amount` of a farm reward, which is emitted along its schedule
+pub fn fund_reward(ctx: Context<FundReward>, reward_index: u8, amount: u64) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    // Slots the reward ran dry in are not paid retroactively
+    farm.update(Clock::get()?.unix_timestamp)?;
+
+    let farm_key = farm.key();
+    let reward = farm.reward_mut(reward_index)?;
+
+    require_keys_eq!(
+        reward.funder,
+        ctx.accounts.funder.key(),
+        DEXError::Unauthorized
+    );
+    require_keys_eq!(
+        reward.vault,
+        ctx.accounts.reward_vault.key(),
+        DEXError::InvalidFarm
+    );
+
+    reward.amount_funded = reward
+        .amount_funded
+        .checked_add(amount)
+        .ok_or(DEXError::MathOverflow)?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.funder_token_account.to_account_info(),
+                to: ctx.accounts.reward_vault.to_account_info(),
+                authority: ctx.accounts.funder.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(FarmRewardFundedEvent {
+        farm: farm_key,
+        reward_mint: reward.mint,
+        funder: reward.funder,
+        amount,
+        end_ts: reward.end_ts,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct FundReward<'info> {
+    pub funder: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.pool.as_ref()],
+        bump = farm.bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    pub reward_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = funder
+    )]
+    pub funder_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = farm
+    )]
+    pub reward_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::BATCH_AUCTION_SEED;
+use crate::errors::DEXError;
+use crate::state::{BatchAuction, Pool, PoolCurve};
+
+/// Turns the pool into a batch auction pool: from now on its swaps are queued per slot
+/// and cleared at a single price by `settle_batch`. Orders selling less than the minimum
+/// amount of their input token are rejected.
+pub fn initialize_batch_auction(
+    ctx: Context<InitializeBatchAuction>,
+    min_order_amount_a: u64,
+    min_order_amount_b: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    require!(
+        min_order_amount_a > 0 && min_order_amount_b > 0,
+        DEXError::BatchOrderTooSmall
+    );
+
+    require!(
+        pool.curve == PoolCurve::ConstantProduct,
+        DEXError::ConstantProductOnly
+    );
+    // Long-term orders swap against the pool, which batched pools only do in settle_batch
+    require!(pool.twamm == Pubkey::default(), DEXError::InvalidTwamm);
+
+    pool.batch_auction = true;
+
+    let batch_auction = &mut ctx.accounts.batch_auction;
+    batch_auction.pool = pool.key();
+    batch_auction.escrow_a = ctx.accounts.escrow_a.key();
+    batch_auction.escrow_b = ctx.accounts.escrow_b.key();
+    batch_auction.slot = 0;
+    batch_auction.orders = Vec::new();
+    batch_auction.next_slot = 0;
+    batch_auction.next_orders = Vec::new();
+    batch_auction.min_order_amount_a = min_order_amount_a;
+    batch_auction.min_order_amount_b = min_order_amount_b;
+    batch_auction.bump = ctx.bumps.batch_auction;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeBatchAuction<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = BatchAuction::MAX_SIZE,
+        seeds = [BATCH_AUCTION_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub batch_auction: Account<'info, BatchAuction>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = mint_a,
+        associated_token::authority = batch_auction,
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = mint_b,
+        associated_token::authority = batch_auction,
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
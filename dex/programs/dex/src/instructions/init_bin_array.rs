@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::BIN_ARRAY_SEED;
+use crate::state::{BinArray, BinPool};
+
+pub fn initialize_bin_array(ctx: Context<InitializeBinArray>, index: i32) -> Result<()> {
+    BinPool::bin_array_bit(index)?;
+
+    let mut bin_array = ctx.accounts.bin_array.load_init()?;
+    bin_array.pool = ctx.accounts.bin_pool.key();
+    bin_array.index = index;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(index: i32)]
+pub struct InitializeBinArray<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub bin_pool: Account<'info, BinPool>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = BinArray::MAX_SIZE,
+        seeds = [BIN_ARRAY_SEED, bin_pool.key().as_ref(), index.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bin_array: AccountLoader<'info, BinArray>,
+
+    pub system_program: Program<'info, System>,
+}
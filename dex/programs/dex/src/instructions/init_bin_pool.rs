@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::BIN_POOL_SEED;
+use crate::curves::bins::bin_price;
+use crate::errors::DEXError;
+use crate::state::{BinArray, BinPool, BinWithdrawal};
+
+pub fn initialize_bin_pool(
+    ctx: Context<InitializeBinPool>,
+    bin_step: u16,
+    fee_bps: u64,
+    active_bin_id: i32,
+) -> Result<()> {
+    // Bins charge the fee on top of the amount they consume, so it has to stay below 100%
+    require!(fee_bps < 10_000, DEXError::InvalidBPSValue);
+    require!(
+        bin_step > 0 && bin_step <= 10_000,
+        DEXError::InvalidCurveParameters
+    );
+
+    // Both the active bin and its price have to be representable
+    BinPool::bin_array_bit(BinArray::index_for(active_bin_id))?;
+    bin_price(active_bin_id, bin_step)?;
+
+    let pool = &mut ctx.accounts.bin_pool;
+
+    pool.vault_a = ctx.accounts.vault_a.key();
+    pool.vault_b = ctx.accounts.vault_b.key();
+    pool.mint_a = ctx.accounts.mint_a.key();
+    pool.mint_b = ctx.accounts.mint_b.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.fee_bps = fee_bps;
+    pool.bin_step = bin_step;
+    pool.active_bin_id = active_bin_id;
+    pool.bin_array_bitmap = 0;
+    pool.pending_withdrawal = BinWithdrawal::default();
+    pool.bump = ctx.bumps.bin_pool;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeBinPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = BinPool::MAX_SIZE,
+        seeds = [BIN_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub bin_pool: Account<'info, BinPool>,
+
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = 9,
+        mint::authority = bin_pool,
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = bin_pool,
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = bin_pool,
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::CONCENTRATED_POOL_SEED;
+use crate::curves::concentrated::tick_at_sqrt_price;
+use crate::errors::DEXError;
+use crate::state::ConcentratedPool;
+
+pub fn initialize_concentrated_pool(
+    ctx: Context<InitializeConcentratedPool>,
+    tick_spacing: u16,
+    fee_bps: u64,
+    initial_sqrt_price: u128,
+) -> Result<()> {
+    // The swap step charges the fee on top of the amount moving the price, so it has to stay below 100%
+    require!(fee_bps < 10_000, DEXError::InvalidBPSValue);
+    require!(tick_spacing > 0, DEXError::InvalidCurveParameters);
+
+    let pool = &mut ctx.accounts.concentrated_pool;
+
+    pool.vault_a = ctx.accounts.vault_a.key();
+    pool.vault_b = ctx.accounts.vault_b.key();
+    pool.mint_a = ctx.accounts.mint_a.key();
+    pool.mint_b = ctx.accounts.mint_b.key();
+    pool.fee_bps = fee_bps;
+    pool.tick_spacing = tick_spacing;
+    pool.sqrt_price = initial_sqrt_price;
+    pool.tick_current = tick_at_sqrt_price(initial_sqrt_price)?;
+    pool.liquidity = 0;
+    pool.fee_growth_global_a = 0;
+    pool.fee_growth_global_b = 0;
+    pool.bump = ctx.bumps.concentrated_pool;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConcentratedPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = ConcentratedPool::MAX_SIZE,
+        seeds = [CONCENTRATED_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub concentrated_pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = concentrated_pool,
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = concentrated_pool,
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::FARM_SEED;
+use crate::errors::DEXError;
+use crate::state::{Farm, Pool};
+
+/// Opens the farm of a pool, which emits nothing until rewards are added to it
+pub fn initialize_farm(ctx: Context<InitializeFarm>) -> Result<()> {
+    let farm = &mut ctx.accounts.farm;
+
+    farm.pool = ctx.accounts.liquidity_pool.key();
+    farm.lp_mint = ctx.accounts.lp_mint.key();
+    farm.lp_vault = ctx.accounts.lp_vault.key();
+    farm.total_staked = 0;
+    farm.last_update_ts = Clock::get()?.unix_timestamp;
+    farm.rewards = Vec::new();
+    farm.bump = ctx.bumps.farm;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeFarm<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        address = liquidity_pool.lp_mint @ DEXError::MintNotInPool
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Farm::MAX_SIZE,
+        seeds = [FARM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = lp_mint,
+        associated_token::authority = farm,
+    )]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
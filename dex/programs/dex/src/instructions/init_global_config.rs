@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::errors::DEXError;
+use crate::state::GlobalConfig;
+
+/// Creates the program-wide config, the signer becomes its admin
+pub fn initialize_global_config(
+    ctx: Context<InitializeGlobalConfig>,
+    max_referral_fee_share_bps: u64,
+) -> Result<()> {
+    require!(
+        max_referral_fee_share_bps <= 10_000,
+        DEXError::InvalidBPSValue
+    );
+
+    let global_config = &mut ctx.accounts.global_config;
+
+    global_config.admin = ctx.accounts.admin.key();
+    global_config.max_referral_fee_share_bps = max_referral_fee_share_bps;
+    global_config.fee_tiers = Vec::new();
+    global_config.bump = ctx.bumps.global_config;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeGlobalConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = GlobalConfig::MAX_SIZE,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+}
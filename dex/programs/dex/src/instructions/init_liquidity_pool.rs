@@ -1,4 +1,5 @@
 use crate::constants::LIQUIDITY_POOL_SEED;
+use crate::curves::decimal_multipliers;
 use crate::errors::DEXError;
 use anchor_lang::prelude::*;
 use anchor_spl::{
@@ -7,26 +8,97 @@ use anchor_spl::{
     token_interface::{Mint, TokenAccount},
 };
 
-use crate::state::Pool;
+use crate::state::{Pool, PoolCurve, PriceOracle};
 
 pub fn initialize_liquidity_pool(
     ctx: Context<InitializeLiquidityPool>,
     initial_fee_bps: u64,
+    curve: PoolCurve,
 ) -> Result<()> {
     require!(initial_fee_bps <= 10_000, DEXError::InvalidBPSValue);
+    validate_curve(
+        &curve,
+        ctx.accounts.mint_a.decimals,
+        ctx.accounts.mint_b.decimals,
+    )?;
 
     let liquidity_pool = &mut ctx.accounts.liquidity_pool;
 
-    let (vault_a, vault_b) = if ctx.accounts.mint_a.key() < ctx.accounts.mint_b.key() {
-        (&ctx.accounts.vault_a, &ctx.accounts.vault_b)
-    } else {
-        (&ctx.accounts.vault_b, &ctx.accounts.vault_a)
-    };
+    let (vault_a, vault_b, mint_a, mint_b) =
+        if ctx.accounts.mint_a.key() < ctx.accounts.mint_b.key() {
+            (
+                &ctx.accounts.vault_a,
+                &ctx.accounts.vault_b,
+                &ctx.accounts.mint_a,
+                &ctx.accounts.mint_b,
+            )
+        } else {
+            (
+                &ctx.accounts.vault_b,
+                &ctx.accounts.vault_a,
+                &ctx.accounts.mint_b,
+                &ctx.accounts.mint_a,
+            )
+        };
 
     liquidity_pool.vault_a = vault_a.key();
     liquidity_pool.vault_b = vault_b.key();
+    liquidity_pool.mint_a = mint_a.key();
+    liquidity_pool.mint_b = mint_b.key();
     liquidity_pool.lp_mint = ctx.accounts.lp_mint.key();
-    liquidity_pool.fee_bps = initial_fee_bps;
+    liquidity_pool.fee_bps_a_to_b = initial_fee_bps;
+    liquidity_pool.fee_bps_b_to_a = initial_fee_bps;
+    liquidity_pool.bump = ctx.bumps.liquidity_pool;
+    liquidity_pool.admin = ctx.accounts.signer.key();
+    liquidity_pool.curve = curve;
+    liquidity_pool.lbp_finalized = false;
+    liquidity_pool.dynamic_fee = None;
+    liquidity_pool.impact_fee = None;
+    liquidity_pool.referral_fee_share_bps = 0;
+    liquidity_pool.price_oracle = PriceOracle::default();
+    liquidity_pool.batch_auction = false;
+    liquidity_pool.book_a = 0;
+    liquidity_pool.book_b = 0;
+    liquidity_pool.separate_fees = false;
+    liquidity_pool.fee_growth_global_a = 0;
+    liquidity_pool.fee_growth_global_b = 0;
+    liquidity_pool.fees_a = 0;
+    liquidity_pool.fees_b = 0;
+    liquidity_pool.position_liquidity = 0;
+    liquidity_pool.locked_liquidity = 0;
+    liquidity_pool.swap_cap_slot = 0;
+    liquidity_pool.swap_cap_used_a_to_b = 0;
+    liquidity_pool.swap_cap_used_b_to_a = 0;
+    liquidity_pool.twamm = Pubkey::default();
+
+    Ok(())
+}
+
+fn validate_curve(curve: &PoolCurve, decimals_a: u8, decimals_b: u8) -> Result<()> {
+    match *curve {
+        PoolCurve::ConstantProduct => {}
+        PoolCurve::LiquidityBootstrapping {
+            start_weight_a_bps,
+            end_weight_a_bps,
+            start_ts,
+            end_ts,
+        } => {
+            // Both tokens need a non-zero weight at every point of the schedule
+            require!(
+                (1..10_000).contains(&start_weight_a_bps)
+                    && (1..10_000).contains(&end_weight_a_bps),
+                DEXError::InvalidCurveParameters
+            );
+            require!(start_ts < end_ts, DEXError::InvalidCurveParameters);
+        }
+        PoolCurve::Stable => {
+            decimal_multipliers(decimals_a, decimals_b)?;
+        }
+        PoolCurve::Pegged { rate, .. } => {
+            require!(rate > 0, DEXError::InvalidCurveParameters);
+            decimal_multipliers(decimals_a, decimals_b)?;
+        }
+    }
 
     Ok(())
 }
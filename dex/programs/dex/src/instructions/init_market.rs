@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MARKET_SEED;
+use crate::errors::DEXError;
+use crate::state::{Market, Pool, PoolCurve};
+
+/// Opens the order book market of a constant product pool, trading out of the pool vaults
+pub fn initialize_market(ctx: Context<InitializeMarket>) -> Result<()> {
+    require!(
+        ctx.accounts.liquidity_pool.curve == PoolCurve::ConstantProduct,
+        DEXError::ConstantProductOnly
+    );
+
+    let mut market = ctx.accounts.market.load_init()?;
+
+    market.pool = ctx.accounts.liquidity_pool.key();
+    market.bid_count = 0;
+    market.ask_count = 0;
+    market.next_order_id = 0;
+    market.bump = ctx.bumps.market;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeMarket<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Market::MAX_SIZE,
+        seeds = [MARKET_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::MULTI_POOL_SEED;
+use crate::curves::multi::{multipliers, MAX_AMPLIFICATION};
+use crate::errors::DEXError;
+use crate::state::{MultiPool, MultiPoolCurve, PoolToken};
+
+/// Creates a pool over the mints passed as remaining accounts, followed by their vaults
+/// (the associated token accounts of the pool, created beforehand). Mints have to be
+/// strictly increasing. `weights_bps` lists the token weights of weighted pools and
+/// is left empty for StableSwap pools.
+pub fn initialize_multi_pool<'info>(
+    ctx: Context<'_, '_, 'info, 'info, InitializeMultiPool<'info>>,
+    fee_bps: u64,
+    curve: MultiPoolCurve,
+    weights_bps: Vec<u64>,
+) -> Result<()> {
+    require!(fee_bps < 10_000, DEXError::InvalidBPSValue);
+
+    let (mints, vaults) = ctx
+        .remaining_accounts
+        .split_at(ctx.remaining_accounts.len() / 2);
+
+    require!(
+        mints.len() == vaults.len() && (2..=MultiPool::MAX_TOKENS).contains(&mints.len()),
+        DEXError::InvalidTokenAccounts
+    );
+
+    match curve {
+        MultiPoolCurve::StableSwap { amplification } => {
+            require!(
+                (1..=MAX_AMPLIFICATION).contains(&amplification) && weights_bps.is_empty(),
+                DEXError::InvalidCurveParameters
+            );
+        }
+        MultiPoolCurve::Weighted => {
+            require!(
+                weights_bps.len() == mints.len()
+                    && weights_bps.iter().all(|weight| *weight > 0)
+                    && weights_bps.iter().sum::<u64>() == 10_000,
+                DEXError::InvalidCurveParameters
+            );
+        }
+    }
+
+    let pool_key = ctx.accounts.multi_pool.key();
+    let mut tokens = Vec::with_capacity(mints.len());
+
+    for (index, (mint_info, vault_info)) in mints.iter().zip(vaults).enumerate() {
+        let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+        let vault = InterfaceAccount::<TokenAccount>::try_from(vault_info)?;
+
+        if let Some(previous) = tokens.last().map(|token: &PoolToken| token.mint) {
+            require!(previous < mint.key(), DEXError::InvalidMintOrdering);
+        }
+
+        require_keys_eq!(
+            vault.key(),
+            get_associated_token_address(&pool_key, &mint.key()),
+            DEXError::InvalidTokenAccounts
+        );
+
+        tokens.push(PoolToken {
+            mint: mint.key(),
+            vault: vault.key(),
+            balance: 0,
+            decimals: mint.decimals,
+            weight_bps: weights_bps.get(index).copied().unwrap_or(0),
+        });
+    }
+
+    // Every token has to be representable in the shared decimals
+    multipliers(&tokens)?;
+
+    let pool = &mut ctx.accounts.multi_pool;
+
+    pool.admin = ctx.accounts.signer.key();
+    pool.lp_mint = ctx.accounts.lp_mint.key();
+    pool.fee_bps = fee_bps;
+    pool.curve = curve;
+    pool.tokens = tokens;
+    pool.bump = ctx.bumps.multi_pool;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeMultiPool<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = MultiPool::MAX_SIZE,
+        seeds = [MULTI_POOL_SEED, lp_mint.key().as_ref()],
+        bump
+    )]
+    pub multi_pool: Account<'info, MultiPool>,
+
+    #[account(
+        init,
+        payer = signer,
+        mint::decimals = 9,
+        mint::authority = multi_pool,
+    )]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
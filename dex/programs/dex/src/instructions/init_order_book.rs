@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::ORDER_BOOK_SEED;
+use crate::errors::DEXError;
+use crate::state::{OrderBook, Pool};
+
+pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+    let mut order_book = ctx.accounts.order_book.load_init()?;
+
+    order_book.pool = ctx.accounts.liquidity_pool.key();
+    order_book.escrow_a = ctx.accounts.escrow_a.key();
+    order_book.escrow_b = ctx.accounts.escrow_b.key();
+    order_book.next_order_id = 0;
+    order_book.bump = ctx.bumps.order_book;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeOrderBook<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = OrderBook::MAX_SIZE,
+        seeds = [ORDER_BOOK_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = order_book,
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = order_book,
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
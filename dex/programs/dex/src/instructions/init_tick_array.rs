@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::TICK_ARRAY_SEED;
+use crate::errors::DEXError;
+use crate::state::{ConcentratedPool, TickArray};
+
+pub fn initialize_tick_array(
+    ctx: Context<InitializeTickArray>,
+    start_tick_index: i32,
+) -> Result<()> {
+    let tick_spacing = ctx.accounts.concentrated_pool.tick_spacing;
+
+    require!(
+        TickArray::start_index_for(start_tick_index, tick_spacing) == start_tick_index,
+        DEXError::InvalidTickIndex
+    );
+
+    let mut tick_array = ctx.accounts.tick_array.load_init()?;
+    tick_array.pool = ctx.accounts.concentrated_pool.key();
+    tick_array.start_tick_index = start_tick_index;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(start_tick_index: i32)]
+pub struct InitializeTickArray<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    pub concentrated_pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = TickArray::MAX_SIZE,
+        seeds = [
+            TICK_ARRAY_SEED,
+            concentrated_pool.key().as_ref(),
+            start_tick_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub tick_array: AccountLoader<'info, TickArray>,
+
+    pub system_program: Program<'info, System>,
+}
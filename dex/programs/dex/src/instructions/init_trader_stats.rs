@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::TRADER_STATS_SEED;
+use crate::state::{Pool, TraderStats};
+
+/// Opens the account tracking the swap volume of the signer on a pool. Swaps passing it
+/// count towards the volume tiers of the global config.
+pub fn initialize_trader_stats(ctx: Context<InitializeTraderStats>) -> Result<()> {
+    let trader_stats = &mut ctx.accounts.trader_stats;
+
+    trader_stats.trader = ctx.accounts.trader.key();
+    trader_stats.pool = ctx.accounts.liquidity_pool.key();
+    trader_stats.bump = ctx.bumps.trader_stats;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeTraderStats<'info> {
+    #[account(mut)]
+    pub trader: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        init,
+        payer = trader,
+        space = TraderStats::MAX_SIZE,
+        seeds = [TRADER_STATS_SEED, liquidity_pool.key().as_ref(), trader.key().as_ref()],
+        bump
+    )]
+    pub trader_stats: Account<'info, TraderStats>,
+
+    pub system_program: Program<'info, System>,
+}
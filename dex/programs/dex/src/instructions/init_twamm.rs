@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TWAMM_SEED;
+use crate::errors::DEXError;
+use crate::state::{Pool, Twamm};
+
+/// Opens the long-term orders of the pool. From now on every instruction moving the pool
+/// reserves requires the TWAMM and executes it first. Orders end on multiples of
+/// `order_interval` seconds, which bounds the expiries the TWAMM has to track.
+pub fn initialize_twamm(ctx: Context<InitializeTwamm>, order_interval: i64) -> Result<()> {
+    require!(order_interval > 0, DEXError::InvalidLongTermOrder);
+
+    // Batched swaps cannot execute the orders, their pool only trades in settle_batch
+    require!(
+        !ctx.accounts.liquidity_pool.batch_auction,
+        DEXError::BatchAuctionPool
+    );
+
+    ctx.accounts.liquidity_pool.twamm = ctx.accounts.twamm.key();
+
+    let mut twamm = ctx.accounts.twamm.load_init()?;
+
+    twamm.pool = ctx.accounts.liquidity_pool.key();
+    twamm.escrow_a = ctx.accounts.escrow_a.key();
+    twamm.escrow_b = ctx.accounts.escrow_b.key();
+    twamm.last_execution_ts = Clock::get()?.unix_timestamp;
+    twamm.order_interval = order_interval;
+    twamm.bump = ctx.bumps.twamm;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeTwamm<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init,
+        payer = signer,
+        space = Twamm::MAX_SIZE,
+        seeds = [TWAMM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub twamm: AccountLoader<'info, Twamm>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = twamm,
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = twamm,
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{LIQUIDITY_LOCK_SEED, LOCK_VAULT_SEED};
+use crate::errors::DEXError;
+use crate::events::LiquidityLockedEvent;
+use crate::state::{LiquidityLock, Pool};
+
+/// Locks `lp_amount` LP tokens of the pool in a lock vault owned by the pool until
+/// `unlock_ts`. With `linear_vesting` they unlock gradually from now until then instead of
+/// all at once.
+pub fn lock_liquidity(
+    ctx: Context<LockLiquidity>,
+    id: u64,
+    lp_amount: u64,
+    unlock_ts: i64,
+    linear_vesting: bool,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(
+        lp_amount > 0 && unlock_ts > now,
+        DEXError::InvalidLiquidityLock
+    );
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_lp_account.to_account_info(),
+                to: ctx.accounts.lock_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+    pool.locked_liquidity = pool
+        .locked_liquidity
+        .checked_add(lp_amount)
+        .ok_or(DEXError::MathOverflow)?;
+
+    let lock = &mut ctx.accounts.liquidity_lock;
+    lock.owner = ctx.accounts.owner.key();
+    lock.pool = pool.key();
+    lock.id = id;
+    lock.lp_amount = lp_amount;
+    lock.withdrawn = 0;
+    lock.locked_at = now;
+    lock.unlock_ts = unlock_ts;
+    lock.linear_vesting = linear_vesting;
+    lock.bump = ctx.bumps.liquidity_lock;
+
+    emit!(LiquidityLockedEvent {
+        pool: pool.key(),
+        liquidity_lock: lock.key(),
+        owner: lock.owner,
+        lp_amount,
+        unlock_ts,
+        linear_vesting,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct LockLiquidity<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = lp_mint
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = LiquidityLock::MAX_SIZE,
+        seeds = [
+            LIQUIDITY_LOCK_SEED,
+            liquidity_pool.key().as_ref(),
+            owner.key().as_ref(),
+            &id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+
+    #[account(
+        init,
+        payer = owner,
+        seeds = [LOCK_VAULT_SEED, liquidity_lock.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = liquidity_pool,
+        token::token_program = token_program,
+    )]
+    pub lock_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -9,3 +9,228 @@ pub use withdraw_liquidity::*;
 
 pub mod exchange_tokens;
 pub use exchange_tokens::*;
+
+pub mod quote_exchange;
+pub use quote_exchange::*;
+
+pub mod init_global_config;
+pub use init_global_config::*;
+
+pub mod update_global_config;
+pub use update_global_config::*;
+
+pub mod set_fee_tiers;
+pub use set_fee_tiers::*;
+
+pub mod init_trader_stats;
+pub use init_trader_stats::*;
+
+pub mod set_referral_fee_share;
+pub use set_referral_fee_share::*;
+
+pub mod register_referrer;
+pub use register_referrer::*;
+
+pub mod set_swap_fees;
+pub use set_swap_fees::*;
+
+pub mod set_dynamic_fee;
+pub use set_dynamic_fee::*;
+
+pub mod set_impact_fee;
+pub use set_impact_fee::*;
+
+pub mod init_order_book;
+pub use init_order_book::*;
+
+pub mod place_limit_order;
+pub use place_limit_order::*;
+
+pub mod cancel_order;
+pub use cancel_order::*;
+
+pub mod claim_filled;
+pub use claim_filled::*;
+
+pub mod fill_orders;
+pub use fill_orders::*;
+
+pub mod init_twamm;
+pub use init_twamm::*;
+
+pub mod submit_long_term_order;
+pub use submit_long_term_order::*;
+
+pub mod cancel_long_term_order;
+pub use cancel_long_term_order::*;
+
+pub mod withdraw_proceeds;
+pub use withdraw_proceeds::*;
+
+pub mod settle_long_term_order;
+pub use settle_long_term_order::*;
+
+pub mod execute_long_term_orders;
+pub use execute_long_term_orders::*;
+
+pub mod open_dca_order;
+pub use open_dca_order::*;
+
+pub mod execute_dca;
+pub use execute_dca::*;
+
+pub mod close_dca_order;
+pub use close_dca_order::*;
+
+pub mod place_trigger_order;
+pub use place_trigger_order::*;
+
+pub mod execute_trigger_order;
+pub use execute_trigger_order::*;
+
+pub mod cancel_trigger_order;
+pub use cancel_trigger_order::*;
+
+pub mod add_market_maker;
+pub use add_market_maker::*;
+
+pub mod remove_market_maker;
+pub use remove_market_maker::*;
+
+pub mod fill_rfq;
+pub use fill_rfq::*;
+
+pub mod close_rfq_nonce;
+pub use close_rfq_nonce::*;
+
+pub mod execute_intent;
+pub use execute_intent::*;
+
+pub mod close_intent_nonce;
+pub use close_intent_nonce::*;
+
+pub mod init_batch_auction;
+pub use init_batch_auction::*;
+
+pub mod settle_batch;
+pub use settle_batch::*;
+
+pub mod init_market;
+pub use init_market::*;
+
+pub mod place_market_order;
+pub use place_market_order::*;
+
+pub mod close_market_order;
+pub use close_market_order::*;
+
+pub mod swap_best;
+pub use swap_best::*;
+
+pub mod init_farm;
+pub use init_farm::*;
+
+pub mod add_farm_reward;
+pub use add_farm_reward::*;
+
+pub mod fund_reward;
+pub use fund_reward::*;
+
+pub mod extend_reward;
+pub use extend_reward::*;
+
+pub mod stake_lp;
+pub use stake_lp::*;
+
+pub mod unstake_lp;
+pub use unstake_lp::*;
+
+pub mod claim_rewards;
+pub use claim_rewards::*;
+
+pub mod open_lp_position;
+pub use open_lp_position::*;
+
+pub mod close_lp_position;
+pub use close_lp_position::*;
+
+pub mod quote_lp_position;
+pub use quote_lp_position::*;
+
+pub mod set_fee_separation;
+pub use set_fee_separation::*;
+
+pub mod collect_fees;
+pub use collect_fees::*;
+
+pub mod lock_liquidity;
+pub use lock_liquidity::*;
+
+pub mod unlock_liquidity;
+pub use unlock_liquidity::*;
+
+pub mod quote_locked_liquidity;
+pub use quote_locked_liquidity::*;
+
+pub mod finalize_lbp;
+pub use finalize_lbp::*;
+
+pub mod init_concentrated_pool;
+pub use init_concentrated_pool::*;
+
+pub mod init_tick_array;
+pub use init_tick_array::*;
+
+pub mod open_position;
+pub use open_position::*;
+
+pub mod increase_liquidity;
+pub use increase_liquidity::*;
+
+pub mod decrease_liquidity;
+pub use decrease_liquidity::*;
+
+pub mod collect_concentrated_fees;
+pub use collect_concentrated_fees::*;
+
+pub mod exchange_tokens_concentrated;
+pub use exchange_tokens_concentrated::*;
+
+pub mod init_bin_pool;
+pub use init_bin_pool::*;
+
+pub mod init_bin_array;
+pub use init_bin_array::*;
+
+pub mod add_bin_liquidity;
+pub use add_bin_liquidity::*;
+
+pub mod withdraw_bin_liquidity;
+pub use withdraw_bin_liquidity::*;
+
+pub mod continue_bin_withdrawal;
+pub use continue_bin_withdrawal::*;
+
+pub mod exchange_tokens_bin_pool;
+pub use exchange_tokens_bin_pool::*;
+
+pub mod update_peg_rate;
+pub use update_peg_rate::*;
+
+pub mod init_multi_pool;
+pub use init_multi_pool::*;
+
+pub mod add_multi_liquidity;
+pub use add_multi_liquidity::*;
+
+pub mod withdraw_multi_liquidity;
+pub use withdraw_multi_liquidity::*;
+
+pub mod add_single_token_liquidity;
+pub use add_single_token_liquidity::*;
+
+pub mod withdraw_single_token_liquidity;
+pub use withdraw_single_token_liquidity::*;
+
+pub mod exchange_tokens_multi;
+pub use exchange_tokens_multi::*;
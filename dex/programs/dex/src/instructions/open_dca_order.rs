@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::DCA_ORDER_SEED;
+use crate::errors::DEXError;
+use crate::state::{DcaOrder, Pool};
+
+/// Escrows `deposit` of `mint_in` to be swapped into `mint_out` by keepers, `amount_per_cycle`
+/// at a time and at most once every `cycle_interval` seconds. The first cycle is due right away.
+pub fn open_dca_order(
+    ctx: Context<OpenDcaOrder>,
+    id: u64,
+    deposit: u64,
+    amount_per_cycle: u64,
+    cycle_interval: i64,
+    min_out_per_cycle: u64,
+) -> Result<()> {
+    require!(
+        deposit > 0 && amount_per_cycle > 0 && cycle_interval > 0,
+        DEXError::InvalidDcaOrder
+    );
+
+    let pool = &ctx.accounts.liquidity_pool;
+    let mint_in = ctx.accounts.mint_in.key();
+    let mint_out = ctx.accounts.mint_out.key();
+
+    require!(
+        (mint_in == pool.mint_a && mint_out == pool.mint_b)
+            || (mint_in == pool.mint_b && mint_out == pool.mint_a),
+        DEXError::MintNotInPool
+    );
+
+    let order = &mut ctx.accounts.dca_order;
+    order.owner = ctx.accounts.owner.key();
+    order.pool = pool.key();
+    order.mint_in = mint_in;
+    order.mint_out = mint_out;
+    order.escrow = ctx.accounts.escrow.key();
+    order.id = id;
+    order.amount_per_cycle = amount_per_cycle;
+    order.cycle_interval = cycle_interval;
+    order.min_out_per_cycle = min_out_per_cycle;
+    order.next_cycle_ts = Clock::get()?.unix_timestamp;
+    order.bump = ctx.bumps.dca_order;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_token_account_in.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        deposit,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct OpenDcaOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_in.key() != mint_out.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = DcaOrder::MAX_SIZE,
+        seeds = [
+            DCA_ORDER_SEED,
+            liquidity_pool.key().as_ref(),
+            owner.key().as_ref(),
+            &id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_in,
+        associated_token::authority = dca_order
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
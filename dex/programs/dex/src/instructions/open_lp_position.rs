@@ -0,0 +1,276 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{self, transfer, Token, Transfer},
+    token_2022::{self, spl_token_2022::instruction::AuthorityType, SetAuthority, Token2022},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{LIQUIDITY_POOL_SEED, LP_POSITION_SEED, POSITION_LP_VAULT_SEED};
+use crate::errors::DEXError;
+use crate::events::LpPositionOpenedEvent;
+use crate::state::{LpPosition, Pool, PoolCurve, Twamm};
+use crate::utils::{calculate_deposit_amounts, get_pool_signer_seeds, settle_twamm};
+
+/// Deposits liquidity like `add_liquidity_to_pool`, but into a position represented by a
+/// Token-2022 NFT instead of fungible LP tokens. The position records what was deposited
+/// and the pool fee growth at entry, so the fees it earns can be told apart.
+pub fn open_lp_position(
+    ctx: Context<OpenLpPosition>,
+    token_a_amount: u64,
+    token_b_amount: u64,
+) -> Result<()> {
+    // Long-term orders are executed up to now before the deposit is priced
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_deref(),
+            ctx.accounts.twamm_escrow_b.as_deref(),
+        ),
+        (&mut *ctx.accounts.vault_a, &mut *ctx.accounts.vault_b),
+        &ctx.accounts.token_program.to_account_info(),
+        (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals),
+        Clock::get()?.unix_timestamp,
+    )?;
+
+    let pool = &ctx.accounts.liquidity_pool;
+
+    require!(
+        pool.curve == PoolCurve::ConstantProduct,
+        DEXError::LpPositionUnsupported
+    );
+
+    // Positions join a pool whose price is already set by fungible liquidity
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+    require!(total_lp_supply > 0, DEXError::EmptyPool);
+
+    let total_a = pool.curve_reserve(true, ctx.accounts.vault_a.amount);
+    let total_b = pool.curve_reserve(false, ctx.accounts.vault_b.amount);
+    let (a_amount, b_amount, liquidity) = calculate_deposit_amounts(
+        token_a_amount,
+        token_b_amount,
+        total_lp_supply,
+        total_a,
+        total_b,
+    )?;
+
+    require!(liquidity > 0, DEXError::InvalidAmountOfLiquidation);
+
+    let (a_amount, b_amount, liquidity) = (a_amount as u64, b_amount as u64, liquidity as u64);
+
+    let signer_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    for (from, to, amount) in [
+        (
+            &ctx.accounts.owner_token_a_account,
+            &ctx.accounts.vault_a,
+            a_amount,
+        ),
+        (
+            &ctx.accounts.owner_token_b_account,
+            &ctx.accounts.vault_b,
+            b_amount,
+        ),
+    ] {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+    }
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::MintTo {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                to: ctx.accounts.position_lp_vault.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        liquidity,
+    )?;
+
+    // A single NFT is minted, then the mint authority is dropped for good
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            token_2022::MintTo {
+                mint: ctx.accounts.position_mint.to_account_info(),
+                to: ctx.accounts.owner_position_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        1,
+    )?;
+
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_2022_program.to_account_info(),
+            SetAuthority {
+                current_authority: pool.to_account_info(),
+                account_or_mint: ctx.accounts.position_mint.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    let position = &mut ctx.accounts.position;
+    position.pool = pool.key();
+    position.position_mint = ctx.accounts.position_mint.key();
+    position.liquidity = liquidity;
+    position.deposited_a = a_amount;
+    position.deposited_b = b_amount;
+    position.fee_growth_entry = LpPosition::fee_growth(
+        total_a + a_amount,
+        total_b + b_amount,
+        total_lp_supply + liquidity,
+    )?;
+    position.opened_at = Clock::get()?.unix_timestamp;
+    position.bump = ctx.bumps.position;
+    position.fee_growth_last_a = pool.fee_growth_global_a;
+    position.fee_growth_last_b = pool.fee_growth_global_b;
+    position.fees_collected_a = 0;
+    position.fees_collected_b = 0;
+
+    // Positions share the separated fees of the pool pro rata to their liquidity
+    let pool = &mut ctx.accounts.liquidity_pool;
+    pool.position_liquidity = pool
+        .position_liquidity
+        .checked_add(liquidity)
+        .ok_or(DEXError::MathOverflow)?;
+
+    emit!(LpPositionOpenedEvent {
+        pool: pool.key(),
+        position_mint: position.position_mint,
+        owner: ctx.accounts.owner.key(),
+        liquidity,
+        amount_a: a_amount,
+        amount_b: b_amount,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenLpPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        seeds = [LIQUIDITY_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = liquidity_pool.bump,
+        has_one = vault_a,
+        has_one = vault_b,
+        has_one = lp_mint
+    )]
+    pub liquidity_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        seeds = [POSITION_LP_VAULT_SEED, liquidity_pool.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = liquidity_pool,
+        token::token_program = token_program,
+    )]
+    pub position_lp_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        mint::decimals = 0,
+        mint::authority = liquidity_pool,
+        mint::token_program = token_2022_program,
+    )]
+    pub position_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = position_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_2022_program,
+    )]
+    pub owner_position_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = LpPosition::MAX_SIZE,
+        seeds = [
+            LP_POSITION_SEED,
+            liquidity_pool.key().as_ref(),
+            position_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub position: Box<Account<'info, LpPosition>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_a_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_b_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_2022_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
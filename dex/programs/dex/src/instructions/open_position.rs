@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::POSITION_SEED;
+use crate::curves::concentrated::{MAX_TICK, MIN_TICK};
+use crate::errors::DEXError;
+use crate::state::{ConcentratedPool, Position};
+
+pub fn open_position(ctx: Context<OpenPosition>, tick_lower: i32, tick_upper: i32) -> Result<()> {
+    let tick_spacing = ctx.accounts.concentrated_pool.tick_spacing as i32;
+
+    require!(
+        tick_lower < tick_upper
+            && tick_lower >= MIN_TICK
+            && tick_upper <= MAX_TICK
+            && tick_lower % tick_spacing == 0
+            && tick_upper % tick_spacing == 0,
+        DEXError::InvalidTickIndex
+    );
+
+    let position = &mut ctx.accounts.position;
+
+    position.pool = ctx.accounts.concentrated_pool.key();
+    position.owner = ctx.accounts.owner.key();
+    position.tick_lower = tick_lower;
+    position.tick_upper = tick_upper;
+    position.liquidity = 0;
+    position.fee_growth_inside_last_a = 0;
+    position.fee_growth_inside_last_b = 0;
+    position.tokens_owed_a = 0;
+    position.tokens_owed_b = 0;
+    position.bump = ctx.bumps.position;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(tick_lower: i32, tick_upper: i32)]
+pub struct OpenPosition<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub concentrated_pool: Account<'info, ConcentratedPool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = Position::MAX_SIZE,
+        seeds = [
+            POSITION_SEED,
+            concentrated_pool.key().as_ref(),
+            owner.key().as_ref(),
+            tick_lower.to_le_bytes().as_ref(),
+            tick_upper.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+
+    pub system_program: Program<'info, System>,
+}
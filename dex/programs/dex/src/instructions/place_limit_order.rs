@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::ORDER_BOOK_SEED;
+use crate::errors::DEXError;
+use crate::state::{OrderBook, Pool};
+
+/// Escrows `amount_in` of `mint_from` to be sold for at least `limit_price` (Q64.64 units
+/// of `mint_to` per unit of `mint_from`) once the pool price allows it
+pub fn place_limit_order(
+    ctx: Context<PlaceLimitOrder>,
+    amount_in: u64,
+    limit_price: u128,
+) -> Result<()> {
+    require!(
+        amount_in > 0 && limit_price > 0,
+        DEXError::InvalidLimitOrder
+    );
+
+    let pool = &ctx.accounts.liquidity_pool;
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
+
+    ctx.accounts.order_book.load_mut()?.insert(
+        ctx.accounts.owner.key(),
+        a_to_b,
+        amount_in,
+        limit_price,
+    )?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_token_account_from.to_account_info(),
+                to: ctx.accounts.escrow_from.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [ORDER_BOOK_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub order_book: AccountLoader<'info, OrderBook>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_from.key() != mint_to.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::authority = owner,
+        associated_token::mint = mint_from,
+    )]
+    pub owner_token_account_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::authority = order_book,
+        associated_token::mint = mint_from,
+    )]
+    pub escrow_from: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::MARKET_SEED;
+use crate::errors::DEXError;
+use crate::state::{Market, Pool};
+
+/// Rests `amount_in` on the pool market at `price` (Q64.64 token B per token A): token B
+/// buying token A for a bid, token A for sale for an ask. The input is deposited in the
+/// pool vault and set apart from the curve reserves.
+pub fn place_market_order(
+    ctx: Context<PlaceMarketOrder>,
+    bid: bool,
+    price: u128,
+    amount_in: u64,
+) -> Result<()> {
+    require!(amount_in > 0 && price > 0, DEXError::InvalidMarketOrder);
+
+    ctx.accounts
+        .market
+        .load_mut()?
+        .insert(ctx.accounts.owner.key(), bid, price, amount_in)?;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+    let (from, to, book) = if bid {
+        (
+            &ctx.accounts.owner_token_account_b,
+            &ctx.accounts.vault_b,
+            &mut pool.book_b,
+        )
+    } else {
+        (
+            &ctx.accounts.owner_token_account_a,
+            &ctx.accounts.vault_a,
+            &mut pool.book_a,
+        )
+    };
+
+    *book = book.checked_add(amount_in).ok_or(DEXError::MathOverflow)?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct PlaceMarketOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
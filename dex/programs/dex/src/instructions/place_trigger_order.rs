@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TRIGGER_ORDER_SEED;
+use crate::errors::DEXError;
+use crate::state::{Pool, TriggerOrder, TriggerPriceSource};
+
+/// Escrows `amount_in` of `mint_in` to be swapped into `mint_out` by a keeper once the pool
+/// price (token B per token A, Q64.64) reaches `trigger_price` from below when `trigger_above`
+/// is set, or from above otherwise
+pub fn place_trigger_order(
+    ctx: Context<PlaceTriggerOrder>,
+    id: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    trigger_price: u128,
+    trigger_above: bool,
+    price_source: TriggerPriceSource,
+) -> Result<()> {
+    require!(
+        amount_in > 0 && trigger_price > 0,
+        DEXError::InvalidTriggerOrder
+    );
+
+    let pool = &ctx.accounts.liquidity_pool;
+    let mint_in = ctx.accounts.mint_in.key();
+    let mint_out = ctx.accounts.mint_out.key();
+
+    require!(
+        (mint_in == pool.mint_a && mint_out == pool.mint_b)
+            || (mint_in == pool.mint_b && mint_out == pool.mint_a),
+        DEXError::MintNotInPool
+    );
+
+    let order = &mut ctx.accounts.trigger_order;
+    order.owner = ctx.accounts.owner.key();
+    order.pool = pool.key();
+    order.mint_in = mint_in;
+    order.mint_out = mint_out;
+    order.escrow = ctx.accounts.escrow.key();
+    order.id = id;
+    order.amount_in = amount_in;
+    order.min_amount_out = min_amount_out;
+    order.trigger_price = trigger_price;
+    order.trigger_above = trigger_above;
+    order.price_source = price_source;
+    order.bump = ctx.bumps.trigger_order;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_token_account_in.to_account_info(),
+                to: ctx.accounts.escrow.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct PlaceTriggerOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub mint_in: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_in.key() != mint_out.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_out: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_in,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = TriggerOrder::MAX_SIZE,
+        seeds = [
+            TRIGGER_ORDER_SEED,
+            liquidity_pool.key().as_ref(),
+            owner.key().as_ref(),
+            &id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub trigger_order: Account<'info, TriggerOrder>,
+
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_in,
+        associated_token::authority = trigger_order
+    )]
+    pub escrow: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
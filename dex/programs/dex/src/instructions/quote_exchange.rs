@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::errors::DEXError;
+use crate::instructions::exchange_tokens::{quote_swap, trader_fee_discount_bps, SwapQuote};
+use crate::state::{GlobalConfig, Pool, TraderStats, Twamm};
+use crate::utils::run_twamm;
+
+/// Read-only quote of `exchange_tokens`, including the fee the swap would be charged right now.
+/// Pools with a TWAMM require it, its long-term orders are executed first like in the swap.
+pub fn quote_exchange(ctx: Context<QuoteExchange>, amount_to_exchange: u64) -> Result<SwapQuote> {
+    // Priced on a copy, the pool account itself is left untouched
+    let mut pool = (*ctx.accounts.liquidity_pool).clone();
+
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
+
+    let decimals = (
+        ctx.accounts.mint_from.decimals,
+        ctx.accounts.mint_to.decimals,
+    );
+
+    let clock = Clock::get()?;
+    let (now, slot) = (clock.unix_timestamp, clock.slot);
+
+    // Without stats the quote gets no discount, like the swap would
+    let fee_discount_bps = trader_fee_discount_bps(
+        ctx.accounts.trader_stats.as_deref(),
+        ctx.accounts.global_config.as_deref(),
+        now,
+    );
+
+    let mut reserves = (
+        pool.curve_reserve(a_to_b, ctx.accounts.vault_in.amount),
+        pool.curve_reserve(!a_to_b, ctx.accounts.vault_out.amount),
+    );
+
+    if pool.twamm != Pubkey::default() {
+        let twamm = ctx.accounts.twamm.as_ref().ok_or(DEXError::InvalidTwamm)?;
+        require_keys_eq!(twamm.key(), pool.twamm, DEXError::InvalidTwamm);
+
+        // Executed on a copy too, kept on the heap as the TWAMM is too large for the stack
+        let mut twamm = Box::new(*twamm.load()?);
+
+        let (mut reserves_ab, decimals_ab) = if a_to_b {
+            (reserves, decimals)
+        } else {
+            ((reserves.1, reserves.0), (decimals.1, decimals.0))
+        };
+        run_twamm(
+            &mut pool,
+            &mut twamm,
+            &mut reserves_ab,
+            decimals_ab,
+            now,
+            slot,
+        )?;
+        pool.record_price(reserves_ab.0, reserves_ab.1, now);
+
+        reserves = if a_to_b {
+            reserves_ab
+        } else {
+            (reserves_ab.1, reserves_ab.0)
+        };
+    }
+
+    quote_swap(
+        &mut pool,
+        a_to_b,
+        amount_to_exchange,
+        reserves,
+        decimals,
+        fee_discount_bps,
+        (now, slot),
+    )
+}
+
+#[derive(Accounts)]
+pub struct QuoteExchange<'info> {
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_from.key() != mint_to.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    // Unlike `ExchangeTokens`, the vaults are named after the direction of the funds
+    #[account(
+        associated_token::mint = mint_from,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        associated_token::mint = mint_to,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// Volume of the trader the quote is for, to apply their tier discount
+    #[account(
+        constraint = trader_stats.pool == liquidity_pool.key() @ DEXError::InvalidTraderStats
+    )]
+    pub trader_stats: Option<Account<'info, TraderStats>>,
+
+    pub global_config: Option<Account<'info, GlobalConfig>>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::math::mul_div_floor;
+use crate::state::{LockedLiquidity, Pool};
+
+/// Read-only report of how much of the LP supply of the pool sits in liquidity locks
+pub fn quote_locked_liquidity(ctx: Context<QuoteLockedLiquidity>) -> Result<LockedLiquidity> {
+    let locked = ctx.accounts.liquidity_pool.locked_liquidity;
+    let lp_supply = ctx.accounts.lp_mint.supply;
+
+    let locked_bps = if lp_supply == 0 {
+        0
+    } else {
+        mul_div_floor(locked as u128, 10_000, lp_supply as u128)? as u16
+    };
+
+    Ok(LockedLiquidity {
+        locked,
+        lp_supply,
+        locked_bps,
+    })
+}
+
+#[derive(Accounts)]
+pub struct QuoteLockedLiquidity<'info> {
+    #[account(has_one = lp_mint)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+}
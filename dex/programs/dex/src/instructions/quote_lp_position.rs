@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::LP_POSITION_SEED;
+use crate::state::{LpPosition, LpPositionValue, Pool};
+
+/// Read-only report of what a position is worth right now and how much of it was earned
+/// as swap fees, separated fees included
+pub fn quote_lp_position(ctx: Context<QuoteLpPosition>) -> Result<LpPositionValue> {
+    let pool = &ctx.accounts.liquidity_pool;
+
+    ctx.accounts.position.value(
+        pool,
+        pool.curve_reserve(true, ctx.accounts.vault_a.amount),
+        pool.curve_reserve(false, ctx.accounts.vault_b.amount),
+        ctx.accounts.lp_mint.supply,
+    )
+}
+
+#[derive(Accounts)]
+pub struct QuoteLpPosition<'info> {
+    #[account(
+        has_one = vault_a,
+        has_one = vault_b,
+        has_one = lp_mint
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [
+            LP_POSITION_SEED,
+            liquidity_pool.key().as_ref(),
+            position.position_mint.as_ref()
+        ],
+        bump = position.bump
+    )]
+    pub position: Account<'info, LpPosition>,
+
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{GLOBAL_CONFIG_SEED, REFERRER_SEED};
+use crate::errors::DEXError;
+use crate::state::{GlobalConfig, Referrer};
+
+/// Lets `owner` receive the referral share of swap fees. Only the config admin registers
+/// referrers, so a trader cannot pay their own fees back to a wallet of theirs.
+pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+    let referrer = &mut ctx.accounts.referrer;
+
+    referrer.owner = ctx.accounts.owner.key();
+    referrer.bump = ctx.bumps.referrer;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegisterReferrer<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        constraint = global_config.admin == admin.key() @ DEXError::NotConfigAdmin
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Only the address is recorded, referral fees are paid to its token accounts
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = Referrer::MAX_SIZE,
+        seeds = [REFERRER_SEED, owner.key().as_ref()],
+        bump
+    )]
+    pub referrer: Account<'info, Referrer>,
+
+    pub system_program: Program<'info, System>,
+}
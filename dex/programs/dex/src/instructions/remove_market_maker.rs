@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::errors::DEXError;
+use crate::state::{GlobalConfig, MarketMaker};
+
+/// Removes a market maker from the whitelist, its quotes cannot be filled anymore
+pub fn remove_market_maker(_ctx: Context<RemoveMarketMaker>) -> Result<()> {
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveMarketMaker<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        constraint = global_config.admin == admin.key() @ DEXError::NotConfigAdmin
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        mut,
+        close = admin
+    )]
+    pub market_maker: Account<'info, MarketMaker>,
+}
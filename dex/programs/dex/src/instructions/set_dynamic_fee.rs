@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::{DynamicFee, DynamicFeeConfig, Pool};
+
+/// Enables (or reconfigures) volatility-based fees on the pool, `None` goes back to the static fee
+pub fn set_dynamic_fee(
+    ctx: Context<SetDynamicFee>,
+    config: Option<DynamicFeeConfig>,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    pool.dynamic_fee = match config {
+        Some(config) => {
+            config.validate(pool.max_base_fee_bps())?;
+
+            // Reconfiguring keeps the volatility tracked so far
+            Some(match pool.dynamic_fee {
+                Some(dynamic_fee) => DynamicFee {
+                    config,
+                    ..dynamic_fee
+                },
+                None => DynamicFee::new(config),
+            })
+        }
+        None => None,
+    };
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetDynamicFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+}
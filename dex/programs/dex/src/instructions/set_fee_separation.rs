@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::FEE_VAULT_SEED;
+use crate::errors::DEXError;
+use crate::state::{Pool, PoolCurve};
+
+/// Turns fee separation on or off. While on, the swap fees of the pool stop compounding into
+/// its reserves and accrue to the LP tokens staked in positions instead, pro rata to their
+/// liquidity, to be paid out by `collect_fees`. Fungible LP tokens have to be staked into a
+/// position to keep earning. Fees separated before turning it off stay collectable.
+pub fn set_fee_separation(ctx: Context<SetFeeSeparation>, enabled: bool) -> Result<()> {
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    require!(
+        pool.curve == PoolCurve::ConstantProduct,
+        DEXError::LpPositionUnsupported
+    );
+
+    pool.separate_fees = enabled;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeSeparation<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [FEE_VAULT_SEED, liquidity_pool.key().as_ref(), mint_a.key().as_ref()],
+        bump,
+        token::mint = mint_a,
+        token::authority = liquidity_pool,
+        token::token_program = token_program,
+    )]
+    pub fee_vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        seeds = [FEE_VAULT_SEED, liquidity_pool.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = liquidity_pool,
+        token::token_program = token_program,
+    )]
+    pub fee_vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::errors::DEXError;
+use crate::state::{FeeTier, GlobalConfig};
+
+/// Replaces the volume tier table, an empty table turns discounts off
+pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+    GlobalConfig::validate_fee_tiers(&fee_tiers)?;
+
+    ctx.accounts.global_config.fee_tiers = fee_tiers;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetFeeTiers<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        constraint = global_config.admin == admin.key() @ DEXError::NotConfigAdmin
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::{ImpactFeeConfig, Pool};
+
+/// Enables (or reconfigures) the trade-size surcharge on the pool, `None` disables it
+pub fn set_impact_fee(ctx: Context<SetImpactFee>, config: Option<ImpactFeeConfig>) -> Result<()> {
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    if let Some(config) = config {
+        config.validate(pool.max_base_fee_bps())?;
+    }
+
+    pool.impact_fee = config;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetImpactFee<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::errors::DEXError;
+use crate::state::{GlobalConfig, Pool};
+
+/// Sets the share of each swap fee, in bps of the fee, paid to the referrer of the swap
+pub fn set_referral_fee_share(
+    ctx: Context<SetReferralFeeShare>,
+    referral_fee_share_bps: u64,
+) -> Result<()> {
+    require!(
+        referral_fee_share_bps <= ctx.accounts.global_config.max_referral_fee_share_bps,
+        DEXError::ReferralShareTooHigh
+    );
+
+    ctx.accounts.liquidity_pool.referral_fee_share_bps = referral_fee_share_bps;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetReferralFeeShare<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
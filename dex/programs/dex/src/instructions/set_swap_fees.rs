@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::Pool;
+
+/// Updates the static fee of each swap direction
+pub fn set_swap_fees(
+    ctx: Context<SetSwapFees>,
+    fee_bps_a_to_b: u64,
+    fee_bps_b_to_a: u64,
+) -> Result<()> {
+    require!(fee_bps_a_to_b <= 10_000, DEXError::InvalidBPSValue);
+    require!(fee_bps_b_to_a <= 10_000, DEXError::InvalidBPSValue);
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    pool.fee_bps_a_to_b = fee_bps_a_to_b;
+    pool.fee_bps_b_to_a = fee_bps_b_to_a;
+
+    // Fee caps of the enabled fee models must stay above both base fees
+    if let Some(dynamic_fee) = pool.dynamic_fee {
+        dynamic_fee.config.validate(pool.max_base_fee_bps())?;
+    }
+    if let Some(impact_fee) = pool.impact_fee {
+        impact_fee.validate(pool.max_base_fee_bps())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSwapFees<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+}
@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::TokenAccount,
+};
+
+use crate::constants::BATCH_AUCTION_SEED;
+use crate::errors::DEXError;
+use crate::events::BatchSettledEvent;
+use crate::state::{BatchAuction, BatchClearing, Pool};
+use crate::utils::{get_batch_auction_signer_seeds, get_pool_signer_seeds};
+
+/// Permissionless crank clearing every order queued during a past slot at a single price,
+/// after which the orders queued in the meantime make up the batch to settle next.
+/// Opposing orders are matched with each other and only the imbalance trades against the
+/// constant product curve, so the order of the swaps within the slot does not matter.
+///
+/// Remaining accounts: the (input, output) token accounts of each queued order, in order.
+pub fn settle_batch<'info>(ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>) -> Result<()> {
+    let clock = Clock::get()?;
+    let batch_auction = &ctx.accounts.batch_auction;
+    let orders = batch_auction.orders.clone();
+
+    require!(!orders.is_empty(), DEXError::EmptyBatch);
+    require!(clock.slot > batch_auction.slot, DEXError::BatchSlotNotOver);
+    require!(
+        ctx.remaining_accounts.len() == 2 * orders.len(),
+        DEXError::InvalidTokenAccounts
+    );
+
+    let vault_a = &ctx.accounts.vault_a;
+    let vault_b = &ctx.accounts.vault_b;
+    let pool = &ctx.accounts.liquidity_pool;
+    let reserves = (
+        pool.curve_reserve(true, vault_a.amount),
+        pool.curve_reserve(false, vault_b.amount),
+    );
+
+    // Orders whose limit the clearing price misses are refunded, which moves the price in
+    // favour of the others, so clearing is repeated until every remaining limit holds
+    let mut filled = vec![true; orders.len()];
+    let clearing = loop {
+        let (mut total_a_in, mut total_b_in) = (0u64, 0u64);
+        for (order, _) in orders.iter().zip(&filled).filter(|(_, filled)| **filled) {
+            let total = if order.a_to_b {
+                &mut total_a_in
+            } else {
+                &mut total_b_in
+            };
+            *total = total
+                .checked_add(order.amount_in)
+                .ok_or(DEXError::MathOverflow)?;
+        }
+
+        let clearing = BatchClearing::compute(pool, (total_a_in, total_b_in), reserves)?;
+
+        let mut refunded = false;
+        for (order, filled) in orders.iter().zip(filled.iter_mut()) {
+            if *filled && clearing.amount_out(order)? < order.min_amount_out {
+                *filled = false;
+                refunded = true;
+            }
+        }
+
+        if !refunded {
+            break clearing;
+        }
+    };
+
+    let token_program = ctx.accounts.token_program.to_account_info();
+    let pool_key = ctx.accounts.liquidity_pool.key();
+
+    // The input of the batch moves to the vaults, which then pay every order out. The
+    // escrows keep the input of the orders queued for the next batch.
+    let batch_seeds = get_batch_auction_signer_seeds(&pool_key, &batch_auction.bump);
+    let (mut escrowed_a, mut escrowed_b) = (0u64, 0u64);
+    for order in &orders {
+        let escrowed = if order.a_to_b {
+            &mut escrowed_a
+        } else {
+            &mut escrowed_b
+        };
+        *escrowed = escrowed
+            .checked_add(order.amount_in)
+            .ok_or(DEXError::MathOverflow)?;
+    }
+
+    for (escrow, vault, amount) in [
+        (&ctx.accounts.escrow_a, vault_a, escrowed_a),
+        (&ctx.accounts.escrow_b, vault_b, escrowed_b),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: escrow.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: batch_auction.to_account_info(),
+                },
+                &[&batch_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    let pool_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+    for ((order, filled), accounts) in orders
+        .iter()
+        .zip(&filled)
+        .zip(ctx.remaining_accounts.chunks(2))
+    {
+        let (token_account_in, token_account_out) = (&accounts[0], &accounts[1]);
+
+        require_keys_eq!(
+            token_account_in.key(),
+            order.token_account_in,
+            DEXError::InvalidTokenAccounts
+        );
+        require_keys_eq!(
+            token_account_out.key(),
+            order.token_account_out,
+            DEXError::InvalidTokenAccounts
+        );
+
+        let (vault_in, vault_out) = if order.a_to_b {
+            (vault_a, vault_b)
+        } else {
+            (vault_b, vault_a)
+        };
+
+        let (from, to, amount) = if *filled {
+            (vault_out, token_account_out, clearing.amount_out(order)?)
+        } else {
+            (vault_in, token_account_in, order.amount_in)
+        };
+
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.clone(),
+                    authority: pool.to_account_info(),
+                },
+                &[&pool_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    ctx.accounts.vault_a.reload()?;
+    ctx.accounts.vault_b.reload()?;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    // The fee is paid in the token the pool took the imbalance of
+    pool.separate_fee(
+        clearing.amount_a_out < clearing.total_a_in,
+        clearing.fee_amount,
+    )?;
+
+    let (reserve_a, reserve_b) = (
+        pool.curve_reserve(true, ctx.accounts.vault_a.amount),
+        pool.curve_reserve(false, ctx.accounts.vault_b.amount),
+    );
+    pool.record_price(reserve_a, reserve_b, clock.unix_timestamp);
+
+    let batch_auction = &mut ctx.accounts.batch_auction;
+    let orders_filled = filled.iter().filter(|filled| **filled).count() as u8;
+
+    emit!(BatchSettledEvent {
+        pool: pool_key,
+        slot: batch_auction.slot,
+        clearing_price: clearing.price(),
+        amount_a_in: clearing.total_a_in,
+        amount_b_in: clearing.total_b_in,
+        fee_amount: clearing.fee_amount,
+        orders_filled,
+        orders_refunded: orders.len() as u8 - orders_filled,
+    });
+
+    batch_auction.advance();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SettleBatch<'info> {
+    pub keeper: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [BATCH_AUCTION_SEED, liquidity_pool.key().as_ref()],
+        bump = batch_auction.bump,
+        has_one = escrow_a @ DEXError::InvalidBatchAuction,
+        has_one = escrow_b @ DEXError::InvalidBatchAuction
+    )]
+    pub batch_auction: Account<'info, BatchAuction>,
+
+    #[account(mut)]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = liquidity_pool.vault_a @ DEXError::WrongVaultSpecified
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        address = liquidity_pool.vault_b @ DEXError::WrongVaultSpecified
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
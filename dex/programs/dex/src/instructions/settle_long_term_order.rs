@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TWAMM_SEED;
+use crate::errors::DEXError;
+use crate::state::{LongTermOrder, Pool, Twamm};
+use crate::utils::{execute_twamm, get_twamm_signer_seeds, pay_out_long_term_order};
+
+/// Pays out an expired long-term order to its owner and closes it. Anyone can do this, so
+/// orders left behind by their owners do not keep their expiry slot in the TWAMM taken.
+/// The token accounts of the owner are created at the expense of the signer if needed.
+pub fn settle_long_term_order(ctx: Context<SettleLongTermOrder>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let decimals = (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals);
+
+    execute_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        &ctx.accounts.twamm,
+        (&ctx.accounts.vault_a, &ctx.accounts.vault_b),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let ((proceeds, unsold), bump) = {
+        let mut twamm = ctx.accounts.twamm.load_mut()?;
+        let order = &ctx.accounts.long_term_order;
+        require!(twamm.is_expired(order), DEXError::LongTermOrderActive);
+
+        (twamm.remove_order(order)?, twamm.bump)
+    };
+
+    let pool_key = ctx.accounts.liquidity_pool.key();
+    let signer_seeds = get_twamm_signer_seeds(&pool_key, &bump);
+
+    pay_out_long_term_order(
+        (&ctx.accounts.twamm.to_account_info(), &signer_seeds),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        (
+            &ctx.accounts.owner_token_account_a,
+            &ctx.accounts.owner_token_account_b,
+        ),
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.long_term_order.a_to_b,
+        (proceeds, unsold),
+    )?;
+
+    ctx.accounts
+        .long_term_order
+        .close(ctx.accounts.owner.to_account_info())
+}
+
+#[derive(Accounts)]
+pub struct SettleLongTermOrder<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    /// CHECK: Only receives the payout and the rent of the order, pinned by the order
+    #[account(mut)]
+    pub owner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub twamm: AccountLoader<'info, Twamm>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = twamm
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = twamm
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = signer,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        has_one = owner @ DEXError::OrderNotFound,
+        has_one = twamm @ DEXError::OrderNotFound,
+    )]
+    pub long_term_order: Account<'info, LongTermOrder>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::TokenAccount,
+};
+
+use crate::constants::{FARM_SEED, FARM_STAKE_SEED};
+use crate::errors::DEXError;
+use crate::events::LpStakedEvent;
+use crate::state::{Farm, FarmStake};
+
+/// Stakes `amount` LP tokens in the farm, crediting the rewards of the existing stake first
+pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+    require!(amount > 0, DEXError::ZeroStakeAmount);
+
+    let farm = &mut ctx.accounts.farm;
+    farm.update(Clock::get()?.unix_timestamp)?;
+
+    let stake = &mut ctx.accounts.farm_stake;
+    if stake.owner == Pubkey::default() {
+        stake.farm = farm.key();
+        stake.owner = ctx.accounts.owner.key();
+        stake.bump = ctx.bumps.farm_stake;
+    }
+
+    stake.update_rewards(&farm.rewards)?;
+    stake.amount = stake
+        .amount
+        .checked_add(amount)
+        .ok_or(DEXError::MathOverflow)?;
+    farm.total_staked = farm
+        .total_staked
+        .checked_add(amount)
+        .ok_or(DEXError::MathOverflow)?;
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_lp_account.to_account_info(),
+                to: ctx.accounts.lp_vault.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    emit!(LpStakedEvent {
+        farm: farm.key(),
+        owner: stake.owner,
+        amount,
+        total_staked: farm.total_staked,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct StakeLp<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.pool.as_ref()],
+        bump = farm.bump,
+        has_one = lp_vault @ DEXError::InvalidFarm
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = FarmStake::MAX_SIZE,
+        seeds = [FARM_STAKE_SEED, farm.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub farm_stake: Account<'info, FarmStake>,
+
+    #[account(
+        mut,
+        associated_token::mint = farm.lp_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TWAMM_SEED;
+use crate::errors::DEXError;
+use crate::state::{LongTermOrder, Pool, Twamm};
+use crate::utils::execute_twamm;
+
+/// Sells `amount_in` of token A (or B) into the pool evenly over the next `duration` seconds,
+/// rounded up to the order interval of the TWAMM
+pub fn submit_long_term_order(
+    ctx: Context<SubmitLongTermOrder>,
+    sell_token_a: bool,
+    amount_in: u64,
+    duration: i64,
+) -> Result<()> {
+    require!(
+        amount_in > 0 && duration > 0,
+        DEXError::InvalidLongTermOrder
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    let decimals = (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals);
+
+    execute_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        &ctx.accounts.twamm,
+        (&ctx.accounts.vault_a, &ctx.accounts.vault_b),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let (sell_rate, end_ts, earnings_snapshot) = {
+        let mut twamm = ctx.accounts.twamm.load_mut()?;
+
+        let end_ts = twamm.order_end_ts(now, duration)?;
+        let sell_rate = ((amount_in as u128) << 64) / (end_ts - now) as u128;
+        twamm.add_order(sell_token_a, sell_rate, end_ts)?;
+
+        (
+            sell_rate,
+            end_ts,
+            twamm.virtual_pool(sell_token_a).earnings_per_rate,
+        )
+    };
+
+    let order = &mut ctx.accounts.long_term_order;
+    order.twamm = ctx.accounts.twamm.key();
+    order.owner = ctx.accounts.owner.key();
+    order.a_to_b = sell_token_a;
+    order.amount_in = amount_in;
+    order.sell_rate = sell_rate;
+    order.start_ts = now;
+    order.end_ts = end_ts;
+    order.earnings_snapshot = earnings_snapshot;
+
+    let (from, to) = if sell_token_a {
+        (&ctx.accounts.owner_token_account_a, &ctx.accounts.escrow_a)
+    } else {
+        (&ctx.accounts.owner_token_account_b, &ctx.accounts.escrow_b)
+    };
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: from.to_account_info(),
+                to: to.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        amount_in,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SubmitLongTermOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub twamm: AccountLoader<'info, Twamm>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = twamm
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = twamm
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = LongTermOrder::MAX_SIZE,
+    )]
+    pub long_term_order: Account<'info, LongTermOrder>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+}
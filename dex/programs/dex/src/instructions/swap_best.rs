@@ -0,0 +1,327 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::{MARKET_SEED, MAX_MARKET_FILLS_PER_SWAP};
+use crate::curves::constant_product;
+use crate::errors::DEXError;
+use crate::events::{MarketOrderFilledEvent, SwapEvent};
+use crate::instructions::exchange_tokens::quote_swap;
+use crate::math::mul_div_floor;
+use crate::state::{Market, Pool, Twamm};
+use crate::utils::{get_pool_signer_seeds, settle_twamm};
+
+/// Swaps `amount_to_exchange` through whichever of the pool market and the curve pays more,
+/// level by level: the curve takes the input until its marginal price falls to the best
+/// book order, which is then filled, and so on. Selling token A hits bids, selling token B
+/// lifts asks.
+pub fn swap_best(
+    ctx: Context<SwapBest>,
+    amount_to_exchange: u64,
+    min_receive_amount: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let pool = &mut ctx.accounts.liquidity_pool;
+
+    require!(!pool.batch_auction, DEXError::BatchAuctionPool);
+
+    let a_to_b = ctx.accounts.mint_from.key() == pool.mint_a;
+    let (expected_from, expected_to) = if a_to_b {
+        (pool.mint_a, pool.mint_b)
+    } else {
+        (pool.mint_b, pool.mint_a)
+    };
+
+    require_keys_eq!(
+        ctx.accounts.mint_from.key(),
+        expected_from,
+        DEXError::MintNotInPool
+    );
+    require_keys_eq!(
+        ctx.accounts.mint_to.key(),
+        expected_to,
+        DEXError::MintNotInPool
+    );
+
+    // Long-term orders are executed up to now before the swap is priced
+    let (vaults, decimals) = if a_to_b {
+        (
+            (&mut ctx.accounts.vault_in, &mut ctx.accounts.vault_out),
+            (
+                ctx.accounts.mint_from.decimals,
+                ctx.accounts.mint_to.decimals,
+            ),
+        )
+    } else {
+        (
+            (&mut ctx.accounts.vault_out, &mut ctx.accounts.vault_in),
+            (
+                ctx.accounts.mint_to.decimals,
+                ctx.accounts.mint_from.decimals,
+            ),
+        )
+    };
+    settle_twamm(
+        pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        vaults,
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let decimals = (
+        ctx.accounts.mint_from.decimals,
+        ctx.accounts.mint_to.decimals,
+    );
+    let fee_bps = pool.base_fee_bps(a_to_b);
+
+    let (mut reserve_in, mut reserve_out) = (
+        pool.curve_reserve(a_to_b, ctx.accounts.vault_in.amount),
+        pool.curve_reserve(!a_to_b, ctx.accounts.vault_out.amount),
+    );
+
+    let mut market = ctx.accounts.market.load_mut()?;
+    let bid = a_to_b;
+
+    let mut remaining = amount_to_exchange;
+    let mut amount_out = 0u64;
+    // (input, output, fee) of the curve, (input, output) of the book
+    let mut curve_totals = (0u64, 0u64, 0u64);
+    let mut book_totals = (0u64, 0u64);
+    let mut fills = 0;
+    // Set once the curve got down to the best order, which then goes first whatever the
+    // rounding left of the price difference
+    let mut at_level = false;
+
+    while remaining > 0 {
+        let level = if fills < MAX_MARKET_FILLS_PER_SWAP {
+            market.best_index(bid)?
+        } else {
+            None
+        };
+
+        // Both prices are output per unit of input
+        let curve_amount = match level {
+            Some(_) if at_level => 0,
+            Some(index) => {
+                let price = market.orders(bid)[index].price;
+                let level_price = if bid {
+                    price
+                } else {
+                    mul_div_floor(1 << 64, 1 << 64, price)?
+                };
+                let curve_price = mul_div_floor(
+                    Pool::spot_price(reserve_in, reserve_out),
+                    (10_000 - fee_bps) as u128,
+                    10_000,
+                )?;
+
+                if level_price >= curve_price {
+                    0
+                } else {
+                    constant_product::amount_in_to_price(
+                        reserve_in as u128,
+                        reserve_out as u128,
+                        fee_bps,
+                        level_price,
+                    )
+                    .map_or(remaining, |amount| amount.min(remaining as u128) as u64)
+                }
+            }
+            None => remaining,
+        };
+
+        if curve_amount > 0 {
+            let quote = quote_swap(
+                pool,
+                a_to_b,
+                curve_amount,
+                (reserve_in, reserve_out),
+                decimals,
+                0,
+                (now, Clock::get()?.slot),
+            )?;
+
+            let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount)?;
+            reserve_in = reserve_in
+                .checked_add(curve_amount - separated_fee)
+                .ok_or(DEXError::MathOverflow)?;
+            reserve_out -= quote.amount_out;
+
+            curve_totals.0 += curve_amount;
+            curve_totals.1 += quote.amount_out;
+            curve_totals.2 += quote.fee_amount;
+            amount_out += quote.amount_out;
+            remaining -= curve_amount;
+            at_level = true;
+
+            continue;
+        }
+
+        let Some(index) = level else {
+            break;
+        };
+
+        let order = &mut market.orders_mut(bid)[index];
+        let (filled_in, filled_out) = order.fill(bid, remaining)?;
+
+        // Too little is left to buy anything off the book, the curve takes the rest
+        if filled_out == 0 {
+            fills = MAX_MARKET_FILLS_PER_SWAP;
+            at_level = false;
+            continue;
+        }
+
+        emit!(MarketOrderFilledEvent {
+            pool: pool.key(),
+            order_id: order.id,
+            owner: order.owner,
+            bid,
+            amount_in: filled_in,
+            amount_out: filled_out,
+        });
+
+        book_totals.0 += filled_in;
+        book_totals.1 += filled_out;
+        amount_out += filled_out;
+        remaining -= filled_in;
+        fills += 1;
+        at_level = false;
+    }
+
+    drop(market);
+
+    require!(amount_out >= min_receive_amount, DEXError::SlippageExceeded);
+
+    // Taker input filled by makers is owed to them, the output they paid leaves the book
+    let pool_state: &mut Pool = pool;
+    let (book_in, book_out) = if a_to_b {
+        (&mut pool_state.book_a, &mut pool_state.book_b)
+    } else {
+        (&mut pool_state.book_b, &mut pool_state.book_a)
+    };
+    *book_in = book_in
+        .checked_add(book_totals.0)
+        .ok_or(DEXError::MathOverflow)?;
+    *book_out -= book_totals.1;
+
+    let token_program = &ctx.accounts.token_program;
+
+    transfer(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.trader_token_account_from.to_account_info(),
+                to: ctx.accounts.vault_in.to_account_info(),
+                authority: ctx.accounts.trader.to_account_info(),
+            },
+        ),
+        amount_to_exchange,
+    )?;
+
+    let signer_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault_out.to_account_info(),
+                to: ctx.accounts.trader_token_account_to.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        amount_out,
+    )?;
+
+    if curve_totals.0 > 0 {
+        if a_to_b {
+            pool.record_price(reserve_in, reserve_out, now);
+        } else {
+            pool.record_price(reserve_out, reserve_in, now);
+        }
+
+        emit!(SwapEvent {
+            pool: pool.key(),
+            trader: ctx.accounts.trader.key(),
+            mint_in: ctx.accounts.mint_from.key(),
+            mint_out: ctx.accounts.mint_to.key(),
+            amount_in: curve_totals.0,
+            amount_out: curve_totals.1,
+            fee_amount: curve_totals.2,
+            fee_bps,
+        });
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SwapBest<'info> {
+    pub trader: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [MARKET_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub market: AccountLoader<'info, Market>,
+
+    pub mint_from: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_from.key() != mint_to.key() @ DEXError::SameTokensExchanged
+    )]
+    pub mint_to: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_from,
+        associated_token::authority = trader
+    )]
+    pub trader_token_account_from: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_to,
+        associated_token::authority = trader
+    )]
+    pub trader_token_account_to: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_from,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_in: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_to,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_out: InterfaceAccount<'info, TokenAccount>,
+
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
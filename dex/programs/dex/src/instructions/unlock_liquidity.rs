@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{close_account, transfer, CloseAccount, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::LOCK_VAULT_SEED;
+use crate::errors::DEXError;
+use crate::events::LiquidityUnlockedEvent;
+use crate::state::{LiquidityLock, Pool};
+use crate::utils::get_pool_signer_seeds;
+
+/// Pays the owner of a liquidity lock the LP tokens unlocked since the last withdrawal. The
+/// lock and its vault are closed once everything is withdrawn.
+pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let lock = &mut ctx.accounts.liquidity_lock;
+
+    let amount = lock.withdrawable(now)?;
+    require!(amount > 0, DEXError::NothingToUnlock);
+
+    lock.withdrawn += amount;
+
+    let pool = &mut ctx.accounts.liquidity_pool;
+    pool.locked_liquidity -= amount;
+
+    let signer_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lock_vault.to_account_info(),
+                to: ctx.accounts.owner_lp_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        amount,
+    )?;
+
+    let remaining = lock.lp_amount - lock.withdrawn;
+
+    emit!(LiquidityUnlockedEvent {
+        pool: pool.key(),
+        liquidity_lock: lock.key(),
+        owner: lock.owner,
+        lp_amount: amount,
+        remaining,
+    });
+
+    if remaining == 0 {
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.lock_vault.to_account_info(),
+                destination: ctx.accounts.owner.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ))?;
+
+        ctx.accounts
+            .liquidity_lock
+            .close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnlockLiquidity<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        address = liquidity_lock.pool,
+        has_one = lp_mint
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        has_one = owner
+    )]
+    pub liquidity_lock: Account<'info, LiquidityLock>,
+
+    #[account(
+        mut,
+        seeds = [LOCK_VAULT_SEED, liquidity_lock.key().as_ref()],
+        bump
+    )]
+    pub lock_vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = lp_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
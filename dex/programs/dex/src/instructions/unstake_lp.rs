@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::{transfer, Token, Transfer},
+    token_interface::TokenAccount,
+};
+
+use crate::constants::{FARM_SEED, FARM_STAKE_SEED};
+use crate::errors::DEXError;
+use crate::events::LpUnstakedEvent;
+use crate::state::{Farm, FarmStake};
+use crate::utils::get_farm_signer_seeds;
+
+/// Returns `amount` staked LP tokens to their owner. The rewards earned so far stay owed
+/// to the stake until they are claimed.
+pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<()> {
+    require!(amount > 0, DEXError::ZeroStakeAmount);
+
+    let farm = &mut ctx.accounts.farm;
+    farm.update(Clock::get()?.unix_timestamp)?;
+
+    let stake = &mut ctx.accounts.farm_stake;
+    require!(stake.amount >= amount, DEXError::InsufficientLPTokens);
+
+    stake.update_rewards(&farm.rewards)?;
+    stake.amount -= amount;
+    farm.total_staked -= amount;
+
+    let signer_seeds = get_farm_signer_seeds(&farm.pool, &farm.bump);
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.lp_vault.to_account_info(),
+                to: ctx.accounts.owner_lp_account.to_account_info(),
+                authority: farm.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        amount,
+    )?;
+
+    emit!(LpUnstakedEvent {
+        farm: farm.key(),
+        owner: stake.owner,
+        amount,
+        total_staked: farm.total_staked,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UnstakeLp<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [FARM_SEED, farm.pool.as_ref()],
+        bump = farm.bump,
+        has_one = lp_vault @ DEXError::InvalidFarm
+    )]
+    pub farm: Account<'info, Farm>,
+
+    #[account(
+        mut,
+        seeds = [FARM_STAKE_SEED, farm.key().as_ref(), owner.key().as_ref()],
+        bump = farm_stake.bump
+    )]
+    pub farm_stake: Account<'info, FarmStake>,
+
+    #[account(
+        mut,
+        associated_token::mint = farm.lp_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_lp_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
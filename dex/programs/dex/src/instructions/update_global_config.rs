@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::GLOBAL_CONFIG_SEED;
+use crate::errors::DEXError;
+use crate::state::GlobalConfig;
+
+/// Lowering the cap takes effect on the next swap of every pool, whatever share they configured
+pub fn update_global_config(
+    ctx: Context<UpdateGlobalConfig>,
+    max_referral_fee_share_bps: u64,
+) -> Result<()> {
+    require!(
+        max_referral_fee_share_bps <= 10_000,
+        DEXError::InvalidBPSValue
+    );
+
+    ctx.accounts.global_config.max_referral_fee_share_bps = max_referral_fee_share_bps;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateGlobalConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_CONFIG_SEED],
+        bump = global_config.bump,
+        constraint = global_config.admin == admin.key() @ DEXError::NotConfigAdmin
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+}
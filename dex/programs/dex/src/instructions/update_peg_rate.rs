@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::state::{Pool, PoolCurve};
+
+pub fn update_peg_rate(ctx: Context<UpdatePegRate>, new_rate: u64) -> Result<()> {
+    require!(new_rate > 0, DEXError::InvalidCurveParameters);
+
+    let PoolCurve::Pegged { ref mut rate, .. } = ctx.accounts.liquidity_pool.curve else {
+        return err!(DEXError::NotPeggedPool);
+    };
+
+    *rate = new_rate;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePegRate<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = liquidity_pool.admin == admin.key() @ DEXError::Unauthorized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+}
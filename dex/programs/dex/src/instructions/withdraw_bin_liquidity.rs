@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::{
+    BIN_POOL_SEED, MINIMUM_LIQUIDITY_WITHDRAWAL, MIN_BIN_ARRAYS_PER_WITHDRAWAL,
+};
+use crate::errors::DEXError;
+use crate::state::{BinArray, BinPool, BinWithdrawal};
+use crate::utils::get_bin_pool_signer_seeds;
+
+/// Burns LP tokens for the same share of every bin. The share is paid out of the bin
+/// arrays passed as remaining accounts, the arrays left out of the ones holding liquidity
+/// (see `BinPool::bin_array_bitmap`) are paid later through `continue_bin_withdrawal`.
+/// At least `MIN_BIN_ARRAYS_PER_WITHDRAWAL` arrays are paid right away, and anyone can pay
+/// the rest, so a withdrawal left unfinished does not hold deposits back.
+pub fn withdraw_bin_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawBinLiquidity<'info>>,
+    lp_tokens_amount: u64,
+) -> Result<()> {
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+    require!(total_lp_supply > 0, DEXError::EmptyPool);
+    require!(
+        lp_tokens_amount >= MINIMUM_LIQUIDITY_WITHDRAWAL,
+        DEXError::WithdrawalTooSmall
+    );
+
+    let pool = &mut ctx.accounts.bin_pool;
+    require!(!pool.withdrawal_pending(), DEXError::BinWithdrawalPending);
+
+    let pool_key = pool.key();
+
+    pool.pending_withdrawal = BinWithdrawal {
+        owner: ctx.accounts.signer.key(),
+        lp_amount: lp_tokens_amount,
+        lp_supply: total_lp_supply,
+        remaining_bitmap: pool.bin_array_bitmap,
+    };
+
+    // `withdraw_from` rejects arrays that are not pending or passed twice
+    let required_arrays = pool
+        .bin_array_bitmap
+        .count_ones()
+        .min(MIN_BIN_ARRAYS_PER_WITHDRAWAL);
+    require!(
+        ctx.remaining_accounts.len() >= required_arrays as usize,
+        DEXError::BinWithdrawalTooFewArrays
+    );
+
+    let mut amount_a = 0u64;
+    let mut amount_b = 0u64;
+
+    for account_info in ctx.remaining_accounts.iter() {
+        let loader = AccountLoader::<BinArray>::try_from(account_info)?;
+        let mut bin_array = loader.load_mut()?;
+        require_keys_eq!(bin_array.pool, pool_key, DEXError::InvalidBinArray);
+
+        let (share_a, share_b) = pool.withdraw_from(&mut bin_array)?;
+        amount_a += share_a;
+        amount_b += share_b;
+    }
+
+    let signer_seeds = get_bin_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_tokens_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        ),
+        lp_tokens_amount,
+    )?;
+
+    if amount_a > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_a.to_account_info(),
+                    to: ctx.accounts.user_token_a_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount_a,
+        )?;
+    }
+
+    if amount_b > 0 {
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault_b.to_account_info(),
+                    to: ctx.accounts.user_token_b_account.to_account_info(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount_b,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawBinLiquidity<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        constraint = mint_a.key() < mint_b.key() @ DEXError::InvalidMintOrdering
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [BIN_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+        has_one = vault_a,
+        has_one = vault_b,
+        has_one = lp_mint
+    )]
+    pub bin_pool: Account<'info, BinPool>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bin_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer
+    )]
+    pub user_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = signer
+    )]
+    pub user_token_a_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = signer
+    )]
+    pub user_token_b_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
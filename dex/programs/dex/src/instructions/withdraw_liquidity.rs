@@ -4,8 +4,8 @@ use anchor_spl::token_interface::{Mint, TokenAccount};
 
 use crate::constants::{LIQUIDITY_POOL_SEED, MINIMUM_LIQUIDITY_WITHDRAWAL};
 use crate::errors::DEXError;
-use crate::state::Pool;
-use crate::utils::{calculate_withdrawal_amounts, get_pool_signer_seeds};
+use crate::state::{Pool, Twamm};
+use crate::utils::{calculate_withdrawal_amounts, get_pool_signer_seeds, settle_twamm};
 
 pub fn withdraw_liquidity_from_pool(
     ctx: Context<WithdrawLiquidityFromPool>,
@@ -16,9 +16,24 @@ pub fn withdraw_liquidity_from_pool(
         DEXError::WithdrawalTooSmall
     );
 
+    // Long-term orders are executed up to now before the withdrawal is priced
+    settle_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        ctx.accounts.twamm.as_ref(),
+        (
+            ctx.accounts.twamm_escrow_a.as_ref(),
+            ctx.accounts.twamm_escrow_b.as_ref(),
+        ),
+        (&mut ctx.accounts.vault_a, &mut ctx.accounts.vault_b),
+        &ctx.accounts.token_program.to_account_info(),
+        (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals),
+        Clock::get()?.unix_timestamp,
+    )?;
+
     let total_lp_supply = ctx.accounts.lp_mint.supply;
-    let vault_a_amount = ctx.accounts.vault_a.amount;
-    let vault_b_amount = ctx.accounts.vault_b.amount;
+    let pool = &ctx.accounts.liquidity_pool;
+    let vault_a_amount = pool.curve_reserve(true, ctx.accounts.vault_a.amount);
+    let vault_b_amount = pool.curve_reserve(false, ctx.accounts.vault_b.amount);
 
     require!(total_lp_supply > 0, DEXError::EmptyPool);
 
@@ -90,6 +105,7 @@ pub struct WithdrawLiquidityFromPool<'info> {
     pub mint_b: InterfaceAccount<'info, Mint>,
 
     #[account(
+        mut,
         seeds = [LIQUIDITY_POOL_SEED, mint_a.key().as_ref(), mint_b.key().as_ref()],
         bump,
         has_one = vault_a,
@@ -140,5 +156,16 @@ pub struct WithdrawLiquidityFromPool<'info> {
     )]
     pub user_token_b_account: InterfaceAccount<'info, TokenAccount>,
 
+    /// Long-term orders of the pool, executed first once the pool has a TWAMM, along with
+    /// their escrows
+    #[account(mut)]
+    pub twamm: Option<AccountLoader<'info, Twamm>>,
+
+    #[account(mut)]
+    pub twamm_escrow_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub twamm_escrow_b: Option<InterfaceAccount<'info, TokenAccount>>,
+
     pub token_program: Program<'info, Token>,
 }
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::MINIMUM_LIQUIDITY_WITHDRAWAL;
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::MultiPool;
+use crate::utils::{get_multi_pool_signer_seeds, multi_pool_token_accounts};
+
+/// Proportional withdrawal of every pool token, the (user token account, vault) pairs
+/// are passed as remaining accounts in pool order
+pub fn withdraw_multi_liquidity<'info>(
+    ctx: Context<'_, '_, 'info, 'info, WithdrawMultiLiquidity<'info>>,
+    lp_tokens_amount: u64,
+) -> Result<()> {
+    require!(
+        lp_tokens_amount >= MINIMUM_LIQUIDITY_WITHDRAWAL,
+        DEXError::WithdrawalTooSmall
+    );
+
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+    require!(total_lp_supply > 0, DEXError::EmptyPool);
+
+    let pool = &mut ctx.accounts.multi_pool;
+
+    let token_accounts =
+        multi_pool_token_accounts(pool, &ctx.accounts.signer.key(), ctx.remaining_accounts)?;
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_tokens_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        ),
+        lp_tokens_amount,
+    )?;
+
+    let (lp_mint_key, bump) = (pool.lp_mint, pool.bump);
+    let signer_seeds = get_multi_pool_signer_seeds(&lp_mint_key, &bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    for (index, (user_token_account, vault)) in token_accounts.into_iter().enumerate() {
+        let amount = mul_div_floor(
+            pool.tokens[index].balance as u128,
+            lp_tokens_amount as u128,
+            total_lp_supply as u128,
+        )? as u64;
+
+        if amount == 0 {
+            continue;
+        }
+
+        pool.tokens[index].balance -= amount;
+
+        transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: vault.clone(),
+                    to: user_token_account.clone(),
+                    authority: pool.to_account_info(),
+                },
+                signer_seeds_slice,
+            ),
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawMultiLiquidity<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = lp_mint
+    )]
+    pub multi_pool: Account<'info, MultiPool>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer
+    )]
+    pub user_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+
+use crate::constants::TWAMM_SEED;
+use crate::errors::DEXError;
+use crate::state::{LongTermOrder, Pool, Twamm};
+use crate::utils::{execute_twamm, get_twamm_signer_seeds, pay_out_long_term_order};
+
+/// Pays out the proceeds of a long-term order so far. Once the order has expired this
+/// also returns its leftover input and closes it.
+pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let decimals = (ctx.accounts.mint_a.decimals, ctx.accounts.mint_b.decimals);
+
+    execute_twamm(
+        &mut ctx.accounts.liquidity_pool,
+        &ctx.accounts.twamm,
+        (&ctx.accounts.vault_a, &ctx.accounts.vault_b),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        &ctx.accounts.token_program.to_account_info(),
+        decimals,
+        now,
+    )?;
+
+    let (proceeds, unsold, expired, bump) = {
+        let mut twamm = ctx.accounts.twamm.load_mut()?;
+        let order = &mut ctx.accounts.long_term_order;
+
+        if twamm.is_expired(order) {
+            let (proceeds, unsold) = twamm.remove_order(order)?;
+            (proceeds, unsold, true, twamm.bump)
+        } else {
+            let earnings = twamm.order_earnings(order)?;
+            let proceeds = order.proceeds(earnings)?;
+            order.earnings_snapshot = earnings;
+            (proceeds, 0, false, twamm.bump)
+        }
+    };
+
+    let pool_key = ctx.accounts.liquidity_pool.key();
+    let signer_seeds = get_twamm_signer_seeds(&pool_key, &bump);
+
+    pay_out_long_term_order(
+        (&ctx.accounts.twamm.to_account_info(), &signer_seeds),
+        (&ctx.accounts.escrow_a, &ctx.accounts.escrow_b),
+        (
+            &ctx.accounts.owner_token_account_a,
+            &ctx.accounts.owner_token_account_b,
+        ),
+        &ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.long_term_order.a_to_b,
+        (proceeds, unsold),
+    )?;
+
+    if expired {
+        ctx.accounts
+            .long_term_order
+            .close(ctx.accounts.owner.to_account_info())?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Account<'info, Pool>,
+
+    #[account(
+        mut,
+        seeds = [TWAMM_SEED, liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub twamm: AccountLoader<'info, Twamm>,
+
+    #[account(
+        constraint = mint_a.key() == liquidity_pool.mint_a @ DEXError::MintNotInPool
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        constraint = mint_b.key() == liquidity_pool.mint_b @ DEXError::MintNotInPool
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = twamm
+    )]
+    pub escrow_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = twamm
+    )]
+    pub escrow_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = owner
+    )]
+    pub owner_token_account_b: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        has_one = owner @ DEXError::OrderNotFound,
+        has_one = twamm @ DEXError::OrderNotFound,
+    )]
+    pub long_term_order: Account<'info, LongTermOrder>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,124 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{burn, transfer, Burn, Token, Transfer};
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::constants::MINIMUM_LIQUIDITY_WITHDRAWAL;
+use crate::curves::multi::{fee_bearing_amount, single_token_withdrawal_amount};
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::MultiPool;
+use crate::utils::get_multi_pool_signer_seeds;
+
+/// Withdraws a single token of a multi-asset pool. The part of the withdrawal that is
+/// implicitly swapped from the other tokens pays the swap fee.
+pub fn withdraw_single_token_liquidity(
+    ctx: Context<WithdrawSingleTokenLiquidity>,
+    lp_tokens_amount: u64,
+    min_amount_out: u64,
+) -> Result<()> {
+    require!(
+        lp_tokens_amount >= MINIMUM_LIQUIDITY_WITHDRAWAL,
+        DEXError::WithdrawalTooSmall
+    );
+
+    let total_lp_supply = ctx.accounts.lp_mint.supply;
+    require!(total_lp_supply > 0, DEXError::EmptyPool);
+
+    let pool = &mut ctx.accounts.multi_pool;
+    let index = pool.token_index(&ctx.accounts.mint.key())?;
+
+    require_keys_eq!(
+        ctx.accounts.vault.key(),
+        pool.tokens[index].vault,
+        DEXError::WrongVaultSpecified
+    );
+
+    let amount_out = single_token_withdrawal_amount(
+        pool,
+        index,
+        lp_tokens_amount as u128,
+        total_lp_supply as u128,
+    )?;
+
+    let fee_amount = mul_div_floor(
+        fee_bearing_amount(pool, index, amount_out)?,
+        pool.fee_bps as u128,
+        10_000,
+    )?;
+    let amount_out = (amount_out - fee_amount) as u64;
+
+    require!(amount_out > 0, DEXError::WithdrawalTooSmall);
+    require!(amount_out >= min_amount_out, DEXError::SlippageExceeded);
+
+    pool.tokens[index].balance -= amount_out;
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.lp_mint.to_account_info(),
+                from: ctx.accounts.user_lp_tokens_account.to_account_info(),
+                authority: ctx.accounts.signer.to_account_info(),
+            },
+        ),
+        lp_tokens_amount,
+    )?;
+
+    let signer_seeds = get_multi_pool_signer_seeds(&pool.lp_mint, &pool.bump);
+    let signer_seeds_slice: &[&[&[u8]]] = &[&signer_seeds];
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.user_token_account.to_account_info(),
+                authority: pool.to_account_info(),
+            },
+            signer_seeds_slice,
+        ),
+        amount_out,
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSingleTokenLiquidity<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = lp_mint
+    )]
+    pub multi_pool: Account<'info, MultiPool>,
+
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = multi_pool
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub lp_mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = signer
+    )]
+    pub user_lp_tokens_account: InterfaceAccount<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = signer
+    )]
+    pub user_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
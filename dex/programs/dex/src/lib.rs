@@ -1,11 +1,18 @@
 mod constants;
+mod curves;
 mod errors;
+mod events;
 mod instructions;
-mod state;
+mod math;
+pub mod state;
 mod utils;
 use anchor_lang::prelude::*;
 
 use instructions::*;
+use state::{
+    DynamicFeeConfig, FeeTier, ImpactFeeConfig, LiquidityShape, LockedLiquidity, LpPositionValue,
+    MultiPoolCurve, PoolCurve, RfqQuote, SwapIntent, TriggerPriceSource,
+};
 
 declare_id!("3Erst2Kv5xtrBemCEHekz2wbVEaGet7N3Z6s2eEt7Wjj");
 
@@ -13,8 +20,12 @@ declare_id!("3Erst2Kv5xtrBemCEHekz2wbVEaGet7N3Z6s2eEt7Wjj");
 pub mod dex {
     use super::*;
 
-    pub fn initialize(ctx: Context<InitializeLiquidityPool>, initial_fee_bps: u64) -> Result<()> {
-        instructions::init_liquidity_pool::initialize_liquidity_pool(ctx, initial_fee_bps)
+    pub fn initialize(
+        ctx: Context<InitializeLiquidityPool>,
+        initial_fee_bps: u64,
+        curve: PoolCurve,
+    ) -> Result<()> {
+        instructions::init_liquidity_pool::initialize_liquidity_pool(ctx, initial_fee_bps, curve)
     }
 
     pub fn add_liquidity_to_pool(
@@ -39,4 +50,533 @@ pub mod dex {
     ) -> Result<()> {
         instructions::exchange_tokens::exchange_tokens(ctx, amount_to_exchange, min_receive_amount)
     }
+
+    pub fn quote_exchange(
+        ctx: Context<QuoteExchange>,
+        amount_to_exchange: u64,
+    ) -> Result<SwapQuote> {
+        instructions::quote_exchange::quote_exchange(ctx, amount_to_exchange)
+    }
+
+    pub fn initialize_global_config(
+        ctx: Context<InitializeGlobalConfig>,
+        max_referral_fee_share_bps: u64,
+    ) -> Result<()> {
+        instructions::init_global_config::initialize_global_config(ctx, max_referral_fee_share_bps)
+    }
+
+    pub fn update_global_config(
+        ctx: Context<UpdateGlobalConfig>,
+        max_referral_fee_share_bps: u64,
+    ) -> Result<()> {
+        instructions::update_global_config::update_global_config(ctx, max_referral_fee_share_bps)
+    }
+
+    pub fn set_fee_tiers(ctx: Context<SetFeeTiers>, fee_tiers: Vec<FeeTier>) -> Result<()> {
+        instructions::set_fee_tiers::set_fee_tiers(ctx, fee_tiers)
+    }
+
+    pub fn initialize_trader_stats(ctx: Context<InitializeTraderStats>) -> Result<()> {
+        instructions::init_trader_stats::initialize_trader_stats(ctx)
+    }
+
+    pub fn set_referral_fee_share(
+        ctx: Context<SetReferralFeeShare>,
+        referral_fee_share_bps: u64,
+    ) -> Result<()> {
+        instructions::set_referral_fee_share::set_referral_fee_share(ctx, referral_fee_share_bps)
+    }
+
+    pub fn register_referrer(ctx: Context<RegisterReferrer>) -> Result<()> {
+        instructions::register_referrer::register_referrer(ctx)
+    }
+
+    pub fn set_swap_fees(
+        ctx: Context<SetSwapFees>,
+        fee_bps_a_to_b: u64,
+        fee_bps_b_to_a: u64,
+    ) -> Result<()> {
+        instructions::set_swap_fees::set_swap_fees(ctx, fee_bps_a_to_b, fee_bps_b_to_a)
+    }
+
+    pub fn set_dynamic_fee(
+        ctx: Context<SetDynamicFee>,
+        config: Option<DynamicFeeConfig>,
+    ) -> Result<()> {
+        instructions::set_dynamic_fee::set_dynamic_fee(ctx, config)
+    }
+
+    pub fn set_impact_fee(
+        ctx: Context<SetImpactFee>,
+        config: Option<ImpactFeeConfig>,
+    ) -> Result<()> {
+        instructions::set_impact_fee::set_impact_fee(ctx, config)
+    }
+
+    pub fn initialize_order_book(ctx: Context<InitializeOrderBook>) -> Result<()> {
+        instructions::init_order_book::initialize_order_book(ctx)
+    }
+
+    pub fn place_limit_order(
+        ctx: Context<PlaceLimitOrder>,
+        amount_in: u64,
+        limit_price: u128,
+    ) -> Result<()> {
+        instructions::place_limit_order::place_limit_order(ctx, amount_in, limit_price)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>, order_id: u64) -> Result<()> {
+        instructions::cancel_order::cancel_order(ctx, order_id)
+    }
+
+    pub fn claim_filled(ctx: Context<ClaimFilled>, order_id: u64) -> Result<()> {
+        instructions::claim_filled::claim_filled(ctx, order_id)
+    }
+
+    pub fn fill_orders(ctx: Context<FillOrders>) -> Result<()> {
+        instructions::fill_orders::fill_orders(ctx)
+    }
+
+    pub fn initialize_twamm(ctx: Context<InitializeTwamm>, order_interval: i64) -> Result<()> {
+        instructions::init_twamm::initialize_twamm(ctx, order_interval)
+    }
+
+    pub fn submit_long_term_order(
+        ctx: Context<SubmitLongTermOrder>,
+        sell_token_a: bool,
+        amount_in: u64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::submit_long_term_order::submit_long_term_order(
+            ctx,
+            sell_token_a,
+            amount_in,
+            duration,
+        )
+    }
+
+    pub fn cancel_long_term_order(ctx: Context<CancelLongTermOrder>) -> Result<()> {
+        instructions::cancel_long_term_order::cancel_long_term_order(ctx)
+    }
+
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>) -> Result<()> {
+        instructions::withdraw_proceeds::withdraw_proceeds(ctx)
+    }
+
+    pub fn settle_long_term_order(ctx: Context<SettleLongTermOrder>) -> Result<()> {
+        instructions::settle_long_term_order::settle_long_term_order(ctx)
+    }
+
+    pub fn execute_long_term_orders(ctx: Context<ExecuteLongTermOrders>) -> Result<()> {
+        instructions::execute_long_term_orders::execute_long_term_orders(ctx)
+    }
+
+    pub fn open_dca_order(
+        ctx: Context<OpenDcaOrder>,
+        id: u64,
+        deposit: u64,
+        amount_per_cycle: u64,
+        cycle_interval: i64,
+        min_out_per_cycle: u64,
+    ) -> Result<()> {
+        instructions::open_dca_order::open_dca_order(
+            ctx,
+            id,
+            deposit,
+            amount_per_cycle,
+            cycle_interval,
+            min_out_per_cycle,
+        )
+    }
+
+    pub fn execute_dca(ctx: Context<ExecuteDca>) -> Result<()> {
+        instructions::execute_dca::execute_dca(ctx)
+    }
+
+    pub fn close_dca_order(ctx: Context<CloseDcaOrder>) -> Result<()> {
+        instructions::close_dca_order::close_dca_order(ctx)
+    }
+
+    pub fn place_trigger_order(
+        ctx: Context<PlaceTriggerOrder>,
+        id: u64,
+        amount_in: u64,
+        min_amount_out: u64,
+        trigger_price: u128,
+        trigger_above: bool,
+        price_source: TriggerPriceSource,
+    ) -> Result<()> {
+        instructions::place_trigger_order::place_trigger_order(
+            ctx,
+            id,
+            amount_in,
+            min_amount_out,
+            trigger_price,
+            trigger_above,
+            price_source,
+        )
+    }
+
+    pub fn execute_trigger_order(ctx: Context<ExecuteTriggerOrder>) -> Result<()> {
+        instructions::execute_trigger_order::execute_trigger_order(ctx)
+    }
+
+    pub fn cancel_trigger_order(ctx: Context<CancelTriggerOrder>) -> Result<()> {
+        instructions::cancel_trigger_order::cancel_trigger_order(ctx)
+    }
+
+    pub fn add_market_maker(ctx: Context<AddMarketMaker>, maker: Pubkey) -> Result<()> {
+        instructions::add_market_maker::add_market_maker(ctx, maker)
+    }
+
+    pub fn remove_market_maker(ctx: Context<RemoveMarketMaker>) -> Result<()> {
+        instructions::remove_market_maker::remove_market_maker(ctx)
+    }
+
+    pub fn fill_rfq(
+        ctx: Context<FillRfq>,
+        quote: RfqQuote,
+        amount_in: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::fill_rfq::fill_rfq(ctx, quote, amount_in, min_amount_out)
+    }
+
+    pub fn close_rfq_nonce(ctx: Context<CloseRfqNonce>) -> Result<()> {
+        instructions::close_rfq_nonce::close_rfq_nonce(ctx)
+    }
+
+    pub fn execute_intent(ctx: Context<ExecuteIntent>, intent: SwapIntent) -> Result<()> {
+        instructions::execute_intent::execute_intent(ctx, intent)
+    }
+
+    pub fn close_intent_nonce(ctx: Context<CloseIntentNonce>) -> Result<()> {
+        instructions::close_intent_nonce::close_intent_nonce(ctx)
+    }
+
+    pub fn initialize_batch_auction(
+        ctx: Context<InitializeBatchAuction>,
+        min_order_amount_a: u64,
+        min_order_amount_b: u64,
+    ) -> Result<()> {
+        instructions::init_batch_auction::initialize_batch_auction(
+            ctx,
+            min_order_amount_a,
+            min_order_amount_b,
+        )
+    }
+
+    pub fn settle_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBatch<'info>>,
+    ) -> Result<()> {
+        instructions::settle_batch::settle_batch(ctx)
+    }
+
+    pub fn initialize_market(ctx: Context<InitializeMarket>) -> Result<()> {
+        instructions::init_market::initialize_market(ctx)
+    }
+
+    pub fn place_market_order(
+        ctx: Context<PlaceMarketOrder>,
+        bid: bool,
+        price: u128,
+        amount_in: u64,
+    ) -> Result<()> {
+        instructions::place_market_order::place_market_order(ctx, bid, price, amount_in)
+    }
+
+    pub fn close_market_order(ctx: Context<CloseMarketOrder>, order_id: u64) -> Result<()> {
+        instructions::close_market_order::close_market_order(ctx, order_id)
+    }
+
+    pub fn swap_best(
+        ctx: Context<SwapBest>,
+        amount_to_exchange: u64,
+        min_receive_amount: u64,
+    ) -> Result<()> {
+        instructions::swap_best::swap_best(ctx, amount_to_exchange, min_receive_amount)
+    }
+
+    pub fn initialize_farm(ctx: Context<InitializeFarm>) -> Result<()> {
+        instructions::init_farm::initialize_farm(ctx)
+    }
+
+    pub fn add_farm_reward(
+        ctx: Context<AddFarmReward>,
+        funder: Pubkey,
+        reward_per_second: u64,
+        start_ts: i64,
+        end_ts: i64,
+    ) -> Result<()> {
+        instructions::add_farm_reward::add_farm_reward(
+            ctx,
+            funder,
+            reward_per_second,
+            start_ts,
+            end_ts,
+        )
+    }
+
+    pub fn fund_reward(ctx: Context<FundReward>, reward_index: u8, amount: u64) -> Result<()> {
+        instructions::fund_reward::fund_reward(ctx, reward_index, amount)
+    }
+
+    pub fn extend_reward(
+        ctx: Context<ExtendReward>,
+        reward_index: u8,
+        end_ts: i64,
+        reward_per_second: u64,
+    ) -> Result<()> {
+        instructions::extend_reward::extend_reward(ctx, reward_index, end_ts, reward_per_second)
+    }
+
+    pub fn stake_lp(ctx: Context<StakeLp>, amount: u64) -> Result<()> {
+        instructions::stake_lp::stake_lp(ctx, amount)
+    }
+
+    pub fn unstake_lp(ctx: Context<UnstakeLp>, amount: u64) -> Result<()> {
+        instructions::unstake_lp::unstake_lp(ctx, amount)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>, reward_index: u8) -> Result<()> {
+        instructions::claim_rewards::claim_rewards(ctx, reward_index)
+    }
+
+    pub fn open_lp_position(
+        ctx: Context<OpenLpPosition>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+    ) -> Result<()> {
+        instructions::open_lp_position::open_lp_position(ctx, token_a_amount, token_b_amount)
+    }
+
+    pub fn close_lp_position(ctx: Context<CloseLpPosition>) -> Result<()> {
+        instructions::close_lp_position::close_lp_position(ctx)
+    }
+
+    pub fn quote_lp_position(ctx: Context<QuoteLpPosition>) -> Result<LpPositionValue> {
+        instructions::quote_lp_position::quote_lp_position(ctx)
+    }
+
+    pub fn set_fee_separation(ctx: Context<SetFeeSeparation>, enabled: bool) -> Result<()> {
+        instructions::set_fee_separation::set_fee_separation(ctx, enabled)
+    }
+
+    pub fn collect_fees(ctx: Context<CollectFees>) -> Result<()> {
+        instructions::collect_fees::collect_fees(ctx)
+    }
+
+    pub fn lock_liquidity(
+        ctx: Context<LockLiquidity>,
+        id: u64,
+        lp_amount: u64,
+        unlock_ts: i64,
+        linear_vesting: bool,
+    ) -> Result<()> {
+        instructions::lock_liquidity::lock_liquidity(ctx, id, lp_amount, unlock_ts, linear_vesting)
+    }
+
+    pub fn unlock_liquidity(ctx: Context<UnlockLiquidity>) -> Result<()> {
+        instructions::unlock_liquidity::unlock_liquidity(ctx)
+    }
+
+    pub fn quote_locked_liquidity(ctx: Context<QuoteLockedLiquidity>) -> Result<LockedLiquidity> {
+        instructions::quote_locked_liquidity::quote_locked_liquidity(ctx)
+    }
+
+    pub fn finalize(ctx: Context<FinalizeLbp>) -> Result<()> {
+        instructions::finalize_lbp::finalize_lbp(ctx)
+    }
+
+    pub fn update_peg_rate(ctx: Context<UpdatePegRate>, new_rate: u64) -> Result<()> {
+        instructions::update_peg_rate::update_peg_rate(ctx, new_rate)
+    }
+
+    pub fn initialize_concentrated_pool(
+        ctx: Context<InitializeConcentratedPool>,
+        tick_spacing: u16,
+        fee_bps: u64,
+        initial_sqrt_price: u128,
+    ) -> Result<()> {
+        instructions::init_concentrated_pool::initialize_concentrated_pool(
+            ctx,
+            tick_spacing,
+            fee_bps,
+            initial_sqrt_price,
+        )
+    }
+
+    pub fn initialize_tick_array(
+        ctx: Context<InitializeTickArray>,
+        start_tick_index: i32,
+    ) -> Result<()> {
+        instructions::init_tick_array::initialize_tick_array(ctx, start_tick_index)
+    }
+
+    pub fn open_position(
+        ctx: Context<OpenPosition>,
+        tick_lower: i32,
+        tick_upper: i32,
+    ) -> Result<()> {
+        instructions::open_position::open_position(ctx, tick_lower, tick_upper)
+    }
+
+    pub fn increase_liquidity(
+        ctx: Context<IncreaseLiquidity>,
+        liquidity: u128,
+        max_token_a_amount: u64,
+        max_token_b_amount: u64,
+    ) -> Result<()> {
+        instructions::increase_liquidity::increase_liquidity(
+            ctx,
+            liquidity,
+            max_token_a_amount,
+            max_token_b_amount,
+        )
+    }
+
+    pub fn decrease_liquidity(
+        ctx: Context<DecreaseLiquidity>,
+        liquidity: u128,
+        min_token_a_amount: u64,
+        min_token_b_amount: u64,
+    ) -> Result<()> {
+        instructions::decrease_liquidity::decrease_liquidity(
+            ctx,
+            liquidity,
+            min_token_a_amount,
+            min_token_b_amount,
+        )
+    }
+
+    pub fn collect_concentrated_fees(ctx: Context<CollectConcentratedFees>) -> Result<()> {
+        instructions::collect_concentrated_fees::collect_concentrated_fees(ctx)
+    }
+
+    pub fn exchange_tokens_concentrated<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExchangeTokensConcentrated<'info>>,
+        amount_to_exchange: u64,
+        min_receive_amount: u64,
+    ) -> Result<()> {
+        instructions::exchange_tokens_concentrated::exchange_tokens_concentrated(
+            ctx,
+            amount_to_exchange,
+            min_receive_amount,
+        )
+    }
+
+    pub fn initialize_bin_pool(
+        ctx: Context<InitializeBinPool>,
+        bin_step: u16,
+        fee_bps: u64,
+        active_bin_id: i32,
+    ) -> Result<()> {
+        instructions::init_bin_pool::initialize_bin_pool(ctx, bin_step, fee_bps, active_bin_id)
+    }
+
+    pub fn initialize_bin_array(ctx: Context<InitializeBinArray>, index: i32) -> Result<()> {
+        instructions::init_bin_array::initialize_bin_array(ctx, index)
+    }
+
+    pub fn add_bin_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddBinLiquidity<'info>>,
+        token_a_amount: u64,
+        token_b_amount: u64,
+        lower_bin_id: i32,
+        upper_bin_id: i32,
+        shape: LiquidityShape,
+    ) -> Result<()> {
+        instructions::add_bin_liquidity::add_bin_liquidity(
+            ctx,
+            token_a_amount,
+            token_b_amount,
+            lower_bin_id,
+            upper_bin_id,
+            shape,
+        )
+    }
+
+    pub fn withdraw_bin_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawBinLiquidity<'info>>,
+        lp_tokens_amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_bin_liquidity::withdraw_bin_liquidity(ctx, lp_tokens_amount)
+    }
+
+    pub fn continue_bin_withdrawal<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ContinueBinWithdrawal<'info>>,
+    ) -> Result<()> {
+        instructions::continue_bin_withdrawal::continue_bin_withdrawal(ctx)
+    }
+
+    pub fn exchange_tokens_bin_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ExchangeTokensBinPool<'info>>,
+        amount_to_exchange: u64,
+        min_receive_amount: u64,
+    ) -> Result<()> {
+        instructions::exchange_tokens_bin_pool::exchange_tokens_bin_pool(
+            ctx,
+            amount_to_exchange,
+            min_receive_amount,
+        )
+    }
+
+    pub fn initialize_multi_pool<'info>(
+        ctx: Context<'_, '_, 'info, 'info, InitializeMultiPool<'info>>,
+        fee_bps: u64,
+        curve: MultiPoolCurve,
+        weights_bps: Vec<u64>,
+    ) -> Result<()> {
+        instructions::init_multi_pool::initialize_multi_pool(ctx, fee_bps, curve, weights_bps)
+    }
+
+    pub fn add_multi_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, AddMultiLiquidity<'info>>,
+        max_amounts: Vec<u64>,
+    ) -> Result<()> {
+        instructions::add_multi_liquidity::add_multi_liquidity(ctx, max_amounts)
+    }
+
+    pub fn withdraw_multi_liquidity<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawMultiLiquidity<'info>>,
+        lp_tokens_amount: u64,
+    ) -> Result<()> {
+        instructions::withdraw_multi_liquidity::withdraw_multi_liquidity(ctx, lp_tokens_amount)
+    }
+
+    pub fn add_single_token_liquidity(
+        ctx: Context<AddSingleTokenLiquidity>,
+        amount_in: u64,
+        min_lp_tokens_amount: u64,
+    ) -> Result<()> {
+        instructions::add_single_token_liquidity::add_single_token_liquidity(
+            ctx,
+            amount_in,
+            min_lp_tokens_amount,
+        )
+    }
+
+    pub fn withdraw_single_token_liquidity(
+        ctx: Context<WithdrawSingleTokenLiquidity>,
+        lp_tokens_amount: u64,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::withdraw_single_token_liquidity::withdraw_single_token_liquidity(
+            ctx,
+            lp_tokens_amount,
+            min_amount_out,
+        )
+    }
+
+    pub fn exchange_tokens_multi(
+        ctx: Context<ExchangeTokensMulti>,
+        amount_to_exchange: u64,
+        min_receive_amount: u64,
+    ) -> Result<()> {
+        instructions::exchange_tokens_multi::exchange_tokens_multi(
+            ctx,
+            amount_to_exchange,
+            min_receive_amount,
+        )
+    }
 }
@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+/// 1.0 in the 18-decimal fixed point used by the weighted math.
+pub const ONE: u128 = 1_000_000_000_000_000_000;
+
+// ln(2) scaled by ONE
+const LN_2: u128 = 693_147_180_559_945_309;
+
+// Fractional bits resolved by `log2`. Every extra iteration doubles the error of
+// the squared value, so going further than this only adds noise.
+const LOG2_PRECISION_BITS: u32 = 40;
+
+const LOW_MASK: u128 = u64::MAX as u128;
+
+/// Full 128x128 -> 256 bit multiplication, returned as (high, low) words.
+fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a_hi, a_lo) = (a >> 64, a & LOW_MASK);
+    let (b_hi, b_lo) = (b >> 64, b & LOW_MASK);
+
+    let lo_lo = a_lo * b_lo;
+    let lo_hi = a_lo * b_hi;
+    let hi_lo = a_hi * b_lo;
+    let hi_hi = a_hi * b_hi;
+
+    let mid = (lo_lo >> 64) + (lo_hi & LOW_MASK) + (hi_lo & LOW_MASK);
+    let low = (lo_lo & LOW_MASK) | (mid << 64);
+    let high = hi_hi + (lo_hi >> 64) + (hi_lo >> 64) + (mid >> 64);
+
+    (high, low)
+}
+
+/// Divides the 256 bit value (high, low) by `divisor`, returning (quotient, remainder).
+/// Returns `None` when the quotient does not fit in 128 bits.
+fn div_rem_256(high: u128, low: u128, divisor: u128) -> Option<(u128, u128)> {
+    if divisor == 0 || high >= divisor {
+        return None;
+    }
+
+    if high == 0 {
+        return Some((low / divisor, low % divisor));
+    }
+
+    // Plain shift-subtract long division. `remainder` always stays below `divisor`,
+    // the bit shifted out on the left is tracked so that values above 2^128 are handled.
+    let mut remainder = high;
+    let mut quotient = 0u128;
+
+    for i in (0..128).rev() {
+        let carry = remainder >> 127;
+        remainder = (remainder << 1) | ((low >> i) & 1);
+        quotient <<= 1;
+
+        if carry == 1 || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient |= 1;
+        }
+    }
+
+    Some((quotient, remainder))
+}
+
+/// floor(a * b / divisor) without intermediate overflow
+pub fn mul_div_floor(a: u128, b: u128, divisor: u128) -> Result<u128> {
+    let (high, low) = full_mul(a, b);
+    let (quotient, _) = div_rem_256(high, low, divisor).ok_or(DEXError::MathOverflow)?;
+
+    Ok(quotient)
+}
+
+/// ceil(a * b / divisor) without intermediate overflow
+pub fn mul_div_ceil(a: u128, b: u128, divisor: u128) -> Result<u128> {
+    let (high, low) = full_mul(a, b);
+    let (quotient, remainder) = div_rem_256(high, low, divisor).ok_or(DEXError::MathOverflow)?;
+
+    if remainder == 0 {
+        Ok(quotient)
+    } else {
+        quotient
+            .checked_add(1)
+            .ok_or_else(|| error!(DEXError::MathOverflow))
+    }
+}
+
+/// (a * b) >> shift without intermediate overflow, for shifts up to 128 bits
+pub fn mul_shr(a: u128, b: u128, shift: u32) -> Result<u128> {
+    require!(shift <= 128, DEXError::MathOverflow);
+
+    let (high, low) = full_mul(a, b);
+
+    if shift == 128 {
+        return Ok(high);
+    }
+
+    require!(high >> shift == 0, DEXError::MathOverflow);
+
+    if shift == 0 {
+        Ok(low)
+    } else {
+        Ok((high << (128 - shift)) | (low >> shift))
+    }
+}
+
+/// 2^192 / divisor, the reciprocal of a Q128 fraction expressed in Q64.64
+pub fn reciprocal_q64(divisor: u128) -> Result<u128> {
+    let (quotient, _) = div_rem_256(1 << 64, 0, divisor).ok_or(DEXError::MathOverflow)?;
+
+    Ok(quotient)
+}
+
+/// log2(x) where both `x` and the result are scaled by `ONE`
+pub fn log2(x: u128) -> Result<i128> {
+    require!(x > 0, DEXError::MathOverflow);
+
+    // Bring x into [1, 2) while counting the integer part of the logarithm
+    let mut y = x;
+    let mut integer_part: i128 = 0;
+
+    while y >= 2 * ONE {
+        y >>= 1;
+        integer_part += 1;
+    }
+
+    while y < ONE {
+        y <<= 1;
+        integer_part -= 1;
+    }
+
+    // Every squaring of y in [1, 2) reveals the next binary digit of the fraction
+    let mut result = integer_part * ONE as i128;
+    let mut delta = ONE / 2;
+
+    for _ in 0..LOG2_PRECISION_BITS {
+        y = y * y / ONE;
+
+        if y >= 2 * ONE {
+            y >>= 1;
+            result += delta as i128;
+        }
+
+        delta >>= 1;
+    }
+
+    Ok(result)
+}
+
+/// 2^x where both `x` and the result are scaled by `ONE`
+pub fn exp2(x: i128) -> Result<u128> {
+    let one = ONE as i128;
+
+    // Split x into floor(x) and a fraction in [0, 1)
+    let integer_part = x.div_euclid(one);
+    let fraction = x.rem_euclid(one) as u128;
+
+    // 2^fraction = e^(fraction * ln 2) through the Taylor series, which converges
+    // quickly because the exponent is below ln 2
+    let t = fraction * LN_2 / ONE;
+    let mut term = ONE;
+    let mut sum = ONE;
+
+    for i in 1..=32u128 {
+        term = term * t / ONE / i;
+
+        if term == 0 {
+            break;
+        }
+
+        sum += term;
+    }
+
+    if integer_part >= 0 {
+        // sum < 2 * ONE, which occupies 61 bits
+        require!(integer_part < 66, DEXError::MathOverflow);
+        Ok(sum << integer_part)
+    } else if integer_part > -128 {
+        Ok(sum >> (-integer_part))
+    } else {
+        Ok(0)
+    }
+}
+
+/// base^exponent for a positive `base`, both operands and the result scaled by `ONE`
+pub fn pow(base: u128, exponent: u128) -> Result<u128> {
+    if exponent == 0 {
+        return Ok(ONE);
+    }
+
+    if base == 0 {
+        return Ok(0);
+    }
+
+    let log = log2(base)?;
+    let magnitude = mul_div_floor(log.unsigned_abs(), exponent, ONE)?;
+    let magnitude = i128::try_from(magnitude).map_err(|_| DEXError::MathOverflow)?;
+
+    exp2(if log < 0 { -magnitude } else { magnitude })
+}
@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+
+use crate::curves::constant_product;
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::Pool;
+
+// Upper bound on orders in a batch, keeps the payout accounts of `settle_batch` within
+// the transaction account limit
+pub const MAX_BATCH_ORDERS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct BatchOrder {
+    pub trader: Pubkey,
+    /// Refunded to when the clearing price misses `min_amount_out`
+    pub token_account_in: Pubkey,
+    /// Paid to when the batch clears
+    pub token_account_out: Pubkey,
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+}
+
+impl BatchOrder {
+    // trader + 2 token accounts + direction + amount + min out
+    pub const SIZE: usize = 32 + 32 + 32 + 1 + 8 + 8;
+}
+
+/// Swaps queued on a batch auction pool during `slot`. Their input is escrowed in token
+/// accounts owned by this account until `settle_batch` clears them all at one price.
+/// Swaps placed while that batch waits to be settled queue up for the next one.
+#[account]
+pub struct BatchAuction {
+    pub pool: Pubkey,
+    pub escrow_a: Pubkey,
+    pub escrow_b: Pubkey,
+    /// Slot the queued orders were placed in
+    pub slot: u64,
+    pub orders: Vec<BatchOrder>,
+    /// Last slot an order of the next batch was placed in
+    pub next_slot: u64,
+    pub next_orders: Vec<BatchOrder>,
+    /// Smallest input of an order selling token A, keeps dust out of the batches
+    pub min_order_amount_a: u64,
+    /// Smallest input of an order selling token B
+    pub min_order_amount_b: u64,
+    pub bump: u8,
+}
+
+impl BatchAuction {
+    // discriminator + pool + 2 escrows + 2 slots + 2 order queues + 2 minimums + bump
+    pub const MAX_SIZE: usize =
+        8 + 32 + 32 + 32 + 2 * 8 + 2 * (4 + MAX_BATCH_ORDERS * BatchOrder::SIZE) + 2 * 8 + 1;
+
+    /// Queues an order placed during `slot`. It joins the current batch while that slot
+    /// lasts, and the next batch once the current one only waits to be settled.
+    pub fn push(&mut self, order: BatchOrder, slot: u64) -> Result<()> {
+        let min_amount = if order.a_to_b {
+            self.min_order_amount_a
+        } else {
+            self.min_order_amount_b
+        };
+        require!(order.amount_in >= min_amount, DEXError::BatchOrderTooSmall);
+
+        if self.orders.is_empty() {
+            self.slot = slot;
+        }
+
+        let orders = if self.slot == slot {
+            &mut self.orders
+        } else {
+            self.next_slot = slot;
+            &mut self.next_orders
+        };
+
+        require!(orders.len() < MAX_BATCH_ORDERS, DEXError::BatchFull);
+
+        orders.push(order);
+
+        Ok(())
+    }
+
+    /// Drops the settled batch, the next one taking its place
+    pub fn advance(&mut self) {
+        self.orders = std::mem::take(&mut self.next_orders);
+        self.slot = self.next_slot;
+    }
+}
+
+/// Outcome of clearing a batch against a constant product pool: sellers of token A share
+/// `amount_b_out` pro rata, sellers of token B share `amount_a_out`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BatchClearing {
+    pub total_a_in: u64,
+    pub total_b_in: u64,
+    pub amount_a_out: u64,
+    pub amount_b_out: u64,
+    /// Fee kept by the pool on the part of the batch it took the other side of
+    pub fee_amount: u64,
+}
+
+impl BatchClearing {
+    /// Matches both sides of the batch with each other, swapping only the imbalance
+    /// through the pool, at the base fee of its direction
+    pub fn compute(
+        pool: &Pool,
+        (total_a_in, total_b_in): (u64, u64),
+        (reserve_a, reserve_b): (u64, u64),
+    ) -> Result<Self> {
+        let swap = |sold_in: u64, sold_out: u64, reserve_in: u64, reserve_out: u64, fee_bps| {
+            let pool_in = constant_product::batch_net_amount_in(
+                sold_in as u128,
+                sold_out as u128,
+                reserve_in as u128,
+                reserve_out as u128,
+                fee_bps,
+            )? as u64;
+
+            let fee_amount = mul_div_floor(pool_in as u128, fee_bps as u128, 10_000)? as u64;
+            let pool_out = constant_product::amount_out(
+                (pool_in - fee_amount) as u128,
+                reserve_in as u128,
+                reserve_out as u128,
+            )? as u64;
+
+            Ok::<_, Error>((pool_in, pool_out, fee_amount))
+        };
+
+        let (pool_a_in, pool_b_out, fee_a) = swap(
+            total_a_in,
+            total_b_in,
+            reserve_a,
+            reserve_b,
+            pool.fee_bps_a_to_b,
+        )?;
+
+        let clearing = if pool_a_in > 0 {
+            Self {
+                total_a_in,
+                total_b_in,
+                amount_a_out: total_a_in - pool_a_in,
+                amount_b_out: total_b_in + pool_b_out,
+                fee_amount: fee_a,
+            }
+        } else {
+            let (pool_b_in, pool_a_out, fee_b) = swap(
+                total_b_in,
+                total_a_in,
+                reserve_b,
+                reserve_a,
+                pool.fee_bps_b_to_a,
+            )?;
+
+            Self {
+                total_a_in,
+                total_b_in,
+                amount_a_out: total_a_in + pool_a_out,
+                amount_b_out: total_b_in - pool_b_in,
+                fee_amount: fee_b,
+            }
+        };
+
+        Ok(clearing)
+    }
+
+    /// Pro rata share of the batch output owed to `order`
+    pub fn amount_out(&self, order: &BatchOrder) -> Result<u64> {
+        let (total_in, total_out) = if order.a_to_b {
+            (self.total_a_in, self.amount_b_out)
+        } else {
+            (self.total_b_in, self.amount_a_out)
+        };
+
+        if total_in == 0 {
+            return Ok(0);
+        }
+
+        Ok(mul_div_floor(order.amount_in as u128, total_out as u128, total_in as u128)? as u64)
+    }
+
+    /// Uniform price every order cleared at, token B per token A in Q64.64
+    pub fn price(&self) -> u128 {
+        if self.total_a_in > 0 {
+            Pool::spot_price(self.total_a_in, self.amount_b_out)
+        } else {
+            Pool::spot_price(self.amount_a_out, self.total_b_in)
+        }
+    }
+}
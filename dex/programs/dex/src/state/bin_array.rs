@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+pub const BINS_PER_ARRAY: usize = 64;
+
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct Bin {
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+/// Bins `index * BINS_PER_ARRAY` up to (excluding) `(index + 1) * BINS_PER_ARRAY`
+#[account(zero_copy)]
+pub struct BinArray {
+    pub bins: [Bin; BINS_PER_ARRAY],
+    pub pool: Pubkey,
+    pub index: i32,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 4],
+}
+
+impl BinArray {
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<BinArray>();
+
+    pub fn index_for(bin_id: i32) -> i32 {
+        bin_id.div_euclid(BINS_PER_ARRAY as i32)
+    }
+
+    pub fn contains(&self, bin_id: i32) -> bool {
+        Self::index_for(bin_id) == self.index
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bins
+            .iter()
+            .all(|bin| bin.amount_a == 0 && bin.amount_b == 0)
+    }
+
+    pub fn bin_mut(&mut self, bin_id: i32) -> Result<&mut Bin> {
+        require!(self.contains(bin_id), DEXError::InvalidBinArray);
+
+        Ok(&mut self.bins[bin_id.rem_euclid(BINS_PER_ARRAY as i32) as usize])
+    }
+}
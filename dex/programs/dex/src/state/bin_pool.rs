@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::BinArray;
+
+/// How a deposit is spread over its bin range. Token A goes to the bins at and above
+/// the active bin, token B to the bins at and below it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LiquidityShape {
+    /// Same amount in every bin
+    Spot,
+    /// Concentrated around the active bin, tapering off linearly
+    Curve,
+    /// Growing linearly away from the active bin
+    BidAsk,
+}
+
+/// LP tokens burned for a share of every bin array, paid out array by array.
+/// The share is taken from the arrays as they are passed, so deposits and other
+/// withdrawals wait until every array of `remaining_bitmap` has been paid.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug)]
+pub struct BinWithdrawal {
+    pub owner: Pubkey,
+    pub lp_amount: u64,
+    /// LP supply before the burn
+    pub lp_supply: u64,
+    /// Arrays the share still has to be taken from
+    pub remaining_bitmap: u128,
+}
+
+/// Discrete-bin liquidity pool. Every bin trades at a fixed price, bins above the
+/// active one only hold token A and bins below it only hold token B.
+/// Liquidity providers share the whole pool through the LP mint, like in `Pool`.
+#[account]
+pub struct BinPool {
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fee_bps: u64,
+    /// Price increment between two consecutive bins, in bps
+    pub bin_step: u16,
+    pub active_bin_id: i32,
+    /// Bit `index - MIN_BIN_ARRAY_INDEX` is set for every bin array that holds liquidity
+    pub bin_array_bitmap: u128,
+    pub pending_withdrawal: BinWithdrawal,
+    pub bump: u8,
+}
+
+impl BinPool {
+    // 5 pubkeys + fee + bin step + active bin + bitmap + pending withdrawal + bump
+    pub const MAX_SIZE: usize = 8 + 5 * 32 + 8 + 2 + 4 + 16 + (32 + 8 + 8 + 16) + 1;
+
+    pub const MIN_BIN_ARRAY_INDEX: i32 = -64;
+    pub const MAX_BIN_ARRAY_INDEX: i32 = 63;
+
+    pub fn bin_array_bit(index: i32) -> Result<u128> {
+        require!(
+            (Self::MIN_BIN_ARRAY_INDEX..=Self::MAX_BIN_ARRAY_INDEX).contains(&index),
+            DEXError::InvalidBinId
+        );
+
+        Ok(1u128 << (index - Self::MIN_BIN_ARRAY_INDEX))
+    }
+
+    pub fn withdrawal_pending(&self) -> bool {
+        self.pending_withdrawal.remaining_bitmap != 0
+    }
+
+    /// Takes the share of the pending withdrawal out of every bin of `bin_array` and
+    /// returns the amounts of token A and B. An array left without liquidity is dropped
+    /// from the bitmap.
+    pub fn withdraw_from(&mut self, bin_array: &mut BinArray) -> Result<(u64, u64)> {
+        // Also rejects the same array passed twice
+        let bit = Self::bin_array_bit(bin_array.index)?;
+        require!(
+            self.pending_withdrawal.remaining_bitmap & bit != 0,
+            DEXError::InvalidBinArray
+        );
+        self.pending_withdrawal.remaining_bitmap &= !bit;
+
+        let lp_amount = self.pending_withdrawal.lp_amount as u128;
+        let lp_supply = self.pending_withdrawal.lp_supply as u128;
+        let mut amount_a = 0u64;
+        let mut amount_b = 0u64;
+
+        for bin in bin_array.bins.iter_mut() {
+            let share_a = mul_div_floor(bin.amount_a as u128, lp_amount, lp_supply)? as u64;
+            let share_b = mul_div_floor(bin.amount_b as u128, lp_amount, lp_supply)? as u64;
+
+            bin.amount_a -= share_a;
+            bin.amount_b -= share_b;
+            amount_a += share_a;
+            amount_b += share_b;
+        }
+
+        if bin_array.is_empty() {
+            self.bin_array_bitmap &= !bit;
+        }
+
+        Ok((amount_a, amount_b))
+    }
+}
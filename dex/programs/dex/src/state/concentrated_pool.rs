@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// Concentrated liquidity pool. Liquidity is provided over tick ranges through
+/// positions, ticks themselves live in `TickArray` accounts.
+#[account]
+pub struct ConcentratedPool {
+    pub vault_a: Pubkey,
+    pub vault_b: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub fee_bps: u64,
+    pub tick_spacing: u16,
+    /// Q64.64 square root of the price of token A in token B
+    pub sqrt_price: u128,
+    pub tick_current: i32,
+    /// Liquidity of the positions whose range contains the current price
+    pub liquidity: u128,
+    /// Q64.64 fees earned per unit of liquidity over the whole life of the pool
+    pub fee_growth_global_a: u128,
+    pub fee_growth_global_b: u128,
+    pub bump: u8,
+}
+
+impl ConcentratedPool {
+    // 4 pubkeys + fee + spacing + sqrt price + tick + liquidity + 2 fee growths + bump
+    pub const MAX_SIZE: usize = 8 + 4 * 32 + 8 + 2 + 16 + 4 + 16 + 2 * 16 + 1;
+}
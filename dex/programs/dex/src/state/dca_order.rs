@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::utils::keeper_tip;
+
+/// Recurring swap of an escrowed balance, one cycle at a time
+#[account]
+pub struct DcaOrder {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    /// Token account holding the input left to swap, owned by the order
+    pub escrow: Pubkey,
+    pub id: u64,
+    pub amount_per_cycle: u64,
+    /// Seconds between two cycles
+    pub cycle_interval: i64,
+    /// Minimum output of a full cycle, scaled down for a smaller last cycle
+    pub min_out_per_cycle: u64,
+    pub next_cycle_ts: i64,
+    pub bump: u8,
+}
+
+impl DcaOrder {
+    // discriminator + owner + pool + 2 mints + escrow + id + amount + interval + min out
+    // + next cycle + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Splits the next cycle out of the `remaining` escrow balance into
+    /// (amount swapped, keeper tip, minimum output)
+    pub fn next_cycle(&self, remaining: u64) -> Result<(u64, u64, u64)> {
+        let cycle_amount = self.amount_per_cycle.min(remaining);
+
+        let tip = keeper_tip(cycle_amount)?;
+
+        let min_out = (self.min_out_per_cycle as u128)
+            .checked_mul(cycle_amount as u128)
+            .ok_or(DEXError::MathOverflow)?
+            .checked_div(self.amount_per_cycle as u128)
+            .ok_or(DEXError::MathOverflow)? as u64;
+
+        Ok((cycle_amount - tip, tip, min_out))
+    }
+}
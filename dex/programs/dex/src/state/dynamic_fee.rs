@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+// Variable fee in bps = variable_fee_control * volatility^2 / VARIABLE_FEE_PRECISION
+const VARIABLE_FEE_PRECISION: u128 = 1_000_000;
+
+// Volatility is capped at a 100x price move, far past any sensible fee cap
+const MAX_VOLATILITY_ACCUMULATOR: u64 = 1_000_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DynamicFeeConfig {
+    /// Swaps less than this many seconds apart keep accumulating volatility against the same reference
+    pub filter_period: i64,
+    /// After this many seconds without swaps the volatility is forgotten entirely
+    pub decay_period: i64,
+    /// Share of the volatility carried over when the reference moves, in bps
+    pub reduction_factor_bps: u64,
+    /// Scales the squared volatility into the variable fee
+    pub variable_fee_control: u64,
+    /// Cap on the total fee
+    pub max_fee_bps: u64,
+}
+
+impl DynamicFeeConfig {
+    pub fn validate(&self, base_fee_bps: u64) -> Result<()> {
+        require!(
+            0 <= self.filter_period && self.filter_period < self.decay_period,
+            DEXError::InvalidFeeConfig
+        );
+        require!(
+            self.reduction_factor_bps <= 10_000,
+            DEXError::InvalidFeeConfig
+        );
+        require!(
+            base_fee_bps <= self.max_fee_bps && self.max_fee_bps < 10_000,
+            DEXError::InvalidFeeConfig
+        );
+
+        Ok(())
+    }
+}
+
+/// Volatility-driven fee on top of the pool's base fee. Volatility is measured as the
+/// price movement (in bps) away from a reference price that trails recent swaps.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DynamicFee {
+    pub config: DynamicFeeConfig,
+    /// Volatility after the last swap
+    pub volatility_accumulator: u64,
+    /// Volatility carried over from before the reference price was last moved
+    pub volatility_reference: u64,
+    /// Spot price (token B per token A, Q64.64) the movement is measured from
+    pub reference_price: u128,
+    pub last_update_ts: i64,
+}
+
+impl DynamicFee {
+    // config + 2 volatilities + reference price + timestamp
+    pub const MAX_SIZE: usize = 5 * 8 + 2 * 8 + 16 + 8;
+
+    pub fn new(config: DynamicFeeConfig) -> Self {
+        Self {
+            config,
+            volatility_accumulator: 0,
+            volatility_reference: 0,
+            reference_price: 0,
+            last_update_ts: 0,
+        }
+    }
+
+    /// Moves the reference to `price` unless the last swap happened within the filter
+    /// period, decaying the volatility carried over with the time elapsed
+    pub fn update_reference(&mut self, price: u128, now: i64) {
+        let elapsed = now.saturating_sub(self.last_update_ts);
+
+        if elapsed < self.config.filter_period && self.reference_price != 0 {
+            return;
+        }
+
+        self.reference_price = price;
+        self.volatility_reference = if elapsed < self.config.decay_period {
+            (self.volatility_accumulator as u128 * self.config.reduction_factor_bps as u128
+                / 10_000) as u64
+        } else {
+            0
+        };
+    }
+
+    /// Volatility once the price sits at `price`
+    pub fn volatility_at(&self, price: u128) -> u64 {
+        let movement = if self.reference_price == 0 {
+            0
+        } else {
+            (price.abs_diff(self.reference_price) * 10_000 / self.reference_price)
+                .min(MAX_VOLATILITY_ACCUMULATOR as u128) as u64
+        };
+
+        self.volatility_reference
+            .saturating_add(movement)
+            .min(MAX_VOLATILITY_ACCUMULATOR)
+    }
+
+    pub fn fee_bps(&self, base_fee_bps: u64, volatility: u64) -> u64 {
+        let volatility = volatility as u128;
+        let variable_fee = self.config.variable_fee_control as u128 * volatility * volatility
+            / VARIABLE_FEE_PRECISION;
+
+        (base_fee_bps as u128 + variable_fee).min(self.config.max_fee_bps as u128) as u64
+    }
+
+    /// Records the price reached by a swap
+    pub fn record_swap(&mut self, price: u128, now: i64) {
+        self.volatility_accumulator = self.volatility_at(price);
+        self.last_update_ts = now;
+    }
+}
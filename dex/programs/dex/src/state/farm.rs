@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::mul_shr;
+
+// Upper bound on the rewards of a farm, keeps stakes at a fixed size
+pub const MAX_FARM_REWARDS: usize = 4;
+
+/// Reward token emitted by a farm at `reward_per_second` over the unix times
+/// [start_ts, end_ts), for as long as what its funder deposited covers it
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct FarmReward {
+    pub mint: Pubkey,
+    /// Token account owned by the farm paying the rewards
+    pub vault: Pubkey,
+    /// Only account allowed to fund and extend the reward
+    pub funder: Pubkey,
+    pub reward_per_second: u64,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    /// Q64.64 rewards earned per staked LP token over the whole life of the reward
+    pub reward_per_share: u128,
+    pub amount_funded: u64,
+    pub amount_emitted: u64,
+}
+
+impl FarmReward {
+    // 3 pubkeys + reward per second + 2 timestamps + reward per share + funded + emitted
+    pub const SIZE: usize = 3 * 32 + 8 + 2 * 8 + 16 + 8 + 8;
+}
+
+/// Liquidity mining program of a pool: LP tokens staked in the farm share the emissions of
+/// each of its rewards pro rata
+#[account]
+pub struct Farm {
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    /// Token account owned by the farm holding the staked LP tokens
+    pub lp_vault: Pubkey,
+    pub total_staked: u64,
+    pub last_update_ts: i64,
+    pub rewards: Vec<FarmReward>,
+    pub bump: u8,
+}
+
+impl Farm {
+    // discriminator + 3 pubkeys + staked + timestamp + rewards + bump
+    pub const MAX_SIZE: usize = 8 + 3 * 32 + 8 + 8 + 4 + MAX_FARM_REWARDS * FarmReward::SIZE + 1;
+
+    /// Emits the rewards of the scheduled time since the last update, as far as their
+    /// funding covers them. Nothing is emitted while nothing is staked.
+    pub fn update(&mut self, now: i64) -> Result<()> {
+        let last_update_ts = self.last_update_ts;
+        self.last_update_ts = last_update_ts.max(now);
+
+        if self.total_staked == 0 {
+            return Ok(());
+        }
+
+        for reward in self.rewards.iter_mut() {
+            let from = last_update_ts.max(reward.start_ts);
+            let to = now.min(reward.end_ts);
+
+            if to <= from {
+                continue;
+            }
+
+            let emitted = ((to - from) as u128)
+                .checked_mul(reward.reward_per_second as u128)
+                .ok_or(DEXError::MathOverflow)?
+                .min((reward.amount_funded - reward.amount_emitted) as u128);
+
+            // Reward per share is allowed to wrap around, only differences matter
+            reward.reward_per_share = reward
+                .reward_per_share
+                .wrapping_add((emitted << 64) / self.total_staked as u128);
+            reward.amount_emitted += emitted as u64;
+        }
+
+        Ok(())
+    }
+
+    pub fn reward_mut(&mut self, index: u8) -> Result<&mut FarmReward> {
+        self.rewards
+            .get_mut(index as usize)
+            .ok_or(error!(DEXError::InvalidFarm))
+    }
+}
+
+/// LP tokens staked by a single owner in a farm
+#[account]
+pub struct FarmStake {
+    pub farm: Pubkey,
+    pub owner: Pubkey,
+    pub amount: u64,
+    /// Reward per share of each farm reward at the last time the rewards of this stake
+    /// were updated
+    pub reward_per_share_last: [u128; MAX_FARM_REWARDS],
+    pub rewards_owed: [u64; MAX_FARM_REWARDS],
+    pub bump: u8,
+}
+
+impl FarmStake {
+    // 2 pubkeys + amount + reward per share and owed amount of each reward + bump
+    pub const MAX_SIZE: usize = 8 + 2 * 32 + 8 + MAX_FARM_REWARDS * (16 + 8) + 1;
+
+    /// Credits the rewards earned since the last update and checkpoints the reward per share
+    /// of every farm reward
+    pub fn update_rewards(&mut self, rewards: &[FarmReward]) -> Result<()> {
+        for (index, reward) in rewards.iter().enumerate() {
+            let earned = mul_shr(
+                self.amount as u128,
+                reward
+                    .reward_per_share
+                    .wrapping_sub(self.reward_per_share_last[index]),
+                64,
+            )?;
+
+            self.rewards_owed[index] = self.rewards_owed[index]
+                .checked_add(u64::try_from(earned).map_err(|_| DEXError::MathOverflow)?)
+                .ok_or(DEXError::MathOverflow)?;
+            self.reward_per_share_last[index] = reward.reward_per_share;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+/// Fee discount for traders whose rolling volume on a pool reaches `min_volume`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FeeTier {
+    /// Rolling volume in token A, in the 18-decimal fixed point of `ONE`
+    pub min_volume: u128,
+    /// Discount in bps of the swap fee
+    pub discount_bps: u64,
+}
+
+/// Program-wide settings, a single account at the `GLOBAL_CONFIG_SEED` address
+#[account]
+pub struct GlobalConfig {
+    /// The account allowed to update the config
+    pub admin: Pubkey,
+    /// Highest share of a swap fee, in bps of the fee, a pool may pay out to referrers
+    pub max_referral_fee_share_bps: u64,
+    /// Volume tiers, sorted by `min_volume`
+    pub fee_tiers: Vec<FeeTier>,
+    pub bump: u8,
+}
+
+impl GlobalConfig {
+    pub const MAX_FEE_TIERS: usize = 8;
+
+    // discriminator + admin + referral cap + fee tiers + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 4 + Self::MAX_FEE_TIERS * (16 + 8) + 1;
+
+    /// Discount of the highest tier reached by `volume`
+    pub fn fee_discount_bps(&self, volume: u128) -> u64 {
+        self.fee_tiers
+            .iter()
+            .rev()
+            .find(|tier| volume >= tier.min_volume)
+            .map_or(0, |tier| tier.discount_bps)
+    }
+
+    /// Tiers must be sorted by volume, with discounts growing along with it
+    pub fn validate_fee_tiers(fee_tiers: &[FeeTier]) -> Result<()> {
+        require!(
+            fee_tiers.len() <= Self::MAX_FEE_TIERS,
+            DEXError::InvalidFeeTiers
+        );
+
+        for tier in fee_tiers {
+            require!(tier.discount_bps <= 10_000, DEXError::InvalidBPSValue);
+        }
+
+        for pair in fee_tiers.windows(2) {
+            require!(
+                pair[0].min_volume < pair[1].min_volume
+                    && pair[0].discount_bps <= pair[1].discount_bps,
+                DEXError::InvalidFeeTiers
+            );
+        }
+
+        Ok(())
+    }
+}
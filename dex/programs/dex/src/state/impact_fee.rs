@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ImpactFeeCurve {
+    Linear,
+    Quadratic,
+}
+
+/// Surcharge on trades that are large relative to the input reserve
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ImpactFeeConfig {
+    /// Trade size, in bps of the input reserve, from which the surcharge applies
+    pub threshold_bps: u64,
+    pub curve: ImpactFeeCurve,
+    /// Surcharge in bps for a trade exceeding the threshold by the whole input reserve,
+    /// smaller trades pay it scaled linearly or quadratically
+    pub coefficient_bps: u64,
+    /// Cap on the total fee
+    pub max_fee_bps: u64,
+}
+
+impl ImpactFeeConfig {
+    // threshold + curve tag + coefficient + cap
+    pub const MAX_SIZE: usize = 8 + 1 + 8 + 8;
+
+    pub fn validate(&self, base_fee_bps: u64) -> Result<()> {
+        require!(self.threshold_bps <= 10_000, DEXError::InvalidFeeConfig);
+        require!(
+            base_fee_bps <= self.max_fee_bps && self.max_fee_bps < 10_000,
+            DEXError::InvalidFeeConfig
+        );
+
+        Ok(())
+    }
+
+    /// Fee for trading `amount_in` against `reserve_in` on top of `fee_bps`
+    pub fn fee_bps(&self, fee_bps: u64, amount_in: u64, reserve_in: u64) -> u64 {
+        if reserve_in == 0 {
+            return self.max_fee_bps;
+        }
+
+        let share_bps = (amount_in as u128 * 10_000 / reserve_in as u128).min(10_000);
+        let excess_bps = share_bps.saturating_sub(self.threshold_bps as u128);
+
+        let surcharge = match self.curve {
+            ImpactFeeCurve::Linear => self.coefficient_bps as u128 * excess_bps / 10_000,
+            ImpactFeeCurve::Quadratic => {
+                self.coefficient_bps as u128 * excess_bps * excess_bps / 100_000_000
+            }
+        };
+
+        (fee_bps as u128 + surcharge).min(self.max_fee_bps as u128) as u64
+    }
+}
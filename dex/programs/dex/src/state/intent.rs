@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+/// Swap a user signs off-chain for a relayer to submit, its Borsh serialization being
+/// signed with ed25519
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SwapIntent {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    /// Token sold, the other pool token is bought
+    pub mint_in: Pubkey,
+    pub amount_in: u64,
+    /// Least output the owner receives, after the relayer tip
+    pub min_amount_out: u64,
+    /// Relayer tip in bps of the output, capped by `MAX_RELAYER_TIP_BPS`
+    pub relayer_tip_bps: u64,
+    pub deadline: i64,
+    pub nonce: u64,
+}
+
+/// Marks an intent nonce of a user as used. It can be closed once the intent deadline
+/// passed, as the intent cannot be executed anymore by then.
+#[account]
+pub struct IntentNonce {
+    /// The relayer that executed the intent and paid the rent
+    pub payer: Pubkey,
+    pub deadline: i64,
+    pub bump: u8,
+}
+
+impl IntentNonce {
+    // discriminator + payer + deadline + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 1;
+}
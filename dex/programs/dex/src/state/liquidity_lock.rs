@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+
+/// LP tokens of a pool locked until `unlock_ts`, held in a lock vault owned by the pool so
+/// nobody can move them early. With linear vesting they unlock gradually from `locked_at`.
+#[account]
+pub struct LiquidityLock {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub id: u64,
+    pub lp_amount: u64,
+    /// LP tokens already unlocked and paid back to the owner
+    pub withdrawn: u64,
+    pub locked_at: i64,
+    pub unlock_ts: i64,
+    pub linear_vesting: bool,
+    pub bump: u8,
+}
+
+impl LiquidityLock {
+    // discriminator + owner + pool + id + amount + withdrawn + 2 timestamps + vesting flag
+    // + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 8 + 8 + 8 + 8 + 8 + 1 + 1;
+
+    /// LP tokens unlocked by `now`, withdrawn ones included
+    pub fn unlocked(&self, now: i64) -> Result<u64> {
+        if now >= self.unlock_ts {
+            return Ok(self.lp_amount);
+        }
+
+        if !self.linear_vesting || now <= self.locked_at {
+            return Ok(0);
+        }
+
+        Ok(mul_div_floor(
+            self.lp_amount as u128,
+            (now - self.locked_at) as u128,
+            (self.unlock_ts - self.locked_at) as u128,
+        )? as u64)
+    }
+
+    /// LP tokens that may be withdrawn at `now`
+    pub fn withdrawable(&self, now: i64) -> Result<u64> {
+        let withdrawable = self
+            .unlocked(now)?
+            .checked_sub(self.withdrawn)
+            .ok_or(DEXError::MathOverflow)?;
+
+        Ok(withdrawable)
+    }
+}
+
+/// Report of how much of the LP supply of a pool is locked
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LockedLiquidity {
+    pub locked: u64,
+    pub lp_supply: u64,
+    /// Locked share of the supply, in basis points
+    pub locked_bps: u16,
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::{mul_div_floor, mul_shr};
+use crate::state::Pool;
+use crate::utils::i_sqrt;
+
+/// Liquidity of a constant product pool held as a position instead of fungible LP tokens.
+/// Whoever holds the position NFT owns it, the LP tokens backing it sit in the position LP
+/// vault of the pool.
+#[account]
+pub struct LpPosition {
+    pub pool: Pubkey,
+    /// Token-2022 mint of the position NFT, its supply is fixed at 1
+    pub position_mint: Pubkey,
+    /// LP tokens the position is worth
+    pub liquidity: u64,
+    pub deposited_a: u64,
+    pub deposited_b: u64,
+    /// Fee growth per LP token of the pool when the position was opened
+    pub fee_growth_entry: u128,
+    pub opened_at: i64,
+    pub bump: u8,
+    /// Separated fee growth of the pool at the last time the fees of this position were
+    /// collected
+    pub fee_growth_last_a: u128,
+    pub fee_growth_last_b: u128,
+    pub fees_collected_a: u64,
+    pub fees_collected_b: u64,
+}
+
+impl LpPosition {
+    // 2 pubkeys + liquidity + 2 deposited amounts + fee growth + timestamp + bump
+    // + 2 separated fee growths + 2 collected amounts
+    pub const MAX_SIZE: usize = 8 + 2 * 32 + 8 + 2 * 8 + 16 + 8 + 1 + 2 * 16 + 2 * 8;
+
+    /// Q64.64 value of an LP token in sqrt(reserve_a * reserve_b). Swap fees left in the
+    /// reserves are the only thing raising it, deposits and withdrawals keep it unchanged.
+    pub fn fee_growth(reserve_a: u64, reserve_b: u64, lp_supply: u64) -> Result<u128> {
+        require!(lp_supply > 0, DEXError::EmptyPool);
+
+        mul_div_floor(
+            i_sqrt(reserve_a as u128 * reserve_b as u128),
+            1 << 64,
+            lp_supply as u128,
+        )
+    }
+
+    /// Separated fees the position earned since they were last collected
+    pub fn uncollected_fees(&self, pool: &Pool) -> Result<(u64, u64)> {
+        // Fee growth is allowed to wrap around, only the difference matters
+        let fees_a = mul_shr(
+            self.liquidity as u128,
+            pool.fee_growth_global_a
+                .wrapping_sub(self.fee_growth_last_a),
+            64,
+        )?;
+        let fees_b = mul_shr(
+            self.liquidity as u128,
+            pool.fee_growth_global_b
+                .wrapping_sub(self.fee_growth_last_b),
+            64,
+        )?;
+
+        Ok((
+            u64::try_from(fees_a).map_err(|_| DEXError::MathOverflow)?,
+            u64::try_from(fees_b).map_err(|_| DEXError::MathOverflow)?,
+        ))
+    }
+
+    /// Takes the uncollected fees of the position, checkpointing the fee growth of the pool
+    pub fn collect_fees(&mut self, pool: &Pool) -> Result<(u64, u64)> {
+        let (fees_a, fees_b) = self.uncollected_fees(pool)?;
+
+        self.fee_growth_last_a = pool.fee_growth_global_a;
+        self.fee_growth_last_b = pool.fee_growth_global_b;
+        self.fees_collected_a = self
+            .fees_collected_a
+            .checked_add(fees_a)
+            .ok_or(DEXError::MathOverflow)?;
+        self.fees_collected_b = self
+            .fees_collected_b
+            .checked_add(fees_b)
+            .ok_or(DEXError::MathOverflow)?;
+
+        Ok((fees_a, fees_b))
+    }
+
+    /// Tokens the position is worth at the given reserves, the part of them earned as swap
+    /// fees compounding into the reserves since it was opened, and its separated fees
+    pub fn value(
+        &self,
+        pool: &Pool,
+        reserve_a: u64,
+        reserve_b: u64,
+        lp_supply: u64,
+    ) -> Result<LpPositionValue> {
+        let fee_growth = Self::fee_growth(reserve_a, reserve_b, lp_supply)?;
+
+        let amount_a = mul_div_floor(self.liquidity as u128, reserve_a as u128, lp_supply as u128)?;
+        let amount_b = mul_div_floor(self.liquidity as u128, reserve_b as u128, lp_supply as u128)?;
+
+        // Share of the position value that grew out of fees, nothing when rounding took the
+        // growth below its entry level
+        let fee_share = fee_growth.saturating_sub(self.fee_growth_entry);
+        let (uncollected_a, uncollected_b) = self.uncollected_fees(pool)?;
+
+        Ok(LpPositionValue {
+            amount_a: amount_a as u64,
+            amount_b: amount_b as u64,
+            fees_a: mul_div_floor(amount_a, fee_share, fee_growth)? as u64,
+            fees_b: mul_div_floor(amount_b, fee_share, fee_growth)? as u64,
+            uncollected_a,
+            uncollected_b,
+            collected_a: self.fees_collected_a,
+            collected_b: self.fees_collected_b,
+        })
+    }
+}
+
+/// Report of what an LP position is worth, fees included
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug)]
+pub struct LpPositionValue {
+    pub amount_a: u64,
+    pub amount_b: u64,
+    /// Part of the amounts earned as fees compounding into the reserves
+    pub fees_a: u64,
+    pub fees_b: u64,
+    /// Separated fees waiting to be collected, on top of the amounts
+    pub uncollected_a: u64,
+    pub uncollected_b: u64,
+    /// Separated fees collected over the life of the position
+    pub collected_a: u64,
+    pub collected_b: u64,
+}
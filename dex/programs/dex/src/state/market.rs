@@ -0,0 +1,196 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor, mul_shr};
+
+pub const MARKET_ORDERS_PER_SIDE: usize = 32;
+
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct MarketOrder {
+    /// Q64.64 token B per token A
+    pub price: u128,
+    pub owner: Pubkey,
+    pub id: u64,
+    /// Input still resting on the book: token A for asks, token B for bids
+    pub amount_in: u64,
+    /// Output of fills waiting to be paid out: token B for asks, token A for bids
+    pub amount_filled: u64,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 8],
+}
+
+impl MarketOrder {
+    /// Token A an ask still sells, or a bid can still buy
+    pub fn amount_a(&self, bid: bool) -> Result<u64> {
+        if !bid {
+            return Ok(self.amount_in);
+        }
+
+        Ok(mul_div_floor(self.amount_in as u128, 1 << 64, self.price)? as u64)
+    }
+
+    /// Fills the order with up to `amount` of the taker input (token A against a bid, token B
+    /// against an ask), returning (input taken, output paid). Nothing is filled when the
+    /// amount is too small to be paid anything.
+    pub fn fill(&mut self, bid: bool, amount: u64) -> Result<(u64, u64)> {
+        let (amount_in, amount_out) = if bid {
+            let amount_a = amount.min(self.amount_a(true)?);
+
+            (amount_a, mul_shr(amount_a as u128, self.price, 64)? as u64)
+        } else {
+            // Rounded up, the maker is never paid less than its price
+            let cost = mul_div_ceil(self.amount_in as u128, self.price, 1 << 64)?;
+
+            if (amount as u128) >= cost {
+                (cost as u64, self.amount_in)
+            } else {
+                (
+                    amount,
+                    mul_div_floor(amount as u128, 1 << 64, self.price)? as u64,
+                )
+            }
+        };
+
+        if amount_out == 0 {
+            return Ok((0, 0));
+        }
+
+        self.amount_in -= amount_out;
+        self.amount_filled = self
+            .amount_filled
+            .checked_add(amount_in)
+            .ok_or(DEXError::MathOverflow)?;
+
+        Ok((amount_in, amount_out))
+    }
+}
+
+/// Central limit order book of a pool. Maker funds sit in the pool vaults, set apart from
+/// the curve reserves by the pool book balances, and `swap_best` routes takers between
+/// the book and the curve.
+#[account(zero_copy)]
+pub struct Market {
+    /// Highest price first, oldest first among equal prices
+    pub bids: [MarketOrder; MARKET_ORDERS_PER_SIDE],
+    /// Lowest price first, oldest first among equal prices
+    pub asks: [MarketOrder; MARKET_ORDERS_PER_SIDE],
+    pub pool: Pubkey,
+    pub bid_count: u64,
+    pub ask_count: u64,
+    pub next_order_id: u64,
+    pub bump: u8,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 7],
+}
+
+impl Market {
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<Market>();
+
+    pub fn orders(&self, bid: bool) -> &[MarketOrder] {
+        if bid {
+            &self.bids[..self.bid_count as usize]
+        } else {
+            &self.asks[..self.ask_count as usize]
+        }
+    }
+
+    pub fn orders_mut(&mut self, bid: bool) -> &mut [MarketOrder] {
+        if bid {
+            &mut self.bids[..self.bid_count as usize]
+        } else {
+            &mut self.asks[..self.ask_count as usize]
+        }
+    }
+
+    /// Index of the best order of a side that can still be filled. Filled orders keep
+    /// their place until their owner closes them.
+    pub fn best_index(&self, bid: bool) -> Result<Option<usize>> {
+        for (index, order) in self.orders(bid).iter().enumerate() {
+            if order.amount_a(bid)? > 0 {
+                return Ok(Some(index));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Stores a new order at its price-time priority, returning its id. Orders crossing the
+    /// other side are rejected, makers only ever rest on the book.
+    pub fn insert(&mut self, owner: Pubkey, bid: bool, price: u128, amount_in: u64) -> Result<u64> {
+        if let Some(index) = self.best_index(!bid)? {
+            let best = self.orders(!bid)[index].price;
+
+            require!(
+                if bid { price < best } else { price > best },
+                DEXError::MarketOrderCrosses
+            );
+        }
+
+        let (orders, count) = if bid {
+            (&mut self.bids, &mut self.bid_count)
+        } else {
+            (&mut self.asks, &mut self.ask_count)
+        };
+        let len = *count as usize;
+
+        require!(len < MARKET_ORDERS_PER_SIDE, DEXError::MarketFull);
+
+        let index = orders[..len]
+            .iter()
+            .position(|order| {
+                if bid {
+                    order.price < price
+                } else {
+                    order.price > price
+                }
+            })
+            .unwrap_or(len);
+
+        let id = self.next_order_id;
+
+        orders.copy_within(index..len, index + 1);
+        orders[index] = MarketOrder {
+            price,
+            owner,
+            id,
+            amount_in,
+            amount_filled: 0,
+            _padding: [0; 8],
+        };
+
+        *count += 1;
+        self.next_order_id += 1;
+
+        Ok(id)
+    }
+
+    /// Takes an order of `owner` off the book, returning its side (true for bids) and state
+    pub fn remove(&mut self, id: u64, owner: &Pubkey) -> Result<(bool, MarketOrder)> {
+        for bid in [true, false] {
+            let Some(index) = self
+                .orders(bid)
+                .iter()
+                .position(|order| order.id == id && order.owner == *owner)
+            else {
+                continue;
+            };
+
+            let (orders, count) = if bid {
+                (&mut self.bids, &mut self.bid_count)
+            } else {
+                (&mut self.asks, &mut self.ask_count)
+            };
+            let len = *count as usize;
+            let order = orders[index];
+
+            orders.copy_within(index + 1..len, index);
+            orders[len - 1] = MarketOrder::default();
+            *count -= 1;
+
+            return Ok((bid, order));
+        }
+
+        err!(DEXError::OrderNotFound)
+    }
+}
@@ -1,2 +1,71 @@
 pub mod pool;
 pub use pool::*;
+
+pub mod dynamic_fee;
+pub use dynamic_fee::*;
+
+pub mod impact_fee;
+pub use impact_fee::*;
+
+pub mod price_oracle;
+pub use price_oracle::*;
+
+pub mod global_config;
+pub use global_config::*;
+
+pub mod trader_stats;
+pub use trader_stats::*;
+
+pub mod referrer;
+pub use referrer::*;
+
+pub mod order_book;
+pub use order_book::*;
+
+pub mod twamm;
+pub use twamm::*;
+
+pub mod dca_order;
+pub use dca_order::*;
+
+pub mod trigger_order;
+pub use trigger_order::*;
+
+pub mod rfq;
+pub use rfq::*;
+
+pub mod intent;
+pub use intent::*;
+
+pub mod batch_auction;
+pub use batch_auction::*;
+
+pub mod market;
+pub use market::*;
+
+pub mod farm;
+pub use farm::*;
+
+pub mod lp_position;
+pub use lp_position::*;
+
+pub mod liquidity_lock;
+pub use liquidity_lock::*;
+
+pub mod concentrated_pool;
+pub use concentrated_pool::*;
+
+pub mod tick_array;
+pub use tick_array::*;
+
+pub mod position;
+pub use position::*;
+
+pub mod bin_pool;
+pub use bin_pool::*;
+
+pub mod bin_array;
+pub use bin_array::*;
+
+pub mod multi_pool;
+pub use multi_pool::*;
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+/// Swap invariant of a multi-asset pool
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MultiPoolCurve {
+    /// Curve's StableSwap invariant for baskets of pegged assets
+    StableSwap { amplification: u64 },
+
+    /// Balancer's weighted invariant, using the `weight_bps` of every token
+    Weighted,
+}
+
+impl MultiPoolCurve {
+    // tag + amplification
+    pub const MAX_SIZE: usize = 1 + 8;
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PoolToken {
+    pub mint: Pubkey,
+    pub vault: Pubkey,
+    /// Balance accounted by the pool, tokens sent straight to the vault are ignored
+    pub balance: u64,
+    pub decimals: u8,
+    /// Weight in bps in weighted pools, 0 in StableSwap pools
+    pub weight_bps: u64,
+}
+
+impl PoolToken {
+    // mint + vault + balance + decimals + weight
+    pub const SIZE: usize = 32 + 32 + 8 + 1 + 8;
+}
+
+/// Pool of 2 up to `MAX_TOKENS` assets sharing a single LP mint. The pool address is
+/// derived from the LP mint, as there is no fixed number of mints to derive it from.
+#[account]
+pub struct MultiPool {
+    /// The account that created the pool
+    pub admin: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fee_bps: u64,
+    pub curve: MultiPoolCurve,
+    /// Ordered by mint
+    pub tokens: Vec<PoolToken>,
+    pub bump: u8,
+}
+
+impl MultiPool {
+    pub const MAX_TOKENS: usize = 8;
+
+    // admin + lp mint + fee + curve + token list + bump
+    pub const MAX_SIZE: usize =
+        8 + 32 + 32 + 8 + MultiPoolCurve::MAX_SIZE + 4 + Self::MAX_TOKENS * PoolToken::SIZE + 1;
+
+    pub fn token_index(&self, mint: &Pubkey) -> Result<usize> {
+        self.tokens
+            .iter()
+            .position(|token| token.mint == *mint)
+            .ok_or_else(|| error!(DEXError::MintNotInPool))
+    }
+}
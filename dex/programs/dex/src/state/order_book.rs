@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::mul_div_ceil;
+
+pub const ORDERS_PER_BOOK: usize = 32;
+
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct LimitOrder {
+    /// Q64.64 minimum amount of output per unit of input
+    pub limit_price: u128,
+    /// Empty slots have the default owner
+    pub owner: Pubkey,
+    pub id: u64,
+    /// Input still escrowed and waiting to be filled
+    pub amount_in: u64,
+    /// Output of fills, waiting to be claimed
+    pub amount_filled: u64,
+    /// 1 when the order sells token A, 0 when it sells token B
+    pub a_to_b: u8,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 7],
+}
+
+impl LimitOrder {
+    pub fn is_empty(&self) -> bool {
+        self.owner == Pubkey::default()
+    }
+
+    pub fn is_a_to_b(&self) -> bool {
+        self.a_to_b == 1
+    }
+
+    /// Least output `amount_in` of the remaining input may be filled for
+    pub fn min_amount_out(&self, amount_in: u64) -> Result<u64> {
+        let min_amount_out = mul_div_ceil(amount_in as u128, self.limit_price, 1 << 64)?;
+
+        u64::try_from(min_amount_out).map_err(|_| error!(DEXError::MathOverflow))
+    }
+}
+
+/// Resting limit orders of a pool. Their input is escrowed in token accounts
+/// owned by this account and filled against the pool once its price allows it.
+#[account(zero_copy)]
+pub struct OrderBook {
+    pub orders: [LimitOrder; ORDERS_PER_BOOK],
+    pub pool: Pubkey,
+    pub escrow_a: Pubkey,
+    pub escrow_b: Pubkey,
+    pub next_order_id: u64,
+    pub bump: u8,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 7],
+}
+
+impl OrderBook {
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<OrderBook>();
+
+    pub fn order_mut(&mut self, id: u64, owner: &Pubkey) -> Result<&mut LimitOrder> {
+        self.orders
+            .iter_mut()
+            .find(|order| !order.is_empty() && order.id == id && order.owner == *owner)
+            .ok_or_else(|| error!(DEXError::OrderNotFound))
+    }
+
+    /// Stores a new order in a free slot, returning its id
+    pub fn insert(
+        &mut self,
+        owner: Pubkey,
+        a_to_b: bool,
+        amount_in: u64,
+        limit_price: u128,
+    ) -> Result<u64> {
+        let id = self.next_order_id;
+
+        let slot = self
+            .orders
+            .iter_mut()
+            .find(|order| order.is_empty())
+            .ok_or(DEXError::OrderBookFull)?;
+
+        *slot = LimitOrder {
+            limit_price,
+            owner,
+            id,
+            amount_in,
+            amount_filled: 0,
+            a_to_b: a_to_b as u8,
+            _padding: [0; 7],
+        };
+
+        self.next_order_id = id.checked_add(1).ok_or(DEXError::MathOverflow)?;
+
+        Ok(id)
+    }
+}
@@ -1,5 +1,61 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::DEXError;
+use crate::math::mul_div_floor;
+use crate::state::{DynamicFee, ImpactFeeConfig, PriceOracle};
+
+/// Swap invariant of a pool, chosen once at `initialize` time
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PoolCurve {
+    /// Classic x * y = k
+    ConstantProduct,
+
+    /// Weighted pool whose weights move linearly from the start pair to the end pair
+    /// between `start_ts` and `end_ts`. Weights are expressed for token A in bps,
+    /// token B always holds the remainder up to 10_000.
+    LiquidityBootstrapping {
+        start_weight_a_bps: u64,
+        end_weight_a_bps: u64,
+        start_ts: i64,
+        end_ts: i64,
+    },
+
+    /// Solidly's x^3 * y + x * y^3 = k for correlated assets, with both reserves
+    /// normalized to the same decimals
+    Stable,
+
+    /// Constant sum at a fixed, admin-updatable rate for wrapped or bridged pairs.
+    /// `rate` is token B per token A (decimals normalized) scaled by `PEG_RATE_SCALE`,
+    /// the caps bound the input swapped in each direction over a slot, all swaps of the
+    /// slot included.
+    Pegged {
+        rate: u64,
+        max_a_to_b: u64,
+        max_b_to_a: u64,
+    },
+}
+
+impl PoolCurve {
+    // tag + the largest variant (2 weights + 2 timestamps)
+    pub const MAX_SIZE: usize = 1 + 2 * 8 + 2 * 8;
+
+    pub fn is_liquidity_bootstrapping(&self) -> bool {
+        matches!(self, PoolCurve::LiquidityBootstrapping { .. })
+    }
+
+    /// Largest input accepted in the given direction over a slot, if the curve limits it
+    pub fn swap_cap(&self, a_to_b: bool) -> Option<u64> {
+        match *self {
+            PoolCurve::Pegged {
+                max_a_to_b,
+                max_b_to_a,
+                ..
+            } => Some(if a_to_b { max_a_to_b } else { max_b_to_a }),
+            _ => None,
+        }
+    }
+}
+
 #[account]
 pub struct Pool {
     pub vault_a: Pubkey,
@@ -7,11 +63,202 @@ pub struct Pool {
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
     pub lp_mint: Pubkey,
-    pub fee_bps: u64,
+    /// Fee on swaps selling token A
+    pub fee_bps_a_to_b: u64,
+    /// Fee on swaps selling token B
+    pub fee_bps_b_to_a: u64,
     pub bump: u8,
+    /// The account that created the pool
+    pub admin: Pubkey,
+    pub curve: PoolCurve,
+    /// Set once the creator pulled the remaining liquidity of a bootstrapping sale
+    pub lbp_finalized: bool,
+    /// Raises the fee above the base fee while the price is volatile, when enabled
+    pub dynamic_fee: Option<DynamicFee>,
+    /// Surcharge on large trades, when enabled
+    pub impact_fee: Option<ImpactFeeConfig>,
+    /// Share of the swap fee, in bps of the fee, paid to the referrer of a swap
+    pub referral_fee_share_bps: u64,
+    /// TWAP of the spot price, recorded after swaps
+    pub price_oracle: PriceOracle,
+    /// Swaps are queued per slot and cleared together by `settle_batch` instead of
+    /// executing right away
+    pub batch_auction: bool,
+    /// Part of each vault held for the order book market: resting maker input and fills
+    /// not paid out yet. It does not back the curve.
+    pub book_a: u64,
+    pub book_b: u64,
+    /// Swap fees are set apart for the LP tokens staked in positions instead of compounding
+    /// into the reserves. Fungible LP tokens earn no swap fees meanwhile.
+    pub separate_fees: bool,
+    /// Q64.64 swap fees set apart per LP token held in positions, over the life of the pool
+    pub fee_growth_global_a: u128,
+    pub fee_growth_global_b: u128,
+    /// Fees set apart but still in the vaults, moved to the fee vaults on collection. They do
+    /// not back the curve.
+    pub fees_a: u64,
+    pub fees_b: u64,
+    /// LP tokens held by open positions
+    pub position_liquidity: u64,
+    /// LP tokens held by liquidity locks
+    pub locked_liquidity: u64,
+    /// Input swapped in each direction during `swap_cap_slot`, counted against the swap
+    /// caps of the curve
+    pub swap_cap_slot: u64,
+    pub swap_cap_used_a_to_b: u64,
+    pub swap_cap_used_b_to_a: u64,
+    /// Long-term orders of the pool, executed before anything else moves the reserves.
+    /// The default key while the pool has no TWAMM.
+    pub twamm: Pubkey,
 }
 
 impl Pool {
-    // 5 pubkeys + fee + bump
-    pub const MAX_SIZE: usize = 8 + 5 * 32 + 8 + 1;
+    // 5 pubkeys + 2 directional fees + bump + admin + curve + finalized flag + dynamic fee + impact fee
+    // + referral share + price oracle + batch auction flag + 2 book balances + fee separation flag
+    // + 2 fee growths + 2 separated fees + position liquidity + locked liquidity
+    // + swap cap slot + 2 swap cap usages + twamm
+    pub const MAX_SIZE: usize = 8
+        + 5 * 32
+        + 2 * 8
+        + 1
+        + 32
+        + PoolCurve::MAX_SIZE
+        + 1
+        + 1
+        + DynamicFee::MAX_SIZE
+        + 1
+        + ImpactFeeConfig::MAX_SIZE
+        + 8
+        + PriceOracle::MAX_SIZE
+        + 1
+        + 2 * 8
+        + 1
+        + 2 * 16
+        + 2 * 8
+        + 8
+        + 8
+        + 8
+        + 2 * 8
+        + 32;
+
+    /// Liquidity of a bootstrapping pool may only be provided by its creator until the sale is finalized
+    pub fn is_creator_only_liquidity(&self) -> bool {
+        self.curve.is_liquidity_bootstrapping() && !self.lbp_finalized
+    }
+
+    /// Static fee of a swap in the given direction
+    pub fn base_fee_bps(&self, a_to_b: bool) -> u64 {
+        if a_to_b {
+            self.fee_bps_a_to_b
+        } else {
+            self.fee_bps_b_to_a
+        }
+    }
+
+    /// Highest of the two directional fees, which fee caps must not undercut
+    pub fn max_base_fee_bps(&self) -> u64 {
+        self.fee_bps_a_to_b.max(self.fee_bps_b_to_a)
+    }
+
+    /// Fee charged for swapping `amount_in` against `reserve_in` in the given direction,
+    /// starting from the spot price `price`
+    pub fn swap_fee_bps(
+        &mut self,
+        a_to_b: bool,
+        price: u128,
+        amount_in: u64,
+        reserve_in: u64,
+        now: i64,
+    ) -> u64 {
+        let base_fee_bps = self.base_fee_bps(a_to_b);
+
+        let fee_bps = match self.dynamic_fee.as_mut() {
+            Some(dynamic_fee) => {
+                dynamic_fee.update_reference(price, now);
+                dynamic_fee.fee_bps(base_fee_bps, dynamic_fee.volatility_at(price))
+            }
+            None => base_fee_bps,
+        };
+
+        match self.impact_fee {
+            Some(impact_fee) => impact_fee.fee_bps(fee_bps, amount_in, reserve_in),
+            None => fee_bps,
+        }
+    }
+
+    /// Counts `amount_in` against the swap cap of the curve in the given direction. Caps are
+    /// a budget per slot, so splitting a swap within a transaction or a slot does not get
+    /// around them.
+    pub fn use_swap_cap(&mut self, a_to_b: bool, amount_in: u64, slot: u64) -> Result<()> {
+        let Some(cap) = self.curve.swap_cap(a_to_b) else {
+            return Ok(());
+        };
+
+        if self.swap_cap_slot != slot {
+            self.swap_cap_slot = slot;
+            self.swap_cap_used_a_to_b = 0;
+            self.swap_cap_used_b_to_a = 0;
+        }
+
+        let used = if a_to_b {
+            &mut self.swap_cap_used_a_to_b
+        } else {
+            &mut self.swap_cap_used_b_to_a
+        };
+        let total = used.checked_add(amount_in).ok_or(DEXError::MathOverflow)?;
+        require!(total <= cap, DEXError::SwapCapExceeded);
+
+        *used = total;
+
+        Ok(())
+    }
+
+    /// Balance of the token A vault, or token B vault, that backs the curve
+    pub fn curve_reserve(&self, token_a: bool, vault_amount: u64) -> u64 {
+        let (book, fees) = if token_a {
+            (self.book_a, self.fees_a)
+        } else {
+            (self.book_b, self.fees_b)
+        };
+
+        vault_amount.saturating_sub(book + fees)
+    }
+
+    /// Sets the whole LP fee of a swap, paid in token A or token B, apart for the LP tokens
+    /// staked in positions when fee separation is on. Returns the amount set apart, which
+    /// does not reach the curve. Without staked LP tokens to earn it, the fee compounds into
+    /// the reserves as usual.
+    pub fn separate_fee(&mut self, token_a: bool, fee_amount: u64) -> Result<u64> {
+        if !self.separate_fees || self.position_liquidity == 0 || fee_amount == 0 {
+            return Ok(0);
+        }
+
+        let growth = mul_div_floor(fee_amount as u128, 1 << 64, self.position_liquidity as u128)?;
+        let (fee_growth_global, fees) = if token_a {
+            (&mut self.fee_growth_global_a, &mut self.fees_a)
+        } else {
+            (&mut self.fee_growth_global_b, &mut self.fees_b)
+        };
+
+        // Fee growth is allowed to wrap around, only differences matter
+        *fee_growth_global = fee_growth_global.wrapping_add(growth);
+        *fees = fees.checked_add(fee_amount).ok_or(DEXError::MathOverflow)?;
+
+        Ok(fee_amount)
+    }
+
+    /// Records the spot price a swap left the reserves at for the TWAP
+    pub fn record_price(&mut self, reserve_a: u64, reserve_b: u64, now: i64) {
+        self.price_oracle
+            .record(Self::spot_price(reserve_a, reserve_b), now);
+    }
+
+    /// Spot price of token A in token B, in Q64.64
+    pub fn spot_price(reserve_a: u64, reserve_b: u64) -> u128 {
+        if reserve_a == 0 {
+            return 0;
+        }
+
+        ((reserve_b as u128) << 64) / reserve_a as u128
+    }
 }
@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::mul_shr;
+
+/// Liquidity owned by a single account over [tick_lower, tick_upper) of a concentrated pool
+#[account]
+pub struct Position {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+    /// Fee growth inside the range at the last time the fees of this position were updated
+    pub fee_growth_inside_last_a: u128,
+    pub fee_growth_inside_last_b: u128,
+    pub tokens_owed_a: u64,
+    pub tokens_owed_b: u64,
+    pub bump: u8,
+}
+
+impl Position {
+    // 2 pubkeys + 2 ticks + liquidity + 2 fee growths + 2 owed amounts + bump
+    pub const MAX_SIZE: usize = 8 + 2 * 32 + 2 * 4 + 16 + 2 * 16 + 2 * 8 + 1;
+
+    /// Credits the fees earned since the last update and checkpoints the fee growth
+    pub fn update_fees(
+        &mut self,
+        fee_growth_inside_a: u128,
+        fee_growth_inside_b: u128,
+    ) -> Result<()> {
+        // Fee growth is allowed to wrap around, only the difference matters
+        let earned_a = mul_shr(
+            self.liquidity,
+            fee_growth_inside_a.wrapping_sub(self.fee_growth_inside_last_a),
+            64,
+        )?;
+        let earned_b = mul_shr(
+            self.liquidity,
+            fee_growth_inside_b.wrapping_sub(self.fee_growth_inside_last_b),
+            64,
+        )?;
+
+        self.tokens_owed_a = self
+            .tokens_owed_a
+            .checked_add(u64::try_from(earned_a).map_err(|_| DEXError::MathOverflow)?)
+            .ok_or(DEXError::MathOverflow)?;
+        self.tokens_owed_b = self
+            .tokens_owed_b
+            .checked_add(u64::try_from(earned_b).map_err(|_| DEXError::MathOverflow)?)
+            .ok_or(DEXError::MathOverflow)?;
+
+        self.fee_growth_inside_last_a = fee_growth_inside_a;
+        self.fee_growth_inside_last_b = fee_growth_inside_b;
+
+        Ok(())
+    }
+}
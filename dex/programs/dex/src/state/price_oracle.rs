@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::TWAP_WINDOW;
+
+/// Time-weighted average of the pool spot price, fed by every swap
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct PriceOracle {
+    /// Spot price left by the last recorded swap, token B per token A in Q64.64
+    pub last_price: u128,
+    /// Sum of spot price * seconds. Wraps around, only differences are meaningful
+    pub price_cumulative: u128,
+    pub last_update_ts: i64,
+    /// Accumulator value and time the current averaging window started at
+    pub window_start_cumulative: u128,
+    pub window_start_ts: i64,
+    /// Average over the last completed window, zero until one completes
+    pub last_window_twap: u128,
+}
+
+impl PriceOracle {
+    // last price + accumulator + timestamp + window start accumulator + window start + last twap
+    pub const MAX_SIZE: usize = 16 + 16 + 8 + 16 + 8 + 16;
+
+    /// Accumulates the previous price up to `now` and records `price` as the new one
+    pub fn record(&mut self, price: u128, now: i64) {
+        if self.last_update_ts == 0 {
+            self.last_price = price;
+            self.last_update_ts = now;
+            self.window_start_ts = now;
+            return;
+        }
+
+        let cumulative = self.cumulative_at(now);
+        let window = now - self.window_start_ts;
+
+        if window >= TWAP_WINDOW {
+            self.last_window_twap =
+                cumulative.wrapping_sub(self.window_start_cumulative) / window as u128;
+            self.window_start_cumulative = cumulative;
+            self.window_start_ts = now;
+        }
+
+        self.price_cumulative = cumulative;
+        self.last_update_ts = self.last_update_ts.max(now);
+        self.last_price = price;
+    }
+
+    /// Average price over at least the last `TWAP_WINDOW` seconds, none until the oracle
+    /// has been recording for that long
+    pub fn twap(&self, now: i64) -> Option<u128> {
+        if self.last_update_ts == 0 {
+            return None;
+        }
+
+        let window = now - self.window_start_ts;
+
+        if window >= TWAP_WINDOW {
+            Some(
+                self.cumulative_at(now)
+                    .wrapping_sub(self.window_start_cumulative)
+                    / window as u128,
+            )
+        } else if self.last_window_twap != 0 {
+            Some(self.last_window_twap)
+        } else {
+            None
+        }
+    }
+
+    fn cumulative_at(&self, now: i64) -> u128 {
+        let elapsed = (now - self.last_update_ts).max(0) as u128;
+
+        self.price_cumulative
+            .wrapping_add(self.last_price.wrapping_mul(elapsed))
+    }
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Wallet the config admin allowed to receive referral fees, at the
+/// `[REFERRER_SEED, owner]` address
+#[account]
+pub struct Referrer {
+    pub owner: Pubkey,
+    pub bump: u8,
+}
+
+impl Referrer {
+    // discriminator + owner + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 1;
+}
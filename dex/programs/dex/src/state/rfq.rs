@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+/// Market maker allowed to fill RFQ quotes, registered by the global config admin
+#[account]
+pub struct MarketMaker {
+    pub maker: Pubkey,
+    pub bump: u8,
+}
+
+impl MarketMaker {
+    // discriminator + maker + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 1;
+}
+
+/// Marks a quote nonce of a market maker as used. It can be closed once the quote expired,
+/// as the quote cannot be filled anymore by then.
+#[account]
+pub struct RfqNonce {
+    /// The taker that filled the quote and paid the rent
+    pub payer: Pubkey,
+    pub expiry: i64,
+    pub bump: u8,
+}
+
+impl RfqNonce {
+    // discriminator + payer + expiry + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 8 + 1;
+}
+
+/// Firm quote of a market maker, who signs its Borsh serialization with ed25519
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RfqQuote {
+    pub maker: Pubkey,
+    /// Token the taker sells to the maker
+    pub mint_in: Pubkey,
+    /// Token the maker sells to the taker
+    pub mint_out: Pubkey,
+    /// Most input the maker takes at this price, smaller fills get a pro-rata output
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub expiry: i64,
+    pub nonce: u64,
+}
+
+impl RfqQuote {
+    /// Output the maker owes for `fill_amount` of input, rounded down
+    pub fn amount_out_for(&self, fill_amount: u64) -> u64 {
+        ((fill_amount as u128) * (self.amount_out as u128) / (self.amount_in as u128)) as u64
+    }
+}
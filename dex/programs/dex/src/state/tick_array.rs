@@ -0,0 +1,176 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+
+pub const TICK_ARRAY_SIZE: usize = 64;
+
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct Tick {
+    /// Liquidity added when the price crosses this tick upwards (removed downwards)
+    pub liquidity_net: i128,
+    /// Total liquidity referencing this tick, a tick is initialized while this is non-zero
+    pub liquidity_gross: u128,
+    /// Q64.64 fee growth on the other side of this tick relative to the current price
+    pub fee_growth_outside_a: u128,
+    pub fee_growth_outside_b: u128,
+}
+
+impl Tick {
+    pub fn is_initialized(&self) -> bool {
+        self.liquidity_gross != 0
+    }
+
+    /// Applies a liquidity change of a position bounded by this tick. A tick that becomes
+    /// initialized assumes all fees so far were earned below it when it is under the current price.
+    /// Ticks left with no liquidity are not cleared here so fees can still be computed with them.
+    pub fn update(
+        &mut self,
+        tick_index: i32,
+        tick_current: i32,
+        liquidity_delta: i128,
+        fee_growth_global_a: u128,
+        fee_growth_global_b: u128,
+        is_upper: bool,
+    ) -> Result<()> {
+        let liquidity_gross = self
+            .liquidity_gross
+            .checked_add_signed(liquidity_delta)
+            .ok_or(DEXError::MathOverflow)?;
+
+        if !self.is_initialized() && tick_index <= tick_current {
+            self.fee_growth_outside_a = fee_growth_global_a;
+            self.fee_growth_outside_b = fee_growth_global_b;
+        }
+
+        self.liquidity_gross = liquidity_gross;
+        self.liquidity_net = if is_upper {
+            self.liquidity_net.checked_sub(liquidity_delta)
+        } else {
+            self.liquidity_net.checked_add(liquidity_delta)
+        }
+        .ok_or(DEXError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Flips the fee growth outside of the tick when the price moves across it
+    pub fn cross(&mut self, fee_growth_global_a: u128, fee_growth_global_b: u128) {
+        self.fee_growth_outside_a = fee_growth_global_a.wrapping_sub(self.fee_growth_outside_a);
+        self.fee_growth_outside_b = fee_growth_global_b.wrapping_sub(self.fee_growth_outside_b);
+    }
+}
+
+/// Fee growth per unit of liquidity earned inside [tick_lower, tick_upper)
+pub fn fee_growth_inside(
+    lower: &Tick,
+    upper: &Tick,
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_current: i32,
+    fee_growth_global_a: u128,
+    fee_growth_global_b: u128,
+) -> (u128, u128) {
+    let (below_a, below_b) = if tick_current >= tick_lower {
+        (lower.fee_growth_outside_a, lower.fee_growth_outside_b)
+    } else {
+        (
+            fee_growth_global_a.wrapping_sub(lower.fee_growth_outside_a),
+            fee_growth_global_b.wrapping_sub(lower.fee_growth_outside_b),
+        )
+    };
+
+    let (above_a, above_b) = if tick_current < tick_upper {
+        (upper.fee_growth_outside_a, upper.fee_growth_outside_b)
+    } else {
+        (
+            fee_growth_global_a.wrapping_sub(upper.fee_growth_outside_a),
+            fee_growth_global_b.wrapping_sub(upper.fee_growth_outside_b),
+        )
+    };
+
+    (
+        fee_growth_global_a
+            .wrapping_sub(below_a)
+            .wrapping_sub(above_a),
+        fee_growth_global_b
+            .wrapping_sub(below_b)
+            .wrapping_sub(above_b),
+    )
+}
+
+/// A fixed window of `TICK_ARRAY_SIZE` ticks, `tick_spacing` apart, starting at `start_tick_index`
+#[account(zero_copy)]
+pub struct TickArray {
+    pub ticks: [Tick; TICK_ARRAY_SIZE],
+    pub pool: Pubkey,
+    pub start_tick_index: i32,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 12],
+}
+
+impl TickArray {
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<TickArray>();
+
+    /// Number of ticks covered by a single array
+    pub fn ticks_in_array(tick_spacing: u16) -> i32 {
+        TICK_ARRAY_SIZE as i32 * tick_spacing as i32
+    }
+
+    /// Start index of the array containing `tick_index`
+    pub fn start_index_for(tick_index: i32, tick_spacing: u16) -> i32 {
+        let span = Self::ticks_in_array(tick_spacing);
+        tick_index.div_euclid(span) * span
+    }
+
+    pub fn contains(&self, tick_index: i32, tick_spacing: u16) -> bool {
+        tick_index >= self.start_tick_index
+            && tick_index < self.start_tick_index + Self::ticks_in_array(tick_spacing)
+    }
+
+    fn offset(&self, tick_index: i32, tick_spacing: u16) -> Result<usize> {
+        require!(
+            self.contains(tick_index, tick_spacing)
+                && tick_index.rem_euclid(tick_spacing as i32) == 0,
+            DEXError::InvalidTickIndex
+        );
+
+        Ok(((tick_index - self.start_tick_index) / tick_spacing as i32) as usize)
+    }
+
+    pub fn get_tick(&self, tick_index: i32, tick_spacing: u16) -> Result<Tick> {
+        Ok(self.ticks[self.offset(tick_index, tick_spacing)?])
+    }
+
+    pub fn set_tick(&mut self, tick_index: i32, tick_spacing: u16, tick: Tick) -> Result<()> {
+        let offset = self.offset(tick_index, tick_spacing)?;
+        self.ticks[offset] = tick;
+
+        Ok(())
+    }
+
+    /// Closest initialized tick at or below `tick_index` (price moving down)
+    /// or strictly above it (price moving up) inside this array
+    pub fn next_initialized_tick(
+        &self,
+        tick_index: i32,
+        tick_spacing: u16,
+        a_to_b: bool,
+    ) -> Option<i32> {
+        let spacing = tick_spacing as i32;
+        let position = (tick_index - self.start_tick_index).div_euclid(spacing);
+
+        if a_to_b {
+            let from = position.min(TICK_ARRAY_SIZE as i32 - 1);
+            (0..=from)
+                .rev()
+                .find(|i| self.ticks[*i as usize].is_initialized())
+                .map(|i| self.start_tick_index + i * spacing)
+        } else {
+            let from = (position + 1).max(0);
+            (from..TICK_ARRAY_SIZE as i32)
+                .find(|i| self.ticks[*i as usize].is_initialized())
+                .map(|i| self.start_tick_index + i * spacing)
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{SECONDS_PER_DAY, VOLUME_WINDOW_DAYS};
+use crate::errors::DEXError;
+use crate::math::{mul_div_floor, ONE};
+
+/// Rolling swap volume of a trader on a pool, counted in token A in the 18-decimal fixed
+/// point of `ONE` so that the tiers of the global config mean the same on every pool
+#[account]
+pub struct TraderStats {
+    pub trader: Pubkey,
+    pub pool: Pubkey,
+    /// Volume per day, indexed by the day number modulo the window length
+    pub daily_volume: [u128; VOLUME_WINDOW_DAYS],
+    /// Day number of the last recorded swap
+    pub last_day: i64,
+    pub bump: u8,
+}
+
+impl TraderStats {
+    // discriminator + trader + pool + daily volumes + last day + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + VOLUME_WINDOW_DAYS * 16 + 8 + 1;
+
+    /// Volume over the window ending at `now`
+    pub fn volume_at(&self, now: i64) -> u128 {
+        let mut stats = self.clone();
+        stats.roll(now.div_euclid(SECONDS_PER_DAY));
+
+        stats
+            .daily_volume
+            .iter()
+            .fold(0u128, |total, volume| total.saturating_add(*volume))
+    }
+
+    /// Brings `amount` of a token with `decimals` to the unit volume is counted in
+    pub fn normalized_volume(amount: u64, decimals: u8) -> Result<u128> {
+        let scale = 10u128
+            .checked_pow(decimals as u32)
+            .ok_or(DEXError::MathOverflow)?;
+
+        mul_div_floor(amount as u128, ONE, scale)
+    }
+
+    pub fn record_swap(&mut self, volume: u128, now: i64) {
+        let today = now.div_euclid(SECONDS_PER_DAY);
+        self.roll(today);
+
+        let bucket = &mut self.daily_volume[Self::bucket(today)];
+        *bucket = bucket.saturating_add(volume);
+    }
+
+    /// Clears the buckets of the days that left the window since the last swap
+    fn roll(&mut self, today: i64) {
+        let elapsed = today - self.last_day;
+        if elapsed <= 0 {
+            return;
+        }
+
+        if elapsed >= VOLUME_WINDOW_DAYS as i64 {
+            self.daily_volume = [0; VOLUME_WINDOW_DAYS];
+        } else {
+            for day in self.last_day + 1..=today {
+                self.daily_volume[Self::bucket(day)] = 0;
+            }
+        }
+
+        self.last_day = today;
+    }
+
+    fn bucket(day: i64) -> usize {
+        day.rem_euclid(VOLUME_WINDOW_DAYS as i64) as usize
+    }
+}
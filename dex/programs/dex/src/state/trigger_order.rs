@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriggerPriceSource {
+    /// Current pool spot price
+    Spot,
+    /// Pool TWAP, harder to push past the trigger for a single transaction
+    Twap,
+}
+
+/// Escrowed input swapped out through the pool by a keeper once the pool price crosses
+/// the trigger price. Stop-losses and take-profits differ only in the crossing direction.
+#[account]
+pub struct TriggerOrder {
+    pub owner: Pubkey,
+    pub pool: Pubkey,
+    pub mint_in: Pubkey,
+    pub mint_out: Pubkey,
+    /// Token account holding the input, owned by the order
+    pub escrow: Pubkey,
+    pub id: u64,
+    /// Deposited input
+    pub amount_in: u64,
+    pub min_amount_out: u64,
+    /// Token B per token A in Q64.64, whichever token is sold
+    pub trigger_price: u128,
+    /// Fires once the price is at or above the trigger price, otherwise at or below it
+    pub trigger_above: bool,
+    pub price_source: TriggerPriceSource,
+    pub bump: u8,
+}
+
+impl TriggerOrder {
+    // discriminator + owner + pool + 2 mints + escrow + id + amount + min out + trigger price
+    // + direction + price source + bump
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 32 + 32 + 32 + 8 + 8 + 8 + 16 + 1 + 1 + 1;
+
+    pub fn is_triggered(&self, price: u128) -> bool {
+        if self.trigger_above {
+            price >= self.trigger_price
+        } else {
+            price <= self.trigger_price
+        }
+    }
+}
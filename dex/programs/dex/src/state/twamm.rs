@@ -0,0 +1,268 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::DEXError;
+use crate::math::{mul_div_ceil, mul_div_floor, mul_shr};
+
+pub const MAX_TWAMM_EXPIRIES: usize = 32;
+// Orders end at most this many order intervals ahead, so the expiries still to be crossed
+// never take more than half of the slots
+pub const MAX_LONG_TERM_ORDER_INTERVALS: i64 = (MAX_TWAMM_EXPIRIES / 2) as i64;
+
+/// Long-term orders selling the same token, executed as one
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct VirtualOrderPool {
+    /// Q64.64 tokens sold per second by all active orders
+    pub sell_rate: u128,
+    /// Output earned per unit of sell rate, in Q128 of the Q64.64 rate. Wraps around,
+    /// only differences are meaningful
+    pub earnings_per_rate: u128,
+}
+
+impl VirtualOrderPool {
+    /// Spreads `amount_out` over the active orders
+    pub fn distribute(&mut self, amount_out: u64) -> Result<()> {
+        if self.sell_rate == 0 || amount_out == 0 {
+            return Ok(());
+        }
+
+        let earnings = mul_div_floor((amount_out as u128) << 64, 1 << 64, self.sell_rate)?;
+        self.earnings_per_rate = self.earnings_per_rate.wrapping_add(earnings);
+
+        Ok(())
+    }
+}
+
+/// Sell rates of the orders ending at `ts`, and the earnings when the execution crossed it
+#[zero_copy]
+#[derive(Default, Debug)]
+pub struct OrderExpiry {
+    pub sell_rate_a_to_b: u128,
+    pub sell_rate_b_to_a: u128,
+    pub earnings_per_rate_a_to_b: u128,
+    pub earnings_per_rate_b_to_a: u128,
+    pub ts: i64,
+    /// Orders still referencing this expiry, the slot is free at zero
+    pub order_count: u32,
+    /// 1 once the execution went past `ts`
+    pub crossed: u8,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 3],
+}
+
+/// Time-weighted long-term orders of a pool. Sold input is escrowed in token
+/// accounts owned by this account, along with the proceeds until withdrawn.
+#[account(zero_copy)]
+pub struct Twamm {
+    pub a_to_b: VirtualOrderPool,
+    pub b_to_a: VirtualOrderPool,
+    pub expiries: [OrderExpiry; MAX_TWAMM_EXPIRIES],
+    pub pool: Pubkey,
+    pub escrow_a: Pubkey,
+    pub escrow_b: Pubkey,
+    /// Orders are executed up to this time
+    pub last_execution_ts: i64,
+    /// Orders end on multiples of this many seconds
+    pub order_interval: i64,
+    pub bump: u8,
+    // keeps the struct free of implicit padding
+    pub _padding: [u8; 15],
+}
+
+impl Twamm {
+    pub const MAX_SIZE: usize = 8 + std::mem::size_of::<Twamm>();
+
+    pub fn virtual_pool(&self, a_to_b: bool) -> &VirtualOrderPool {
+        if a_to_b {
+            &self.a_to_b
+        } else {
+            &self.b_to_a
+        }
+    }
+
+    pub fn virtual_pool_mut(&mut self, a_to_b: bool) -> &mut VirtualOrderPool {
+        if a_to_b {
+            &mut self.a_to_b
+        } else {
+            &mut self.b_to_a
+        }
+    }
+
+    /// The earliest expiry still to be crossed that is due by `now`
+    pub fn next_expiry(&self, now: i64) -> Option<usize> {
+        self.expiries
+            .iter()
+            .enumerate()
+            .filter(|(_, expiry)| expiry.order_count > 0 && expiry.crossed == 0 && expiry.ts <= now)
+            .min_by_key(|(_, expiry)| expiry.ts)
+            .map(|(index, _)| index)
+    }
+
+    /// Stops the orders ending at the expiry, keeping the earnings they are owed
+    pub fn cross_expiry(&mut self, index: usize) {
+        let (earnings_a_to_b, earnings_b_to_a) =
+            (self.a_to_b.earnings_per_rate, self.b_to_a.earnings_per_rate);
+        let expiry = &mut self.expiries[index];
+
+        self.a_to_b.sell_rate -= expiry.sell_rate_a_to_b;
+        self.b_to_a.sell_rate -= expiry.sell_rate_b_to_a;
+
+        expiry.earnings_per_rate_a_to_b = earnings_a_to_b;
+        expiry.earnings_per_rate_b_to_a = earnings_b_to_a;
+        expiry.crossed = 1;
+    }
+
+    pub fn expiry(&self, ts: i64) -> Result<&OrderExpiry> {
+        self.expiries
+            .iter()
+            .find(|expiry| expiry.order_count > 0 && expiry.ts == ts)
+            .ok_or_else(|| error!(DEXError::InvalidLongTermOrder))
+    }
+
+    pub fn expiry_mut(&mut self, ts: i64) -> Result<&mut OrderExpiry> {
+        self.expiries
+            .iter_mut()
+            .find(|expiry| expiry.order_count > 0 && expiry.ts == ts)
+            .ok_or_else(|| error!(DEXError::InvalidLongTermOrder))
+    }
+
+    pub fn is_expired(&self, order: &LongTermOrder) -> bool {
+        order.end_ts <= self.last_execution_ts
+    }
+
+    /// Earnings per rate reached by the virtual pool of `order`, frozen at its expiry once crossed
+    pub fn order_earnings(&self, order: &LongTermOrder) -> Result<u128> {
+        if !self.is_expired(order) {
+            return Ok(self.virtual_pool(order.a_to_b).earnings_per_rate);
+        }
+
+        let expiry = self.expiry(order.end_ts)?;
+
+        Ok(if order.a_to_b {
+            expiry.earnings_per_rate_a_to_b
+        } else {
+            expiry.earnings_per_rate_b_to_a
+        })
+    }
+
+    /// Takes `order` out of the TWAMM, stopping it if it is still selling. Returns the
+    /// proceeds it has not withdrawn yet and its unsold input.
+    pub fn remove_order(&mut self, order: &LongTermOrder) -> Result<(u64, u64)> {
+        let proceeds = order.proceeds(self.order_earnings(order)?)?;
+        let unsold = order.unsold_amount(self.last_execution_ts)?;
+        let active = !self.is_expired(order);
+
+        let expiry = self.expiry_mut(order.end_ts)?;
+        expiry.order_count -= 1;
+
+        if active {
+            if order.a_to_b {
+                expiry.sell_rate_a_to_b -= order.sell_rate;
+            } else {
+                expiry.sell_rate_b_to_a -= order.sell_rate;
+            }
+
+            self.virtual_pool_mut(order.a_to_b).sell_rate -= order.sell_rate;
+        }
+
+        Ok((proceeds, unsold))
+    }
+
+    /// First multiple of the order interval at or after `now + duration`. Rejects orders
+    /// ending more than `MAX_LONG_TERM_ORDER_INTERVALS` intervals ahead.
+    pub fn order_end_ts(&self, now: i64, duration: i64) -> Result<i64> {
+        let end_ts = now
+            .checked_add(duration)
+            .and_then(|ts| ts.checked_add(self.order_interval - 1))
+            .ok_or(DEXError::MathOverflow)?
+            .div_euclid(self.order_interval)
+            * self.order_interval;
+
+        require!(
+            end_ts - now <= MAX_LONG_TERM_ORDER_INTERVALS * self.order_interval,
+            DEXError::InvalidLongTermOrder
+        );
+
+        Ok(end_ts)
+    }
+
+    /// Starts selling at `sell_rate` until `end_ts`
+    pub fn add_order(&mut self, a_to_b: bool, sell_rate: u128, end_ts: i64) -> Result<()> {
+        let index = match self
+            .expiries
+            .iter()
+            .position(|expiry| expiry.order_count > 0 && expiry.ts == end_ts)
+        {
+            Some(index) => index,
+            None => {
+                let index = self
+                    .expiries
+                    .iter()
+                    .position(|expiry| expiry.order_count == 0)
+                    .ok_or(DEXError::TwammExpiriesFull)?;
+
+                self.expiries[index] = OrderExpiry {
+                    ts: end_ts,
+                    ..Default::default()
+                };
+                index
+            }
+        };
+
+        let expiry = &mut self.expiries[index];
+        expiry.order_count += 1;
+        if a_to_b {
+            expiry.sell_rate_a_to_b += sell_rate;
+        } else {
+            expiry.sell_rate_b_to_a += sell_rate;
+        }
+
+        let virtual_pool = self.virtual_pool_mut(a_to_b);
+        virtual_pool.sell_rate = virtual_pool
+            .sell_rate
+            .checked_add(sell_rate)
+            .ok_or(DEXError::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+#[account]
+pub struct LongTermOrder {
+    pub twamm: Pubkey,
+    pub owner: Pubkey,
+    pub a_to_b: bool,
+    /// Deposited input
+    pub amount_in: u64,
+    /// Q64.64 tokens sold per second
+    pub sell_rate: u128,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    /// Earnings per rate of the virtual pool the proceeds were last paid out at
+    pub earnings_snapshot: u128,
+}
+
+impl LongTermOrder {
+    // discriminator + twamm + owner + direction + amount + rate + 2 timestamps + snapshot
+    pub const MAX_SIZE: usize = 8 + 32 + 32 + 1 + 8 + 16 + 8 + 8 + 16;
+
+    /// Proceeds earned since the snapshot, up to the `earnings_per_rate` reached
+    pub fn proceeds(&self, earnings_per_rate: u128) -> Result<u64> {
+        let proceeds = mul_shr(
+            self.sell_rate,
+            earnings_per_rate.wrapping_sub(self.earnings_snapshot),
+            128,
+        )?;
+
+        u64::try_from(proceeds).map_err(|_| error!(DEXError::MathOverflow))
+    }
+
+    /// Input not sold by `now`, rounded down so that refunds never exceed what the
+    /// escrow still holds
+    pub fn unsold_amount(&self, now: i64) -> Result<u64> {
+        let elapsed = (now.clamp(self.start_ts, self.end_ts) - self.start_ts) as u128;
+        let sold = mul_div_ceil(self.sell_rate, elapsed, 1 << 64)?;
+
+        Ok((self.amount_in as u128).saturating_sub(sold) as u64)
+    }
+}
@@ -1,7 +1,28 @@
 use anchor_lang::{prelude::*, Key};
-use anchor_spl::token_interface::Mint;
+use anchor_spl::{
+    token::{close_account, transfer, CloseAccount, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
 
-use crate::{constants::LIQUIDITY_POOL_SEED, errors::DEXError};
+use solana_instructions_sysvar::get_instruction_relative;
+use solana_sdk_ids::ed25519_program;
+
+use crate::{
+    constants::{
+        BATCH_AUCTION_SEED, BIN_POOL_SEED, CONCENTRATED_POOL_SEED, DCA_ORDER_SEED, FARM_SEED,
+        INTENT_AUTHORITY_SEED, KEEPER_TIP_BPS, LIQUIDITY_POOL_SEED, MAX_ORDER_FILLS_PER_CALL,
+        MULTI_POOL_SEED, ORDER_BOOK_SEED, PARTIAL_FILL_SEARCH_STEPS, RFQ_AUTHORITY_SEED,
+        TRIGGER_ORDER_SEED, TWAMM_SEED,
+    },
+    errors::DEXError,
+    events::LimitOrderFilledEvent,
+    instructions::exchange_tokens::{quote_swap, SwapQuote},
+    math::{mul_div_floor, mul_shr},
+    state::{
+        fee_growth_inside, ConcentratedPool, LimitOrder, MultiPool, OrderBook, Pool, Position,
+        TickArray, Twamm,
+    },
+};
 
 pub fn i_sqrt(n: u128) -> u128 {
     if n < 2 {
@@ -10,7 +31,7 @@ pub fn i_sqrt(n: u128) -> u128 {
 
     // A good initial guess is 2^(bits/2)
     // For u128, we use bit-shifting to get a ballpark figure
-    let mut x = 1u128 << ((128 - n.leading_zeros() + 1) / 2);
+    let mut x = 1u128 << (128 - n.leading_zeros()).div_ceil(2);
 
     loop {
         let y = (x + n / x) >> 1; // Standard Newton: (x + n/x) / 2
@@ -47,6 +68,1022 @@ pub fn get_pool_signer_seeds<'a>(
     ]
 }
 
+pub fn get_concentrated_pool_signer_seeds<'a>(
+    mint_a_key: &'a Pubkey,
+    mint_b_key: &'a Pubkey,
+    bump: &'a u8,
+) -> [&'a [u8]; 4] {
+    [
+        CONCENTRATED_POOL_SEED,
+        mint_a_key.as_ref(),
+        mint_b_key.as_ref(),
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_bin_pool_signer_seeds<'a>(
+    mint_a_key: &'a Pubkey,
+    mint_b_key: &'a Pubkey,
+    bump: &'a u8,
+) -> [&'a [u8]; 4] {
+    [
+        BIN_POOL_SEED,
+        mint_a_key.as_ref(),
+        mint_b_key.as_ref(),
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_multi_pool_signer_seeds<'a>(lp_mint_key: &'a Pubkey, bump: &'a u8) -> [&'a [u8]; 3] {
+    [
+        MULTI_POOL_SEED,
+        lp_mint_key.as_ref(),
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_order_book_signer_seeds<'a>(pool_key: &'a Pubkey, bump: &'a u8) -> [&'a [u8]; 3] {
+    [
+        ORDER_BOOK_SEED,
+        pool_key.as_ref(),
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_twamm_signer_seeds<'a>(pool_key: &'a Pubkey, bump: &'a u8) -> [&'a [u8]; 3] {
+    [TWAMM_SEED, pool_key.as_ref(), std::slice::from_ref(bump)]
+}
+
+pub fn get_batch_auction_signer_seeds<'a>(pool_key: &'a Pubkey, bump: &'a u8) -> [&'a [u8]; 3] {
+    [
+        BATCH_AUCTION_SEED,
+        pool_key.as_ref(),
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_farm_signer_seeds<'a>(pool_key: &'a Pubkey, bump: &'a u8) -> [&'a [u8]; 3] {
+    [FARM_SEED, pool_key.as_ref(), std::slice::from_ref(bump)]
+}
+
+pub fn get_dca_order_signer_seeds<'a>(
+    pool_key: &'a Pubkey,
+    owner_key: &'a Pubkey,
+    id_bytes: &'a [u8; 8],
+    bump: &'a u8,
+) -> [&'a [u8]; 5] {
+    [
+        DCA_ORDER_SEED,
+        pool_key.as_ref(),
+        owner_key.as_ref(),
+        id_bytes,
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_trigger_order_signer_seeds<'a>(
+    pool_key: &'a Pubkey,
+    owner_key: &'a Pubkey,
+    id_bytes: &'a [u8; 8],
+    bump: &'a u8,
+) -> [&'a [u8]; 5] {
+    [
+        TRIGGER_ORDER_SEED,
+        pool_key.as_ref(),
+        owner_key.as_ref(),
+        id_bytes,
+        std::slice::from_ref(bump),
+    ]
+}
+
+pub fn get_rfq_authority_signer_seeds(bump: &u8) -> [&[u8]; 2] {
+    [RFQ_AUTHORITY_SEED, std::slice::from_ref(bump)]
+}
+
+pub fn get_intent_authority_signer_seeds(bump: &u8) -> [&[u8]; 2] {
+    [INTENT_AUTHORITY_SEED, std::slice::from_ref(bump)]
+}
+
+/// Checks that the instruction right before the current one is an Ed25519 program
+/// instruction verifying the signature of `signer` over `message`. The runtime fails the
+/// transaction when that signature is invalid, so only its inputs need checking here.
+pub fn verify_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    // Signature count and padding, then one offsets entry per signature
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    // Instruction index meaning "this instruction" in the offsets
+    const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+    let instruction = get_instruction_relative(-1, instructions_sysvar)
+        .map_err(|_| error!(DEXError::InvalidSignature))?;
+
+    require_keys_eq!(
+        instruction.program_id,
+        ed25519_program::ID,
+        DEXError::InvalidSignature
+    );
+
+    let data = &instruction.data;
+    require!(
+        data.len() >= OFFSETS_START + OFFSETS_SIZE && data[0] == 1,
+        DEXError::InvalidSignature
+    );
+
+    let offsets: Vec<u16> = data[OFFSETS_START..OFFSETS_START + OFFSETS_SIZE]
+        .chunks_exact(2)
+        .map(|bytes| u16::from_le_bytes([bytes[0], bytes[1]]))
+        .collect();
+    let (signature_index, public_key_offset, public_key_index) =
+        (offsets[1], offsets[2] as usize, offsets[3]);
+    let (message_offset, message_size, message_index) =
+        (offsets[4] as usize, offsets[5] as usize, offsets[6]);
+
+    require!(
+        signature_index == CURRENT_INSTRUCTION
+            && public_key_index == CURRENT_INSTRUCTION
+            && message_index == CURRENT_INSTRUCTION,
+        DEXError::InvalidSignature
+    );
+
+    let public_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(DEXError::InvalidSignature)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(DEXError::InvalidSignature)?;
+
+    require!(
+        public_key == signer.as_ref() && signed_message == message,
+        DEXError::InvalidSignature
+    );
+
+    Ok(())
+}
+
+/// Part of a keeper-executed swap of `amount` paid to the keeper
+pub fn keeper_tip(amount: u64) -> Result<u64> {
+    Ok((amount as u128)
+        .checked_mul(KEEPER_TIP_BPS as u128)
+        .ok_or(DEXError::MathOverflow)?
+        .checked_div(10_000)
+        .ok_or(DEXError::MathOverflow)? as u64)
+}
+
+/// Moves a keeper-executed swap of an order: `amount_in` and the `keeper_tip` leave the
+/// order escrow for the pool vault and the keeper, and `amount_out` leaves the pool for the
+/// order owner. Token accounts are given as (vault in, vault out) and (keeper, owner).
+pub fn settle_escrowed_swap<'info>(
+    pool: &Account<'info, Pool>,
+    (order, order_seeds): (&AccountInfo<'info>, &[&[u8]]),
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    (vault_in, vault_out): (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    (keeper_token_account, owner_token_account_out): (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    token_program: &AccountInfo<'info>,
+    (amount_in, keeper_tip, amount_out): (u64, u64, u64),
+) -> Result<()> {
+    let pool_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+    let transfers = [
+        (escrow, vault_in, order.clone(), order_seeds, amount_in),
+        (
+            escrow,
+            keeper_token_account,
+            order.clone(),
+            order_seeds,
+            keeper_tip,
+        ),
+        (
+            vault_out,
+            owner_token_account_out,
+            pool.to_account_info(),
+            pool_seeds.as_slice(),
+            amount_out,
+        ),
+    ];
+
+    for (from, to, authority, seeds, amount) in transfers {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority,
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Closes an order escrow, the rent going to the owner. A `refund` of what is left in
+/// the escrow is first paid to the given owner token account.
+pub fn close_order_escrow<'info>(
+    (order, order_seeds): (&AccountInfo<'info>, &[&[u8]]),
+    escrow: &InterfaceAccount<'info, TokenAccount>,
+    owner: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    refund: Option<(&AccountInfo<'info>, u64)>,
+) -> Result<()> {
+    if let Some((owner_token_account, refund)) = refund.filter(|(_, amount)| *amount > 0) {
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: escrow.to_account_info(),
+                    to: owner_token_account.clone(),
+                    authority: order.clone(),
+                },
+                &[order_seeds],
+            ),
+            refund,
+        )?;
+    }
+
+    close_account(CpiContext::new_with_signer(
+        token_program.clone(),
+        CloseAccount {
+            account: escrow.to_account_info(),
+            destination: owner.clone(),
+            authority: order.clone(),
+        },
+        &[order_seeds],
+    ))
+}
+
+/// Executes the long-term orders of the pool TWAMM up to `now`, so instructions moving the
+/// reserves never price against a pool the orders should already have traded with. Pools
+/// with a TWAMM require it along with its escrows, given as (escrow A, escrow B).
+/// Vaults are given as (token A, token B) and reloaded afterwards.
+pub fn settle_twamm<'info>(
+    pool: &mut Account<'info, Pool>,
+    twamm: Option<&AccountLoader<'info, Twamm>>,
+    escrows: (
+        Option<&InterfaceAccount<'info, TokenAccount>>,
+        Option<&InterfaceAccount<'info, TokenAccount>>,
+    ),
+    vaults: (
+        &mut InterfaceAccount<'info, TokenAccount>,
+        &mut InterfaceAccount<'info, TokenAccount>,
+    ),
+    token_program: &AccountInfo<'info>,
+    decimals: (u8, u8),
+    now: i64,
+) -> Result<()> {
+    if pool.twamm == Pubkey::default() {
+        return Ok(());
+    }
+
+    let (Some(twamm), (Some(escrow_a), Some(escrow_b))) = (twamm, escrows) else {
+        return err!(DEXError::InvalidTwamm);
+    };
+    require_keys_eq!(twamm.key(), pool.twamm, DEXError::InvalidTwamm);
+
+    execute_twamm(
+        pool,
+        twamm,
+        (&*vaults.0, &*vaults.1),
+        (escrow_a, escrow_b),
+        token_program,
+        decimals,
+        now,
+    )?;
+
+    vaults.0.reload()?;
+    vaults.1.reload()
+}
+
+/// Executes the long-term orders of `twamm` up to `now`, one segment per order expiry.
+/// Over a segment both sides are matched with each other at the pool price and only the
+/// excess is swapped against the pool. Vaults and escrows are given as (token A, token B)
+/// and the vaults must hold their current balances.
+pub fn execute_twamm<'info>(
+    pool: &mut Account<'info, Pool>,
+    twamm: &AccountLoader<'info, Twamm>,
+    vaults: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    escrows: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    token_program: &AccountInfo<'info>,
+    decimals: (u8, u8),
+    now: i64,
+) -> Result<()> {
+    require_keys_eq!(vaults.0.key(), pool.vault_a, DEXError::InvalidTwamm);
+    require_keys_eq!(vaults.1.key(), pool.vault_b, DEXError::InvalidTwamm);
+
+    let mut reserves = (
+        pool.curve_reserve(true, vaults.0.amount),
+        pool.curve_reserve(false, vaults.1.amount),
+    );
+
+    // The TWAMM signs the escrow transfers, so it is released before moving funds
+    let (flows, bump) = {
+        let mut twamm = twamm.load_mut()?;
+
+        require_keys_eq!(twamm.pool, pool.key(), DEXError::InvalidTwamm);
+        require_keys_eq!(escrows.0.key(), twamm.escrow_a, DEXError::InvalidTwamm);
+        require_keys_eq!(escrows.1.key(), twamm.escrow_b, DEXError::InvalidTwamm);
+
+        let flows = run_twamm(
+            pool,
+            &mut twamm,
+            &mut reserves,
+            decimals,
+            now,
+            Clock::get()?.slot,
+        )?;
+
+        (flows, twamm.bump)
+    };
+
+    pool.record_price(reserves.0, reserves.1, now);
+
+    let pool_key = pool.key();
+    let twamm_seeds = get_twamm_signer_seeds(&pool_key, &bump);
+
+    move_escrowed_funds(
+        pool,
+        (&twamm.to_account_info(), &twamm_seeds),
+        vaults,
+        escrows,
+        token_program,
+        flows,
+    )
+}
+
+/// Runs the segments of `twamm` up to `now`, at `slot`, against the curve `reserves`, given as
+/// (token A, token B) and updated in place, without moving any funds. Returns the funds
+/// to move as (escrow A -> vault A, escrow B -> vault B, vault A -> escrow A, vault B -> escrow B).
+pub fn run_twamm(
+    pool: &mut Pool,
+    twamm: &mut Twamm,
+    reserves: &mut (u64, u64),
+    decimals: (u8, u8),
+    now: i64,
+    slot: u64,
+) -> Result<(u64, u64, u64, u64)> {
+    let mut flows = (0u64, 0u64, 0u64, 0u64);
+
+    loop {
+        let next_expiry = twamm.next_expiry(now);
+        let segment_end = next_expiry.map_or(now, |index| twamm.expiries[index].ts);
+
+        execute_twamm_segment(
+            pool,
+            twamm,
+            reserves,
+            &mut flows,
+            decimals,
+            (segment_end, slot),
+        )?;
+
+        match next_expiry {
+            Some(index) => twamm.cross_expiry(index),
+            None => break,
+        }
+    }
+
+    Ok(flows)
+}
+
+fn execute_twamm_segment(
+    pool: &mut Pool,
+    twamm: &mut Twamm,
+    (reserve_a, reserve_b): &mut (u64, u64),
+    flows: &mut (u64, u64, u64, u64),
+    decimals: (u8, u8),
+    (segment_end, slot): (i64, u64),
+) -> Result<()> {
+    let elapsed = (segment_end - twamm.last_execution_ts).max(0) as u128;
+    twamm.last_execution_ts = twamm.last_execution_ts.max(segment_end);
+
+    let sold_a = mul_shr(twamm.a_to_b.sell_rate, elapsed, 64)? as u64;
+    let sold_b = mul_shr(twamm.b_to_a.sell_rate, elapsed, 64)? as u64;
+
+    if sold_a == 0 && sold_b == 0 {
+        return Ok(());
+    }
+
+    require!(
+        *reserve_a > 0 && *reserve_b > 0,
+        DEXError::InsufficientReserves
+    );
+
+    // Whichever side is worth less at the pool price is matched in full by the other one
+    let value_of_sold_a = mul_div_floor(sold_a as u128, *reserve_b as u128, *reserve_a as u128)?;
+    let (proceeds_a_to_b, proceeds_b_to_a) = if value_of_sold_a >= sold_b as u128 {
+        let matched_a =
+            mul_div_floor(sold_b as u128, *reserve_a as u128, *reserve_b as u128)? as u64;
+        let swapped_out = swap_twamm_excess(
+            pool,
+            true,
+            sold_a - matched_a,
+            (reserve_a, reserve_b),
+            decimals,
+            (segment_end, slot),
+        )?;
+
+        flows.0 += sold_a - matched_a;
+        flows.3 += swapped_out;
+
+        (sold_b + swapped_out, matched_a)
+    } else {
+        let matched_b = value_of_sold_a as u64;
+        let swapped_out = swap_twamm_excess(
+            pool,
+            false,
+            sold_b - matched_b,
+            (reserve_b, reserve_a),
+            (decimals.1, decimals.0),
+            (segment_end, slot),
+        )?;
+
+        flows.1 += sold_b - matched_b;
+        flows.2 += swapped_out;
+
+        (matched_b, sold_a + swapped_out)
+    };
+
+    twamm.a_to_b.distribute(proceeds_a_to_b)?;
+    twamm.b_to_a.distribute(proceeds_b_to_a)?;
+
+    Ok(())
+}
+
+/// Swaps the unmatched part of a TWAMM segment against the pool, updating the reserves
+fn swap_twamm_excess(
+    pool: &mut Pool,
+    a_to_b: bool,
+    amount_in: u64,
+    (reserve_in, reserve_out): (&mut u64, &mut u64),
+    decimals: (u8, u8),
+    (now, slot): (i64, u64),
+) -> Result<u64> {
+    if amount_in == 0 {
+        return Ok(0);
+    }
+
+    let quote = quote_swap(
+        pool,
+        a_to_b,
+        amount_in,
+        (*reserve_in, *reserve_out),
+        decimals,
+        0,
+        (now, slot),
+    )?;
+
+    let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount)?;
+    *reserve_in = reserve_in
+        .checked_add(amount_in - separated_fee)
+        .ok_or(DEXError::MathOverflow)?;
+    *reserve_out -= quote.amount_out;
+
+    Ok(quote.amount_out)
+}
+
+/// Pays the `proceeds` and `unsold` input of a long-term order out of the TWAMM escrows,
+/// escrows and owner token accounts being given as (token A, token B)
+pub fn pay_out_long_term_order<'info>(
+    (twamm, twamm_seeds): (&AccountInfo<'info>, &[&[u8]]),
+    escrows: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    owner_token_accounts: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    token_program: &AccountInfo<'info>,
+    a_to_b: bool,
+    (proceeds, unsold): (u64, u64),
+) -> Result<()> {
+    let (input, output) = if a_to_b {
+        (
+            (escrows.0, owner_token_accounts.0),
+            (escrows.1, owner_token_accounts.1),
+        )
+    } else {
+        (
+            (escrows.1, owner_token_accounts.1),
+            (escrows.0, owner_token_accounts.0),
+        )
+    };
+
+    for ((escrow, owner_token_account), amount) in [(output, proceeds), (input, unsold)] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: escrow.to_account_info(),
+                    to: owner_token_account.to_account_info(),
+                    authority: twamm.clone(),
+                },
+                &[twamm_seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Settles escrowed order funds with the pool vaults, amounts being given as (escrow A -> vault A,
+/// escrow B -> vault B, vault A -> escrow A, vault B -> escrow B). Inputs are moved in before
+/// any output is paid out of the vaults.
+pub fn move_escrowed_funds<'info>(
+    pool: &Account<'info, Pool>,
+    (escrow_authority, escrow_seeds): (&AccountInfo<'info>, &[&[u8]]),
+    vaults: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    escrows: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    token_program: &AccountInfo<'info>,
+    amounts: (u64, u64, u64, u64),
+) -> Result<()> {
+    let pool_seeds = get_pool_signer_seeds(&pool.mint_a, &pool.mint_b, &pool.bump);
+
+    let transfers = [
+        (
+            escrows.0,
+            vaults.0,
+            escrow_authority.clone(),
+            escrow_seeds,
+            amounts.0,
+        ),
+        (
+            escrows.1,
+            vaults.1,
+            escrow_authority.clone(),
+            escrow_seeds,
+            amounts.1,
+        ),
+        (
+            vaults.0,
+            escrows.0,
+            pool.to_account_info(),
+            pool_seeds.as_slice(),
+            amounts.2,
+        ),
+        (
+            vaults.1,
+            escrows.1,
+            pool.to_account_info(),
+            pool_seeds.as_slice(),
+            amounts.3,
+        ),
+    ];
+
+    for (from, to, authority, seeds, amount) in transfers {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: to.to_account_info(),
+                    authority,
+                },
+                &[seeds],
+            ),
+            amount,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Largest part of the remaining input of `order` the pool fills at its limit price or
+/// better, with its quote and the pool state after the fill. The whole order is tried
+/// first, then the amount is bisected for `PARTIAL_FILL_SEARCH_STEPS` steps.
+fn quote_limit_fill(
+    pool: &Pool,
+    order: &LimitOrder,
+    reserves: (u64, u64),
+    decimals: (u8, u8),
+    now: i64,
+    slot: u64,
+) -> Result<Option<(u64, SwapQuote, Pool)>> {
+    // Orders the pool cannot execute (swap caps, empty reserves) just keep resting
+    let fill = |amount_in: u64| -> Result<Option<(u64, SwapQuote, Pool)>> {
+        let mut priced_pool = pool.clone();
+        let Ok(quote) = quote_swap(
+            &mut priced_pool,
+            order.is_a_to_b(),
+            amount_in,
+            reserves,
+            decimals,
+            0,
+            (now, slot),
+        ) else {
+            return Ok(None);
+        };
+
+        Ok(
+            (quote.amount_out >= order.min_amount_out(amount_in)?).then_some((
+                amount_in,
+                quote,
+                priced_pool,
+            )),
+        )
+    };
+
+    if let Some(full) = fill(order.amount_in)? {
+        return Ok(Some(full));
+    }
+
+    // The average price only gets worse with size, so an order that cannot even be
+    // filled for the smallest step is skipped without bisecting
+    let smallest = (order.amount_in >> PARTIAL_FILL_SEARCH_STEPS).max(1);
+    let Some(mut best) = fill(smallest)? else {
+        return Ok(None);
+    };
+
+    let (mut low, mut high) = (smallest, order.amount_in);
+    for _ in 0..PARTIAL_FILL_SEARCH_STEPS {
+        if high - low <= 1 {
+            break;
+        }
+
+        let middle = low + (high - low) / 2;
+        match fill(middle)? {
+            Some(partial) => {
+                low = middle;
+                best = partial;
+            }
+            None => high = middle,
+        }
+    }
+
+    Ok(Some(best))
+}
+
+/// Fills the resting orders of `order_book` that the pool can now execute at their limit
+/// price or better. On each side the orders asking the least output per unit of input go
+/// first, equal prices in the order they were placed. An order the pool can only take part
+/// of is filled as far as its limit allows and keeps the rest on the book. Vaults and
+/// escrows are given as (token A, token B) and the vaults must hold their current balances,
+/// so reload them after earlier transfers. Returns the number of fills.
+pub fn fill_limit_orders<'info>(
+    pool: &mut Account<'info, Pool>,
+    order_book: &AccountLoader<'info, OrderBook>,
+    vaults: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    escrows: (
+        &InterfaceAccount<'info, TokenAccount>,
+        &InterfaceAccount<'info, TokenAccount>,
+    ),
+    token_program: &AccountInfo<'info>,
+    decimals: (u8, u8),
+    now: i64,
+) -> Result<usize> {
+    require_keys_eq!(vaults.0.key(), pool.vault_a, DEXError::InvalidOrderBook);
+    require_keys_eq!(vaults.1.key(), pool.vault_b, DEXError::InvalidOrderBook);
+
+    let current_slot = Clock::get()?.slot;
+
+    // Fills are priced first, the book is written and funds moved afterwards since
+    // the book signs the escrow transfers and cannot stay borrowed during them
+    let (bump, fills) = {
+        let book = order_book.load()?;
+
+        require_keys_eq!(book.pool, pool.key(), DEXError::InvalidOrderBook);
+        require_keys_eq!(escrows.0.key(), book.escrow_a, DEXError::InvalidOrderBook);
+        require_keys_eq!(escrows.1.key(), book.escrow_b, DEXError::InvalidOrderBook);
+
+        let (mut reserve_a, mut reserve_b) = (
+            pool.curve_reserve(true, vaults.0.amount),
+            pool.curve_reserve(false, vaults.1.amount),
+        );
+
+        let mut slots = (0..book.orders.len())
+            .filter(|&slot| !book.orders[slot].is_empty() && book.orders[slot].amount_in > 0)
+            .collect::<Vec<_>>();
+        slots.sort_by_key(|&slot| {
+            let order = &book.orders[slot];
+            (order.a_to_b, order.limit_price, order.id)
+        });
+
+        // Once an order cannot be filled at all, neither can the pricier ones of its side
+        let mut side_exhausted = [false; 2];
+        let mut fills = Vec::new();
+
+        for slot in slots {
+            if fills.len() == MAX_ORDER_FILLS_PER_CALL {
+                break;
+            }
+
+            let order = &book.orders[slot];
+            let a_to_b = order.is_a_to_b();
+
+            if side_exhausted[a_to_b as usize] {
+                continue;
+            }
+
+            let (reserves, order_decimals) = if a_to_b {
+                ((reserve_a, reserve_b), decimals)
+            } else {
+                ((reserve_b, reserve_a), (decimals.1, decimals.0))
+            };
+
+            let Some((amount_in, quote, priced_pool)) =
+                quote_limit_fill(pool, order, reserves, order_decimals, now, current_slot)?
+            else {
+                side_exhausted[a_to_b as usize] = true;
+                continue;
+            };
+
+            // Keep the dynamic fee and swap cap usage the fill moved
+            **pool = priced_pool;
+            let separated_fee = pool.separate_fee(a_to_b, quote.fee_amount)?;
+
+            if a_to_b {
+                reserve_a = reserve_a
+                    .checked_add(amount_in - separated_fee)
+                    .ok_or(DEXError::MathOverflow)?;
+                reserve_b -= quote.amount_out;
+            } else {
+                reserve_b = reserve_b
+                    .checked_add(amount_in - separated_fee)
+                    .ok_or(DEXError::MathOverflow)?;
+                reserve_a -= quote.amount_out;
+            }
+
+            fills.push((slot, amount_in, quote.amount_out));
+        }
+
+        if !fills.is_empty() {
+            pool.record_price(reserve_a, reserve_b, now);
+        }
+
+        (book.bump, fills)
+    };
+
+    // (escrow A -> vault A, escrow B -> vault B, vault A -> escrow A, vault B -> escrow B)
+    let mut totals = (0u64, 0u64, 0u64, 0u64);
+
+    {
+        let mut book = order_book.load_mut()?;
+
+        for &(slot, amount_in, amount_out) in &fills {
+            let order = &mut book.orders[slot];
+
+            if order.is_a_to_b() {
+                totals.0 += amount_in;
+                totals.3 += amount_out;
+            } else {
+                totals.1 += amount_in;
+                totals.2 += amount_out;
+            }
+
+            emit!(LimitOrderFilledEvent {
+                pool: pool.key(),
+                order_id: order.id,
+                owner: order.owner,
+                amount_in,
+                amount_out,
+            });
+
+            order.amount_filled = order
+                .amount_filled
+                .checked_add(amount_out)
+                .ok_or(DEXError::MathOverflow)?;
+            order.amount_in -= amount_in;
+        }
+    }
+
+    let pool_key = pool.key();
+    let book_seeds = get_order_book_signer_seeds(&pool_key, &bump);
+
+    move_escrowed_funds(
+        pool,
+        (&order_book.to_account_info(), &book_seeds),
+        vaults,
+        escrows,
+        token_program,
+        totals,
+    )?;
+
+    Ok(fills.len())
+}
+
+/// Splits `remaining_accounts` into one (owner token account, pool vault) pair per
+/// token of a multi-asset pool, checking they follow the pool token order
+pub fn multi_pool_token_accounts<'info>(
+    pool: &MultiPool,
+    owner: &Pubkey,
+    remaining_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<(&'info AccountInfo<'info>, &'info AccountInfo<'info>)>> {
+    require!(
+        remaining_accounts.len() == 2 * pool.tokens.len(),
+        DEXError::InvalidTokenAccounts
+    );
+
+    pool.tokens
+        .iter()
+        .zip(remaining_accounts.chunks_exact(2))
+        .map(|(token, accounts)| {
+            let owner_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[0])?;
+
+            require_keys_eq!(
+                owner_account.mint,
+                token.mint,
+                DEXError::InvalidTokenAccounts
+            );
+            require_keys_eq!(owner_account.owner, *owner, DEXError::InvalidTokenAccounts);
+            require_keys_eq!(
+                accounts[1].key(),
+                token.vault,
+                DEXError::InvalidTokenAccounts
+            );
+
+            Ok((&accounts[0], &accounts[1]))
+        })
+        .collect()
+}
+
+/// Adds (or removes, when negative) liquidity to a concentrated position, updating the
+/// bounding ticks, the fees owed to the position and the in-range pool liquidity.
+/// A zero delta only refreshes the fees of the position.
+pub fn modify_position_liquidity(
+    pool: &mut ConcentratedPool,
+    position: &mut Position,
+    tick_array_lower: &AccountLoader<TickArray>,
+    tick_array_upper: &AccountLoader<TickArray>,
+    liquidity_delta: i128,
+) -> Result<()> {
+    let spacing = pool.tick_spacing;
+    let (tick_lower, tick_upper) = (position.tick_lower, position.tick_upper);
+
+    // Ticks are copied out and written back one array at a time, so both
+    // ends of the range may live in the same tick array
+    let mut lower = tick_array_lower.load()?.get_tick(tick_lower, spacing)?;
+    let mut upper = tick_array_upper.load()?.get_tick(tick_upper, spacing)?;
+
+    lower.update(
+        tick_lower,
+        pool.tick_current,
+        liquidity_delta,
+        pool.fee_growth_global_a,
+        pool.fee_growth_global_b,
+        false,
+    )?;
+    upper.update(
+        tick_upper,
+        pool.tick_current,
+        liquidity_delta,
+        pool.fee_growth_global_a,
+        pool.fee_growth_global_b,
+        true,
+    )?;
+
+    let (inside_a, inside_b) = fee_growth_inside(
+        &lower,
+        &upper,
+        tick_lower,
+        tick_upper,
+        pool.tick_current,
+        pool.fee_growth_global_a,
+        pool.fee_growth_global_b,
+    );
+
+    position.update_fees(inside_a, inside_b)?;
+    position.liquidity = position
+        .liquidity
+        .checked_add_signed(liquidity_delta)
+        .ok_or(DEXError::InsufficientPositionLiquidity)?;
+
+    if (tick_lower..tick_upper).contains(&pool.tick_current) {
+        pool.liquidity = pool
+            .liquidity
+            .checked_add_signed(liquidity_delta)
+            .ok_or(DEXError::MathOverflow)?;
+    }
+
+    if !lower.is_initialized() {
+        lower = Default::default();
+    }
+
+    if !upper.is_initialized() {
+        upper = Default::default();
+    }
+
+    tick_array_lower
+        .load_mut()?
+        .set_tick(tick_lower, spacing, lower)?;
+    tick_array_upper
+        .load_mut()?
+        .set_tick(tick_upper, spacing, upper)?;
+
+    Ok(())
+}
+
+/// Token amounts taken from a deposit of up to (`token_a_amount`, `token_b_amount`) into a
+/// pool holding (`total_a`, `total_b`), along with the LP tokens minted for them
+pub fn calculate_deposit_amounts(
+    token_a_amount: u64,
+    token_b_amount: u64,
+    total_lp_supply: u64,
+    total_a: u64,
+    total_b: u64,
+) -> Result<(u128, u128, u128)> {
+    let mut is_initial = true;
+
+    let (a_amount, b_amount) = if total_lp_supply != 0 {
+        is_initial = false;
+
+        let mut token_a_final_amount = token_a_amount as u128;
+        let mut token_b_final_amount = token_b_amount as u128;
+
+        let proportional_b = token_a_final_amount
+            .checked_mul(total_b as u128)
+            .ok_or(DEXError::MathOverflow)?
+            .checked_div(total_a as u128)
+            .ok_or(DEXError::MathOverflow)?;
+
+        if proportional_b < token_b_final_amount {
+            let proportional_a = token_b_final_amount
+                .checked_mul(total_a as u128)
+                .ok_or(DEXError::MathOverflow)?
+                .checked_div(total_b as u128)
+                .ok_or(DEXError::MathOverflow)?;
+
+            if proportional_a < token_a_final_amount {
+                return err!(DEXError::InvalidAmountOfLiquidation);
+            }
+
+            token_a_final_amount = proportional_a;
+        } else {
+            token_b_final_amount = proportional_b;
+        }
+
+        (token_a_final_amount, token_b_final_amount)
+    } else {
+        // initial deposit does not require checking
+        (token_a_amount as u128, token_b_amount as u128)
+    };
+
+    let product = a_amount
+        .checked_mul(b_amount)
+        .ok_or(DEXError::MathOverflow)?;
+
+    // NOTE: Minimum liquidity sent to an unusable address (like the system's address)
+    // to prevent inflation attacks
+    let liquidity = if is_initial {
+        i_sqrt(product)
+    } else {
+        let a_anchored = a_amount
+            .checked_mul(total_lp_supply as u128)
+            .ok_or(DEXError::MathOverflow)?
+            .checked_div(total_a as u128)
+            .ok_or(DEXError::MathOverflow)?;
+
+        let b_anchored = b_amount
+            .checked_mul(total_lp_supply as u128)
+            .ok_or(DEXError::MathOverflow)?
+            .checked_div(total_b as u128)
+            .ok_or(DEXError::MathOverflow)?;
+
+        a_anchored.min(b_anchored)
+    };
+
+    Ok((a_amount, b_amount, liquidity))
+}
+
 pub fn calculate_withdrawal_amounts(
     lp_tokens_to_burn: u64,
     total_lp_supply: u64,
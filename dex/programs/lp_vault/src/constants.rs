@@ -0,0 +1,3 @@
+pub const VAULT_SEED: &[u8] = b"vault";
+pub const VAULT_AUTHORITY_SEED: &[u8] = b"vault_authority";
+pub const SHARE_MINT_SEED: &[u8] = b"share_mint";
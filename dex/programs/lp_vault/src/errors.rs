@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum VaultError {
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Deposits and withdrawals must be greater than zero")]
+    ZeroAmount,
+
+    #[msg("The farm does not belong to the vault pool")]
+    InvalidFarm,
+
+    #[msg("Rewards can only be swapped into a token of the vault pool")]
+    InvalidSwapMint,
+
+    #[msg("Swaps through batch auction pools do not execute right away")]
+    BatchAuctionPool,
+
+    #[msg("The vault pool has no liquidity to compound into")]
+    EmptyPool,
+
+    #[msg("Compounding minted fewer LP tokens than expected")]
+    SlippageExceeded,
+
+    #[msg("Only the vault admin can do this")]
+    Unauthorized,
+
+    #[msg("Only the vault keeper can harvest and compound")]
+    NotKeeper,
+
+    #[msg(
+        "The swap pool is not the one set for this reward or does not pair it with a vault token"
+    )]
+    InvalidSwapPool,
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct VaultDepositEvent {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub lp_amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct VaultWithdrawEvent {
+    pub vault: Pubkey,
+    pub owner: Pubkey,
+    pub lp_amount: u64,
+    pub shares: u64,
+}
+
+#[event]
+pub struct VaultHarvestEvent {
+    pub vault: Pubkey,
+    pub reward_mint: Pubkey,
+    pub reward_amount: u64,
+    /// Received in the pool token the reward was swapped into
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct VaultCompoundEvent {
+    pub vault: Pubkey,
+    /// LP tokens minted and staked back into the farm
+    pub lp_amount: u64,
+    /// LP tokens staked by the vault after compounding
+    pub total_lp: u64,
+}
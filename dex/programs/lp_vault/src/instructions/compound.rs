@@ -0,0 +1,335 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+use dex::{
+    cpi::accounts::{AddLiquidityToPool, ExchangeTokens, StakeLp},
+    program::Dex,
+    state::{FarmStake, Pool},
+};
+
+use crate::constants::{VAULT_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::events::VaultCompoundEvent;
+use crate::state::Vault;
+use crate::utils::{fund_authority_rent, get_vault_authority_signer_seeds, staked_lp};
+
+/// Turns the pool tokens held by the vault into LP tokens staked in the farm. Half of
+/// whichever token is in excess of the pool ratio is swapped into the other first; what the
+/// swap price impact leaves over is picked up by the next compound. Only the vault keeper
+/// can compound, as it picks the minimum output of the swap and of the LP tokens.
+pub fn compound(ctx: Context<Compound>, min_swap_out: u64, min_lp_out: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.liquidity_pool.batch_auction,
+        VaultError::BatchAuctionPool
+    );
+
+    let vault_key = ctx.accounts.vault.key();
+    let signer_seeds =
+        get_vault_authority_signer_seeds(&vault_key, &ctx.accounts.vault.authority_bump);
+
+    let (reserve_a, reserve_b) = curve_reserves(ctx.accounts)?;
+    let (amount_a, amount_b) = (
+        ctx.accounts.authority_token_a.amount as u128,
+        ctx.accounts.authority_token_b.amount as u128,
+    );
+
+    // Value of the A held by the vault in B at the pool ratio
+    let a_in_b = amount_a
+        .checked_mul(reserve_b)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(reserve_a)
+        .ok_or(VaultError::MathOverflow)?;
+    let a_to_b = a_in_b > amount_b;
+
+    let swap_amount = if a_to_b {
+        let b_in_a = amount_b
+            .checked_mul(reserve_a)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(reserve_b)
+            .ok_or(VaultError::MathOverflow)?;
+        (amount_a - b_in_a) / 2
+    } else {
+        (amount_b - a_in_b) / 2
+    } as u64;
+
+    if swap_amount > 0 {
+        let accounts = &ctx.accounts;
+        let (mint_from, mint_to, account_from, account_to, vault_in, vault_out) = if a_to_b {
+            (
+                &accounts.mint_a,
+                &accounts.mint_b,
+                &accounts.authority_token_a,
+                &accounts.authority_token_b,
+                &accounts.pool_vault_a,
+                &accounts.pool_vault_b,
+            )
+        } else {
+            (
+                &accounts.mint_b,
+                &accounts.mint_a,
+                &accounts.authority_token_b,
+                &accounts.authority_token_a,
+                &accounts.pool_vault_b,
+                &accounts.pool_vault_a,
+            )
+        };
+
+        dex::cpi::exchange_tokens(
+            CpiContext::new_with_signer(
+                accounts.dex_program.to_account_info(),
+                ExchangeTokens {
+                    buyer: accounts.authority.to_account_info(),
+                    liquidity_pool: accounts.liquidity_pool.to_account_info(),
+                    mint_from: mint_from.to_account_info(),
+                    mint_to: mint_to.to_account_info(),
+                    buyer_token_account_from: account_from.to_account_info(),
+                    buyer_token_account_to: account_to.to_account_info(),
+                    vault_to: vault_in.to_account_info(),
+                    vault_from: vault_out.to_account_info(),
+                    referrer_token_account: None,
+                    referrer: None,
+                    trader_stats: None,
+                    global_config: None,
+                    order_book: None,
+                    order_escrow_a: None,
+                    order_escrow_b: None,
+                    twamm: accounts.twamm.as_ref().map(|a| a.to_account_info()),
+                    twamm_escrow_a: accounts
+                        .twamm_escrow_a
+                        .as_ref()
+                        .map(|a| a.to_account_info()),
+                    twamm_escrow_b: accounts
+                        .twamm_escrow_b
+                        .as_ref()
+                        .map(|a| a.to_account_info()),
+                    batch_auction: None,
+                    batch_escrow: None,
+                    token_program: accounts.token_program.to_account_info(),
+                },
+                &[&signer_seeds],
+            ),
+            swap_amount,
+            min_swap_out,
+        )?;
+
+        ctx.accounts.liquidity_pool.reload()?;
+        ctx.accounts.pool_vault_a.reload()?;
+        ctx.accounts.pool_vault_b.reload()?;
+        ctx.accounts.authority_token_a.reload()?;
+        ctx.accounts.authority_token_b.reload()?;
+    }
+
+    // The dex takes more A than offered when B is in excess, so B is sized off A
+    let (reserve_a, reserve_b) = curve_reserves(ctx.accounts)?;
+    let deposit_a = (ctx.accounts.authority_token_b.amount as u128)
+        .checked_mul(reserve_a)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(reserve_b)
+        .ok_or(VaultError::MathOverflow)?
+        .min(ctx.accounts.authority_token_a.amount as u128);
+    let deposit_b = deposit_a
+        .checked_mul(reserve_b)
+        .ok_or(VaultError::MathOverflow)?
+        .checked_div(reserve_a)
+        .ok_or(VaultError::MathOverflow)?;
+
+    if deposit_a > 0 && deposit_b > 0 {
+        dex::cpi::add_liquidity_to_pool(
+            CpiContext::new_with_signer(
+                ctx.accounts.dex_program.to_account_info(),
+                AddLiquidityToPool {
+                    signer: ctx.accounts.authority.to_account_info(),
+                    mint_a: ctx.accounts.mint_a.to_account_info(),
+                    mint_b: ctx.accounts.mint_b.to_account_info(),
+                    liquidity_pool: ctx.accounts.liquidity_pool.to_account_info(),
+                    vault_a: ctx.accounts.pool_vault_a.to_account_info(),
+                    vault_b: ctx.accounts.pool_vault_b.to_account_info(),
+                    lp_mint: ctx.accounts.lp_mint.to_account_info(),
+                    user_lp_tokens_account: ctx.accounts.authority_lp_account.to_account_info(),
+                    user_token_a_account: ctx.accounts.authority_token_a.to_account_info(),
+                    user_token_b_account: ctx.accounts.authority_token_b.to_account_info(),
+                    twamm: ctx.accounts.twamm.as_ref().map(|a| a.to_account_info()),
+                    twamm_escrow_a: ctx
+                        .accounts
+                        .twamm_escrow_a
+                        .as_ref()
+                        .map(|a| a.to_account_info()),
+                    twamm_escrow_b: ctx
+                        .accounts
+                        .twamm_escrow_b
+                        .as_ref()
+                        .map(|a| a.to_account_info()),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                    system_program: ctx.accounts.system_program.to_account_info(),
+                    associated_token_program: ctx
+                        .accounts
+                        .associated_token_program
+                        .to_account_info(),
+                },
+                &[&signer_seeds],
+            ),
+            deposit_a as u64,
+            deposit_b as u64,
+        )?;
+    }
+
+    // LP tokens left over by an earlier compound are staked along
+    ctx.accounts.authority_lp_account.reload()?;
+    let lp_amount = ctx.accounts.authority_lp_account.amount;
+    require!(lp_amount >= min_lp_out, VaultError::SlippageExceeded);
+
+    if lp_amount == 0 {
+        return Ok(());
+    }
+
+    let farm_stake = ctx.accounts.farm_stake.to_account_info();
+
+    fund_authority_rent(
+        &ctx.accounts.keeper,
+        &ctx.accounts.authority,
+        &farm_stake,
+        FarmStake::MAX_SIZE,
+        &ctx.accounts.system_program,
+    )?;
+
+    dex::cpi::stake_lp(
+        CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            StakeLp {
+                owner: ctx.accounts.authority.to_account_info(),
+                farm: ctx.accounts.farm.to_account_info(),
+                farm_stake: farm_stake.clone(),
+                owner_lp_account: ctx.accounts.authority_lp_account.to_account_info(),
+                lp_vault: ctx.accounts.farm_lp_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        lp_amount,
+    )?;
+
+    emit!(VaultCompoundEvent {
+        vault: vault_key,
+        lp_amount,
+        total_lp: staked_lp(&farm_stake)?,
+    });
+
+    Ok(())
+}
+
+fn curve_reserves(accounts: &Compound) -> Result<(u128, u128)> {
+    let pool = &accounts.liquidity_pool;
+    let reserves = (
+        pool.curve_reserve(true, accounts.pool_vault_a.amount) as u128,
+        pool.curve_reserve(false, accounts.pool_vault_b.amount) as u128,
+    );
+
+    require!(reserves.0 > 0 && reserves.1 > 0, VaultError::EmptyPool);
+
+    Ok(reserves)
+}
+
+#[derive(Accounts)]
+pub struct Compound<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault.farm.as_ref()],
+        bump = vault.bump,
+        has_one = farm,
+        has_one = mint_a,
+        has_one = mint_b,
+        has_one = lp_mint,
+        has_one = keeper @ VaultError::NotKeeper,
+        constraint = vault.pool == liquidity_pool.key() @ VaultError::InvalidFarm
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump = vault.authority_bump
+    )]
+    pub authority: SystemAccount<'info>,
+
+    #[account(mut)]
+    pub liquidity_pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = liquidity_pool
+    )]
+    pub pool_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = liquidity_pool
+    )]
+    pub pool_vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = authority
+    )]
+    pub authority_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = authority
+    )]
+    pub authority_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = authority
+    )]
+    pub authority_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Farm of the vault, checked by the vault and the dex
+    #[account(mut)]
+    pub farm: UncheckedAccount<'info>,
+
+    /// CHECK: Stake of the vault authority in the farm, checked by the dex
+    #[account(mut)]
+    pub farm_stake: UncheckedAccount<'info>,
+
+    /// CHECK: LP vault of the farm, checked by the dex
+    #[account(mut)]
+    pub farm_lp_vault: UncheckedAccount<'info>,
+
+    /// CHECK: TWAMM of the vault pool, required and checked by the dex once the pool has one
+    #[account(mut)]
+    pub twamm: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Escrows of the TWAMM, checked by the dex
+    #[account(mut)]
+    pub twamm_escrow_a: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Escrows of the TWAMM, checked by the dex
+    #[account(mut)]
+    pub twamm_escrow_b: Option<UncheckedAccount<'info>>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{mint_to, transfer, MintTo, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+use dex::{cpi::accounts::StakeLp, program::Dex, state::FarmStake};
+
+use crate::constants::{VAULT_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::events::VaultDepositEvent;
+use crate::state::Vault;
+use crate::utils::{fund_authority_rent, get_vault_authority_signer_seeds, staked_lp};
+
+/// Stakes `lp_amount` LP tokens of the owner in the farm through the vault, minting vault
+/// shares at the current LP per share
+pub fn deposit(ctx: Context<Deposit>, lp_amount: u64) -> Result<()> {
+    require!(lp_amount > 0, VaultError::ZeroAmount);
+
+    let farm_stake = ctx.accounts.farm_stake.to_account_info();
+    let shares = Vault::shares_for(
+        lp_amount,
+        staked_lp(&farm_stake)?,
+        ctx.accounts.share_mint.supply,
+    )?;
+    require!(shares > 0, VaultError::ZeroAmount);
+
+    transfer(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.owner_lp_account.to_account_info(),
+                to: ctx.accounts.authority_lp_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        lp_amount,
+    )?;
+
+    fund_authority_rent(
+        &ctx.accounts.owner,
+        &ctx.accounts.authority,
+        &farm_stake,
+        FarmStake::MAX_SIZE,
+        &ctx.accounts.system_program,
+    )?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let signer_seeds =
+        get_vault_authority_signer_seeds(&vault_key, &ctx.accounts.vault.authority_bump);
+
+    dex::cpi::stake_lp(
+        CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            StakeLp {
+                owner: ctx.accounts.authority.to_account_info(),
+                farm: ctx.accounts.farm.to_account_info(),
+                farm_stake,
+                owner_lp_account: ctx.accounts.authority_lp_account.to_account_info(),
+                lp_vault: ctx.accounts.farm_lp_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        lp_amount,
+    )?;
+
+    mint_to(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                to: ctx.accounts.owner_share_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        shares,
+    )?;
+
+    emit!(VaultDepositEvent {
+        vault: vault_key,
+        owner: ctx.accounts.owner.key(),
+        lp_amount,
+        shares,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault.farm.as_ref()],
+        bump = vault.bump,
+        has_one = farm,
+        has_one = lp_mint,
+        has_one = share_mint
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump = vault.authority_bump
+    )]
+    pub authority: SystemAccount<'info>,
+
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = share_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = authority
+    )]
+    pub authority_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Farm of the vault, checked by the vault and the dex
+    #[account(mut)]
+    pub farm: UncheckedAccount<'info>,
+
+    /// CHECK: Stake of the vault authority in the farm, checked by the dex
+    #[account(mut)]
+    pub farm_stake: UncheckedAccount<'info>,
+
+    /// CHECK: LP vault of the farm, checked by the dex
+    #[account(mut)]
+    pub farm_lp_vault: UncheckedAccount<'info>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
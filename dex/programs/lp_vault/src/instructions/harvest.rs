@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+use dex::{
+    cpi::accounts::{ClaimRewards, ExchangeTokens},
+    program::Dex,
+    state::Pool,
+};
+
+use crate::constants::{VAULT_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::events::VaultHarvestEvent;
+use crate::state::Vault;
+use crate::utils::get_vault_authority_signer_seeds;
+
+/// Claims what the vault earned of one farm reward and swaps it into a token of the vault
+/// pool through the swap pool the admin set for the reward, ready to be compounded. Only the
+/// vault keeper can harvest, as it picks the minimum output of the swap.
+pub fn harvest(ctx: Context<Harvest>, reward_index: u8, min_amount_out: u64) -> Result<()> {
+    require!(
+        !ctx.accounts.swap_pool.batch_auction,
+        VaultError::BatchAuctionPool
+    );
+
+    let vault_key = ctx.accounts.vault.key();
+    let signer_seeds =
+        get_vault_authority_signer_seeds(&vault_key, &ctx.accounts.vault.authority_bump);
+
+    dex::cpi::claim_rewards(
+        CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            ClaimRewards {
+                owner: ctx.accounts.authority.to_account_info(),
+                farm: ctx.accounts.farm.to_account_info(),
+                farm_stake: ctx.accounts.farm_stake.to_account_info(),
+                reward_mint: ctx.accounts.reward_mint.to_account_info(),
+                owner_reward_account: ctx.accounts.authority_reward_account.to_account_info(),
+                reward_vault: ctx.accounts.reward_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+                associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        reward_index,
+    )?;
+
+    // Rewards left over by an earlier harvest are swapped along
+    ctx.accounts.authority_reward_account.reload()?;
+    let reward_amount = ctx.accounts.authority_reward_account.amount;
+
+    if reward_amount == 0 {
+        return Ok(());
+    }
+
+    let before_out = ctx.accounts.authority_token_out.amount;
+
+    dex::cpi::exchange_tokens(
+        CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            ExchangeTokens {
+                buyer: ctx.accounts.authority.to_account_info(),
+                liquidity_pool: ctx.accounts.swap_pool.to_account_info(),
+                mint_from: ctx.accounts.reward_mint.to_account_info(),
+                mint_to: ctx.accounts.mint_out.to_account_info(),
+                buyer_token_account_from: ctx.accounts.authority_reward_account.to_account_info(),
+                buyer_token_account_to: ctx.accounts.authority_token_out.to_account_info(),
+                vault_to: ctx.accounts.swap_vault_in.to_account_info(),
+                vault_from: ctx.accounts.swap_vault_out.to_account_info(),
+                referrer_token_account: None,
+                referrer: None,
+                trader_stats: None,
+                global_config: None,
+                order_book: None,
+                order_escrow_a: None,
+                order_escrow_b: None,
+                twamm: ctx.accounts.twamm.as_ref().map(|a| a.to_account_info()),
+                twamm_escrow_a: ctx
+                    .accounts
+                    .twamm_escrow_a
+                    .as_ref()
+                    .map(|a| a.to_account_info()),
+                twamm_escrow_b: ctx
+                    .accounts
+                    .twamm_escrow_b
+                    .as_ref()
+                    .map(|a| a.to_account_info()),
+                batch_auction: None,
+                batch_escrow: None,
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        reward_amount,
+        min_amount_out,
+    )?;
+
+    ctx.accounts.authority_token_out.reload()?;
+
+    emit!(VaultHarvestEvent {
+        vault: vault_key,
+        reward_mint: ctx.accounts.reward_mint.key(),
+        reward_amount,
+        amount_out: ctx.accounts.authority_token_out.amount - before_out,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(reward_index: u8)]
+pub struct Harvest<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault.farm.as_ref()],
+        bump = vault.bump,
+        has_one = farm,
+        has_one = keeper @ VaultError::NotKeeper,
+        constraint = vault.swap_pools.get(reward_index as usize) == Some(&swap_pool.key())
+            @ VaultError::InvalidSwapPool,
+        constraint = mint_out.key() == vault.mint_a
+            || mint_out.key() == vault.mint_b @ VaultError::InvalidSwapMint
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump = vault.authority_bump
+    )]
+    pub authority: SystemAccount<'info>,
+
+    /// CHECK: Farm of the vault, checked by the vault and the dex
+    #[account(mut)]
+    pub farm: UncheckedAccount<'info>,
+
+    /// CHECK: Stake of the vault authority in the farm, checked by the dex
+    #[account(mut)]
+    pub farm_stake: UncheckedAccount<'info>,
+
+    /// CHECK: Vault of the farm reward, checked by the dex
+    #[account(mut)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        associated_token::mint = reward_mint,
+        associated_token::authority = authority
+    )]
+    pub authority_reward_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Pool pairing the reward with `mint_out`, as set for the reward
+    #[account(mut)]
+    pub swap_pool: Box<Account<'info, Pool>>,
+
+    #[account(mut)]
+    pub mint_out: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint_out,
+        associated_token::authority = authority
+    )]
+    pub authority_token_out: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Reward vault of the swap pool, checked by the dex
+    #[account(mut)]
+    pub swap_vault_in: UncheckedAccount<'info>,
+
+    /// CHECK: `mint_out` vault of the swap pool, checked by the dex
+    #[account(mut)]
+    pub swap_vault_out: UncheckedAccount<'info>,
+
+    /// CHECK: TWAMM of the swap pool, required and checked by the dex once the pool has one
+    #[account(mut)]
+    pub twamm: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Escrows of the TWAMM, checked by the dex
+    #[account(mut)]
+    pub twamm_escrow_a: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Escrows of the TWAMM, checked by the dex
+    #[account(mut)]
+    pub twamm_escrow_b: Option<UncheckedAccount<'info>>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
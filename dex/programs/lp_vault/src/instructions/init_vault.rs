@@ -0,0 +1,130 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::Token,
+    token_interface::{Mint, TokenAccount},
+};
+use dex::state::{Farm, Pool, MAX_FARM_REWARDS};
+
+use crate::constants::{SHARE_MINT_SEED, VAULT_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::state::Vault;
+
+/// Opens the vault compounding the farm of a dex pool, along with its share mint and the
+/// token accounts of its authority. The creator administers the vault and is its first
+/// keeper.
+pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let pool = &ctx.accounts.liquidity_pool;
+
+    vault.pool = pool.key();
+    vault.farm = ctx.accounts.farm.key();
+    vault.mint_a = pool.mint_a;
+    vault.mint_b = pool.mint_b;
+    vault.lp_mint = pool.lp_mint;
+    vault.share_mint = ctx.accounts.share_mint.key();
+    vault.admin = ctx.accounts.creator.key();
+    vault.keeper = ctx.accounts.creator.key();
+    vault.swap_pools = [Pubkey::default(); MAX_FARM_REWARDS];
+    vault.authority_bump = ctx.bumps.authority;
+    vault.bump = ctx.bumps.vault;
+
+    // The authority pays for accounts the dex creates on its behalf, and has to stay
+    // rent-exempt itself in between
+    let rent = Rent::get()?.minimum_balance(0);
+    let authority = &ctx.accounts.authority;
+
+    if authority.lamports() < rent {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.creator.to_account_info(),
+                    to: authority.to_account_info(),
+                },
+            ),
+            rent - authority.lamports(),
+        )?;
+    }
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    pub liquidity_pool: Box<Account<'info, Pool>>,
+
+    #[account(
+        constraint = farm.pool == liquidity_pool.key() @ VaultError::InvalidFarm
+    )]
+    pub farm: Box<Account<'info, Farm>>,
+
+    #[account(address = liquidity_pool.mint_a)]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = liquidity_pool.mint_b)]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(address = liquidity_pool.lp_mint)]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = Vault::MAX_SIZE,
+        seeds = [VAULT_SEED, farm.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, Vault>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump
+    )]
+    pub authority: SystemAccount<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        seeds = [SHARE_MINT_SEED, vault.key().as_ref()],
+        bump,
+        mint::decimals = lp_mint.decimals,
+        mint::authority = authority,
+    )]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint_a,
+        associated_token::authority = authority,
+    )]
+    pub authority_token_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = mint_b,
+        associated_token::authority = authority,
+    )]
+    pub authority_token_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = creator,
+        associated_token::mint = lp_mint,
+        associated_token::authority = authority,
+    )]
+    pub authority_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
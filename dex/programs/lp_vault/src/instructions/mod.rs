@@ -0,0 +1,20 @@
+pub mod init_vault;
+pub use init_vault::*;
+
+pub mod deposit;
+pub use deposit::*;
+
+pub mod withdraw;
+pub use withdraw::*;
+
+pub mod set_keeper;
+pub use set_keeper::*;
+
+pub mod set_swap_pool;
+pub use set_swap_pool::*;
+
+pub mod harvest;
+pub use harvest::*;
+
+pub mod compound;
+pub use compound::*;
@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::VAULT_SEED;
+use crate::errors::VaultError;
+use crate::state::Vault;
+
+/// Hands harvesting and compounding over to `keeper`
+pub fn set_keeper(ctx: Context<SetKeeper>, keeper: Pubkey) -> Result<()> {
+    ctx.accounts.vault.keeper = keeper;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetKeeper<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.farm.as_ref()],
+        bump = vault.bump,
+        has_one = admin @ VaultError::Unauthorized
+    )]
+    pub vault: Account<'info, Vault>,
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use dex::state::{Farm, Pool};
+
+use crate::constants::VAULT_SEED;
+use crate::errors::VaultError;
+use crate::state::Vault;
+
+/// Sets the pool the farm reward at `reward_index` is sold in by `harvest`, which has to
+/// pair the reward with a token of the vault pool
+pub fn set_swap_pool(ctx: Context<SetSwapPool>, reward_index: u8) -> Result<()> {
+    let vault = &mut ctx.accounts.vault;
+    let swap_pool = &ctx.accounts.swap_pool;
+    let reward = ctx
+        .accounts
+        .farm
+        .rewards
+        .get(reward_index as usize)
+        .ok_or(VaultError::InvalidSwapPool)?;
+
+    let other_mint = if swap_pool.mint_a == reward.mint {
+        swap_pool.mint_b
+    } else if swap_pool.mint_b == reward.mint {
+        swap_pool.mint_a
+    } else {
+        return err!(VaultError::InvalidSwapPool);
+    };
+
+    require!(
+        other_mint == vault.mint_a || other_mint == vault.mint_b,
+        VaultError::InvalidSwapPool
+    );
+    require!(!swap_pool.batch_auction, VaultError::BatchAuctionPool);
+
+    vault.swap_pools[reward_index as usize] = swap_pool.key();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetSwapPool<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, vault.farm.as_ref()],
+        bump = vault.bump,
+        has_one = admin @ VaultError::Unauthorized,
+        has_one = farm
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    pub farm: Box<Account<'info, Farm>>,
+
+    pub swap_pool: Box<Account<'info, Pool>>,
+}
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{burn, transfer, Burn, Token, Transfer},
+    token_interface::{Mint, TokenAccount},
+};
+use dex::{cpi::accounts::UnstakeLp, program::Dex};
+
+use crate::constants::{VAULT_AUTHORITY_SEED, VAULT_SEED};
+use crate::errors::VaultError;
+use crate::events::VaultWithdrawEvent;
+use crate::state::Vault;
+use crate::utils::{get_vault_authority_signer_seeds, staked_lp};
+
+/// Burns `shares` vault shares of the owner and returns their part of the LP tokens the
+/// vault has staked, compounded rewards included
+pub fn withdraw(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+    require!(shares > 0, VaultError::ZeroAmount);
+
+    let farm_stake = ctx.accounts.farm_stake.to_account_info();
+    let lp_amount = Vault::lp_for(
+        shares,
+        staked_lp(&farm_stake)?,
+        ctx.accounts.share_mint.supply,
+    )?;
+    require!(lp_amount > 0, VaultError::ZeroAmount);
+
+    burn(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.share_mint.to_account_info(),
+                from: ctx.accounts.owner_share_account.to_account_info(),
+                authority: ctx.accounts.owner.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    let vault_key = ctx.accounts.vault.key();
+    let signer_seeds =
+        get_vault_authority_signer_seeds(&vault_key, &ctx.accounts.vault.authority_bump);
+
+    dex::cpi::unstake_lp(
+        CpiContext::new_with_signer(
+            ctx.accounts.dex_program.to_account_info(),
+            UnstakeLp {
+                owner: ctx.accounts.authority.to_account_info(),
+                farm: ctx.accounts.farm.to_account_info(),
+                farm_stake,
+                owner_lp_account: ctx.accounts.authority_lp_account.to_account_info(),
+                lp_vault: ctx.accounts.farm_lp_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        lp_amount,
+    )?;
+
+    transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.authority_lp_account.to_account_info(),
+                to: ctx.accounts.owner_lp_account.to_account_info(),
+                authority: ctx.accounts.authority.to_account_info(),
+            },
+            &[&signer_seeds],
+        ),
+        lp_amount,
+    )?;
+
+    emit!(VaultWithdrawEvent {
+        vault: vault_key,
+        owner: ctx.accounts.owner.key(),
+        lp_amount,
+        shares,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Withdraw<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        seeds = [VAULT_SEED, vault.farm.as_ref()],
+        bump = vault.bump,
+        has_one = farm,
+        has_one = lp_mint,
+        has_one = share_mint
+    )]
+    pub vault: Box<Account<'info, Vault>>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_AUTHORITY_SEED, vault.key().as_ref()],
+        bump = vault.authority_bump
+    )]
+    pub authority: SystemAccount<'info>,
+
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(mut)]
+    pub share_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = lp_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = share_mint,
+        associated_token::authority = owner
+    )]
+    pub owner_share_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = authority
+    )]
+    pub authority_lp_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Farm of the vault, checked by the vault and the dex
+    #[account(mut)]
+    pub farm: UncheckedAccount<'info>,
+
+    /// CHECK: Stake of the vault authority in the farm, checked by the dex
+    #[account(mut)]
+    pub farm_stake: UncheckedAccount<'info>,
+
+    /// CHECK: LP vault of the farm, checked by the dex
+    #[account(mut)]
+    pub farm_lp_vault: UncheckedAccount<'info>,
+
+    pub dex_program: Program<'info, Dex>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,44 @@
+mod constants;
+mod errors;
+mod events;
+mod instructions;
+mod state;
+mod utils;
+use anchor_lang::prelude::*;
+
+use instructions::*;
+
+declare_id!("2CxMNdwHN2SmhBUACqeyjxqgBDYH1QATXQKMBcKAsJbK");
+
+#[program]
+pub mod lp_vault {
+    use super::*;
+
+    pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
+        instructions::init_vault::initialize_vault(ctx)
+    }
+
+    pub fn deposit(ctx: Context<Deposit>, lp_amount: u64) -> Result<()> {
+        instructions::deposit::deposit(ctx, lp_amount)
+    }
+
+    pub fn withdraw(ctx: Context<Withdraw>, shares: u64) -> Result<()> {
+        instructions::withdraw::withdraw(ctx, shares)
+    }
+
+    pub fn set_keeper(ctx: Context<SetKeeper>, keeper: Pubkey) -> Result<()> {
+        instructions::set_keeper::set_keeper(ctx, keeper)
+    }
+
+    pub fn set_swap_pool(ctx: Context<SetSwapPool>, reward_index: u8) -> Result<()> {
+        instructions::set_swap_pool::set_swap_pool(ctx, reward_index)
+    }
+
+    pub fn harvest(ctx: Context<Harvest>, reward_index: u8, min_amount_out: u64) -> Result<()> {
+        instructions::harvest::harvest(ctx, reward_index, min_amount_out)
+    }
+
+    pub fn compound(ctx: Context<Compound>, min_swap_out: u64, min_lp_out: u64) -> Result<()> {
+        instructions::compound::compound(ctx, min_swap_out, min_lp_out)
+    }
+}
@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use dex::state::MAX_FARM_REWARDS;
+
+use crate::errors::VaultError;
+
+/// Auto-compounding position in the farm of a dex pool. Deposited LP tokens are staked by
+/// the vault authority, and vault shares are minted against the staked total, which grows
+/// as farm rewards are compounded into more LP tokens.
+#[account]
+pub struct Vault {
+    pub pool: Pubkey,
+    pub farm: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub lp_mint: Pubkey,
+    pub share_mint: Pubkey,
+    /// Sets the keeper and the swap pools
+    pub admin: Pubkey,
+    /// Only account allowed to harvest and compound, as it picks their slippage bounds
+    pub keeper: Pubkey,
+    /// Pool each farm reward is sold in by `harvest`, by reward index (default = not set)
+    pub swap_pools: [Pubkey; MAX_FARM_REWARDS],
+    /// Bump of the authority PDA owning the vault token accounts and the farm stake
+    pub authority_bump: u8,
+    pub bump: u8,
+}
+
+impl Vault {
+    // discriminator + 8 pubkeys + swap pools + 2 bumps
+    pub const MAX_SIZE: usize = 8 + 8 * 32 + MAX_FARM_REWARDS * 32 + 1 + 1;
+
+    /// Shares minted for depositing `lp_amount` into a vault staking `total_lp` against
+    /// `total_shares`, one per LP token for the first deposit
+    pub fn shares_for(lp_amount: u64, total_lp: u64, total_shares: u64) -> Result<u64> {
+        if total_shares == 0 {
+            return Ok(lp_amount);
+        }
+
+        Ok((lp_amount as u128)
+            .checked_mul(total_shares as u128)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(total_lp as u128)
+            .ok_or(VaultError::MathOverflow)? as u64)
+    }
+
+    /// LP tokens `shares` are redeemed for
+    pub fn lp_for(shares: u64, total_lp: u64, total_shares: u64) -> Result<u64> {
+        Ok((shares as u128)
+            .checked_mul(total_lp as u128)
+            .ok_or(VaultError::MathOverflow)?
+            .checked_div(total_shares as u128)
+            .ok_or(VaultError::MathOverflow)? as u64)
+    }
+}
@@ -0,0 +1,50 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use dex::state::FarmStake;
+
+use crate::constants::VAULT_AUTHORITY_SEED;
+
+pub fn get_vault_authority_signer_seeds<'a>(vault_key: &'a Pubkey, bump: &'a u8) -> [&'a [u8]; 3] {
+    [
+        VAULT_AUTHORITY_SEED,
+        vault_key.as_ref(),
+        std::slice::from_ref(bump),
+    ]
+}
+
+/// LP tokens the vault authority has staked in the farm, none before the stake exists.
+/// The dex checks the stake address when it is passed on.
+pub fn staked_lp(farm_stake: &AccountInfo) -> Result<u64> {
+    if farm_stake.data_is_empty() {
+        return Ok(0);
+    }
+
+    Ok(FarmStake::try_deserialize(&mut &farm_stake.data.borrow()[..])?.amount)
+}
+
+/// The dex creates some accounts with the vault authority as payer. Tops the authority up
+/// from `payer` with the rent of `account` when it does not exist yet.
+pub fn fund_authority_rent<'info>(
+    payer: &Signer<'info>,
+    authority: &SystemAccount<'info>,
+    account: &AccountInfo<'info>,
+    space: usize,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    if !account.data_is_empty() {
+        return Ok(());
+    }
+
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: payer.to_account_info(),
+                to: authority.to_account_info(),
+            },
+        ),
+        Rent::get()?.minimum_balance(space),
+    )
+}
@@ -0,0 +1,296 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("batch_auction", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let batchAuctionPda: anchor.web3.PublicKey;
+  let escrowA: anchor.web3.PublicKey;
+  let escrowB: anchor.web3.PublicKey;
+
+  // Sells token B against the provider wallet, which sells token A
+  const trader = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const MIN_ORDER_AMOUNT = new anchor.BN(100_000);
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  const balance = async (
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+  ) =>
+    BigInt(
+      (
+        await provider.connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(mint, owner),
+        )
+      ).value.amount,
+    );
+
+  const queueSwap = (
+    buyer: anchor.web3.PublicKey,
+    sellTokenA: boolean,
+    amount: number,
+    minReceiveAmount = 1,
+  ) =>
+    program.methods
+      .exchangeTokens(new anchor.BN(amount), new anchor.BN(minReceiveAmount))
+      .accounts({
+        buyer,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: sellTokenA ? mintA : mintB,
+        mintTo: sellTokenA ? mintB : mintA,
+        batchAuction: batchAuctionPda,
+        batchEscrow: sellTokenA ? escrowA : escrowB,
+      });
+
+  const settleBatch = async () => {
+    const batch = await program.account.batchAuction.fetch(batchAuctionPda);
+
+    // Let the slot of the batch end
+    while ((await provider.connection.getSlot()) <= batch.slot.toNumber()) {
+      await sleep(200);
+    }
+
+    await program.methods
+      .settleBatch()
+      .accounts({
+        keeper: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        escrowA,
+        escrowB,
+        vaultA: getAssociatedTokenAddressSync(mintA, liquidityPoolPda, true),
+        vaultB: getAssociatedTokenAddressSync(mintB, liquidityPoolPda, true),
+      })
+      .remainingAccounts(
+        batch.orders.flatMap((order) => [
+          { pubkey: order.tokenAccountIn, isSigner: false, isWritable: true },
+          { pubkey: order.tokenAccountOut, isSigner: false, isWritable: true },
+        ]),
+      )
+      .rpc();
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    const airdropSig = await provider.connection.requestAirdrop(
+      trader.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+    [batchAuctionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("batch_auction"), liquidityPoolPda.toBuffer()],
+      program.programId,
+    );
+    escrowA = getAssociatedTokenAddressSync(mintA, batchAuctionPda, true);
+    escrowB = getAssociatedTokenAddressSync(mintB, batchAuctionPda, true);
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      for (const owner of [provider.wallet.publicKey, trader.publicKey]) {
+        const userToken = await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          payer,
+          mint,
+          owner,
+        );
+        await mintTo(
+          provider.connection,
+          payer,
+          mint,
+          userToken.address,
+          provider.wallet.publicKey,
+          10_000_000_000,
+        );
+      }
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    // 3. Switch the pool to batch auctions
+    await program.methods
+      .initializeBatchAuction(MIN_ORDER_AMOUNT, MIN_ORDER_AMOUNT)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .rpc();
+  });
+
+  it("Queues swaps instead of executing them", async () => {
+    const beforeB = await balance(mintB, provider.wallet.publicKey);
+
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        await queueSwap(
+          provider.wallet.publicKey,
+          true,
+          10_000_000,
+        ).instruction(),
+        await queueSwap(trader.publicKey, false, 4_000_000).instruction(),
+      ),
+      [trader],
+    );
+
+    const batch = await program.account.batchAuction.fetch(batchAuctionPda);
+    assert.equal(batch.orders.length, 2);
+
+    const escrowed = await provider.connection.getTokenAccountBalance(escrowA);
+    assert.equal(escrowed.value.amount, "10000000");
+
+    const afterB = await balance(mintB, provider.wallet.publicKey);
+    assert.equal(afterB, beforeB);
+  });
+
+  it("Clears both sides of the batch at one price", async () => {
+    const walletB = await balance(mintB, provider.wallet.publicKey);
+    const traderA = await balance(mintA, trader.publicKey);
+
+    await settleBatch();
+
+    const receivedB =
+      (await balance(mintB, provider.wallet.publicKey)) - walletB;
+    const receivedA = (await balance(mintA, trader.publicKey)) - traderA;
+
+    // 4M of B crossed with part of the 10M of A, the rest went through the pool
+    assert.isTrue(receivedB > BigInt(9_800_000));
+    assert.isTrue(receivedB < BigInt(10_000_000));
+    assert.isTrue(receivedA > BigInt(4_000_000));
+
+    // The A seller gets the price the B seller pays, up to rounding
+    const priceA = (receivedB * BigInt(1_000_000)) / BigInt(10_000_000);
+    const priceB = (BigInt(4_000_000) * BigInt(1_000_000)) / receivedA;
+    assert.isTrue(priceA - priceB <= BigInt(1) && priceB - priceA <= BigInt(1));
+
+    const batch = await program.account.batchAuction.fetch(batchAuctionPda);
+    assert.equal(batch.orders.length, 0);
+  });
+
+  it("Refunds orders whose limit the clearing price misses", async () => {
+    const beforeA = await balance(mintA, provider.wallet.publicKey);
+
+    await queueSwap(
+      provider.wallet.publicKey,
+      true,
+      5_000_000,
+      6_000_000,
+    ).rpc();
+    await settleBatch();
+
+    const afterA = await balance(mintA, provider.wallet.publicKey);
+    assert.equal(afterA, beforeA);
+  });
+
+  it("Rejects orders below the minimum amount", async () => {
+    try {
+      await queueSwap(provider.wallet.publicKey, true, 99_999).rpc();
+      assert.fail(
+        "The transaction should have failed with BatchOrderTooSmall",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "BatchOrderTooSmall");
+    }
+  });
+
+  it("Queues swaps for the next batch until the last one settles", async () => {
+    await queueSwap(provider.wallet.publicKey, true, 1_000_000).rpc();
+
+    let batch = await program.account.batchAuction.fetch(batchAuctionPda);
+    while ((await provider.connection.getSlot()) <= batch.slot.toNumber()) {
+      await sleep(200);
+    }
+
+    await queueSwap(provider.wallet.publicKey, true, 2_000_000).rpc();
+
+    batch = await program.account.batchAuction.fetch(batchAuctionPda);
+    assert.equal(batch.orders.length, 1);
+    assert.equal(batch.nextOrders.length, 1);
+
+    // Settling the first batch leaves the input of the next one in escrow
+    await settleBatch();
+
+    batch = await program.account.batchAuction.fetch(batchAuctionPda);
+    assert.equal(batch.orders.length, 1);
+    assert.equal(batch.orders[0].amountIn.toNumber(), 2_000_000);
+    assert.equal(batch.nextOrders.length, 0);
+
+    const escrowed = await provider.connection.getTokenAccountBalance(escrowA);
+    assert.equal(escrowed.value.amount, "2000000");
+
+    await settleBatch();
+
+    batch = await program.account.batchAuction.fetch(batchAuctionPda);
+    assert.equal(batch.orders.length, 0);
+  });
+});
@@ -0,0 +1,326 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  getAssociatedTokenAddress,
+  mintTo,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("bin_liquidity", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let poolPda: anchor.web3.PublicKey;
+  let userTokenA: anchor.web3.PublicKey;
+  let userTokenB: anchor.web3.PublicKey;
+  let userLpToken: anchor.web3.PublicKey;
+  let binArrayLowest: anchor.web3.PublicKey;
+  let binArrayLower: anchor.web3.PublicKey;
+  let binArrayUpper: anchor.web3.PublicKey;
+
+  // Constants
+  const BIN_STEP = 25; // 0.25% between bins
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const ACTIVE_BIN_ID = 0; // price 1.0
+
+  const binArrayPda = (index: number) => {
+    const buffer = Buffer.alloc(4);
+    buffer.writeInt32LE(index);
+
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bin_array"), poolPda.toBuffer(), buffer],
+      program.programId,
+    )[0];
+  };
+
+  const binArrays = () => [
+    { pubkey: binArrayLowest, isSigner: false, isWritable: true },
+    { pubkey: binArrayLower, isSigner: false, isWritable: true },
+    { pubkey: binArrayUpper, isSigner: false, isWritable: true },
+  ];
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [poolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("bin_pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize the pool and the bin arrays covering bins [-128, 64)
+    await program.methods
+      .initializeBinPool(BIN_STEP, FEE_BPS, ACTIVE_BIN_ID)
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const index of [-2, -1, 0]) {
+      await program.methods
+        .initializeBinArray(index)
+        .accounts({
+          signer: provider.wallet.publicKey,
+          binPool: poolPda,
+        })
+        .rpc();
+    }
+
+    binArrayLowest = binArrayPda(-2);
+    binArrayLower = binArrayPda(-1);
+    binArrayUpper = binArrayPda(0);
+
+    // 3. Fund the user
+    userTokenA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        provider.wallet.publicKey,
+      )
+    ).address;
+    userTokenB = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintB,
+        provider.wallet.publicKey,
+      )
+    ).address;
+
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      userTokenA,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      userTokenB,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+
+    userLpToken = await getAssociatedTokenAddress(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+  });
+
+  it("Spreads a deposit over the bins around the active one", async () => {
+    await program.methods
+      .addBinLiquidity(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+        -70,
+        50,
+        { spot: {} },
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .remainingAccounts(binArrays())
+      .rpc();
+
+    const userLpAccount = await getAccount(provider.connection, userLpToken);
+    assert.isAbove(Number(userLpAccount.amount), 0);
+
+    const pool = await program.account.binPool.fetch(poolPda);
+    assert.notEqual(pool.binArrayBitmap.toString(), "0");
+  });
+
+  it("Swaps token A for token B through the bins", async () => {
+    const before = await getAccount(provider.connection, userTokenB);
+
+    // Bin arrays in the swap direction, each bin below the active one holds ~14.1 B, so this walks a few bins down
+    await program.methods
+      .exchangeTokensBinPool(new anchor.BN(100_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        binPool: poolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .remainingAccounts([
+        { pubkey: binArrayUpper, isSigner: false, isWritable: true },
+        { pubkey: binArrayLower, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const after = await getAccount(provider.connection, userTokenB);
+    const received = Number(after.amount) - Number(before.amount);
+
+    // Below 1:1 because of the fee and the lower bin prices
+    assert.isAbove(received, 97_000_000);
+    assert.isBelow(received, 100_000_000);
+
+    const pool = await program.account.binPool.fetch(poolPda);
+    assert.isBelow(pool.activeBinId, ACTIVE_BIN_ID);
+  });
+
+  it("Rejects a withdrawal paid out of a single bin array", async () => {
+    const lpAccount = await getAccount(provider.connection, userLpToken);
+    const half = new anchor.BN((lpAccount.amount / BigInt(2)).toString());
+
+    try {
+      await program.methods
+        .withdrawBinLiquidity(half)
+        .accounts({
+          signer: provider.wallet.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          lpMint: lpMintKeypair.publicKey,
+        })
+        .remainingAccounts([binArrays()[0]])
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with BinWithdrawalTooFewArrays",
+      );
+    } catch (err) {
+      assert.strictEqual(
+        err.error.errorCode.code,
+        "BinWithdrawalTooFewArrays",
+      );
+    }
+  });
+
+  it("Pays a withdrawal out one bin array at a time", async () => {
+    const lpAccount = await getAccount(provider.connection, userLpToken);
+    const half = new anchor.BN((lpAccount.amount / BigInt(2)).toString());
+    const beforeA = await getAccount(provider.connection, userTokenA);
+    const beforeB = await getAccount(provider.connection, userTokenB);
+
+    // Only the two lower arrays, which hold the token B side
+    await program.methods
+      .withdrawBinLiquidity(half)
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .remainingAccounts(binArrays().slice(0, 2))
+      .rpc();
+
+    let pool = await program.account.binPool.fetch(poolPda);
+    assert.notEqual(pool.pendingWithdrawal.remainingBitmap.toString(), "0");
+
+    const middleB = await getAccount(provider.connection, userTokenB);
+    assert.isAbove(Number(middleB.amount), Number(beforeB.amount));
+
+    try {
+      await program.methods
+        .addBinLiquidity(
+          new anchor.BN(1_000_000),
+          new anchor.BN(1_000_000),
+          -1,
+          1,
+          { spot: {} },
+        )
+        .accounts({
+          signer: provider.wallet.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          lpMint: lpMintKeypair.publicKey,
+        })
+        .remainingAccounts(binArrays())
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with BinWithdrawalPending",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "BinWithdrawalPending");
+    }
+
+    // Anyone can pay out the rest to the owner
+    const cranker = anchor.web3.Keypair.generate();
+    await program.methods
+      .continueBinWithdrawal()
+      .accounts({
+        signer: cranker.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        owner: provider.wallet.publicKey,
+      })
+      .remainingAccounts([binArrays()[2]])
+      .signers([cranker])
+      .rpc();
+
+    pool = await program.account.binPool.fetch(poolPda);
+    assert.equal(pool.pendingWithdrawal.remainingBitmap.toString(), "0");
+
+    const afterA = await getAccount(provider.connection, userTokenA);
+    assert.isAbove(Number(afterA.amount), Number(beforeA.amount));
+  });
+
+  it("Withdraws a share of every bin", async () => {
+    const lpAccount = await getAccount(provider.connection, userLpToken);
+    const beforeA = await getAccount(provider.connection, userTokenA);
+    const beforeB = await getAccount(provider.connection, userTokenB);
+
+    await program.methods
+      .withdrawBinLiquidity(new anchor.BN(lpAccount.amount.toString()))
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .remainingAccounts(binArrays())
+      .rpc();
+
+    const afterLp = await getAccount(provider.connection, userLpToken);
+    assert.equal(afterLp.amount.toString(), "0");
+
+    const afterA = await getAccount(provider.connection, userTokenA);
+    const afterB = await getAccount(provider.connection, userTokenB);
+    assert.isAbove(Number(afterA.amount), Number(beforeA.amount));
+    assert.isAbove(Number(afterB.amount), Number(beforeB.amount));
+
+    // The last LP tokens empty every bin array
+    const pool = await program.account.binPool.fetch(poolPda);
+    assert.equal(pool.binArrayBitmap.toString(), "0");
+  });
+});
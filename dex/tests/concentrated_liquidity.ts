@@ -0,0 +1,366 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("concentrated_liquidity", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let poolPda: anchor.web3.PublicKey;
+  let positionPda: anchor.web3.PublicKey;
+  let userTokenA: anchor.web3.PublicKey;
+  let userTokenB: anchor.web3.PublicKey;
+  let tickArrayLower: anchor.web3.PublicKey;
+  let tickArrayUpper: anchor.web3.PublicKey;
+
+  // Constants
+  const TICK_SPACING = 64;
+  const TICK_ARRAY_SPAN = 64 * TICK_SPACING;
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const SQRT_PRICE_ONE = new anchor.BN(1).shln(64); // price 1.0 in Q64.64
+  const TICK_LOWER = -640;
+  const TICK_UPPER = 640;
+  const LIQUIDITY = new anchor.BN(1_000_000_000);
+
+  const tickArrayPda = (startTickIndex: number) => {
+    const index = Buffer.alloc(4);
+    index.writeInt32LE(startTickIndex);
+
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("tick_array"), poolPda.toBuffer(), index],
+      program.programId,
+    )[0];
+  };
+
+  const tickIndexBuffer = (tick: number) => {
+    const buffer = Buffer.alloc(4);
+    buffer.writeInt32LE(tick);
+    return buffer;
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    [poolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("concentrated_pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize the pool at a price of 1.0
+    await program.methods
+      .initializeConcentratedPool(TICK_SPACING, FEE_BPS, SQRT_PRICE_ONE)
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .rpc();
+
+    // 3. Tick arrays covering [-4096, 4096)
+    for (const start of [-TICK_ARRAY_SPAN, 0]) {
+      await program.methods
+        .initializeTickArray(start)
+        .accounts({
+          signer: provider.wallet.publicKey,
+          concentratedPool: poolPda,
+        })
+        .rpc();
+    }
+
+    tickArrayLower = tickArrayPda(-TICK_ARRAY_SPAN);
+    tickArrayUpper = tickArrayPda(0);
+
+    // 4. Fund the user
+    userTokenA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        provider.wallet.publicKey,
+      )
+    ).address;
+    userTokenB = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintB,
+        provider.wallet.publicKey,
+      )
+    ).address;
+
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      userTokenA,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      userTokenB,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+
+    [positionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("position"),
+        poolPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+        tickIndexBuffer(TICK_LOWER),
+        tickIndexBuffer(TICK_UPPER),
+      ],
+      program.programId,
+    );
+  });
+
+  it("Opens a position and provides liquidity in range", async () => {
+    await program.methods
+      .openPosition(TICK_LOWER, TICK_UPPER)
+      .accounts({
+        owner: provider.wallet.publicKey,
+        concentratedPool: poolPda,
+      })
+      .rpc();
+
+    await program.methods
+      .increaseLiquidity(
+        LIQUIDITY,
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accountsPartial({
+        owner: provider.wallet.publicKey,
+        concentratedPool: poolPda,
+        position: positionPda,
+        tickArrayLower: tickArrayLower,
+        tickArrayUpper: tickArrayUpper,
+        ownerTokenAAccount: userTokenA,
+        ownerTokenBAccount: userTokenB,
+      })
+      .rpc();
+
+    const pool = await program.account.concentratedPool.fetch(poolPda);
+    assert.equal(pool.liquidity.toString(), LIQUIDITY.toString());
+
+    const position = await program.account.position.fetch(positionPda);
+    assert.equal(position.liquidity.toString(), LIQUIDITY.toString());
+  });
+
+  it("Swaps token A for token B inside the range", async () => {
+    const before = await getAccount(provider.connection, userTokenB);
+
+    await program.methods
+      .exchangeTokensConcentrated(new anchor.BN(1_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        concentratedPool: poolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .remainingAccounts([
+        { pubkey: tickArrayUpper, isSigner: false, isWritable: true },
+        { pubkey: tickArrayLower, isSigner: false, isWritable: true },
+      ])
+      .rpc();
+
+    const after = await getAccount(provider.connection, userTokenB);
+    const received = Number(after.amount) - Number(before.amount);
+
+    // Close to 1:1 minus the 0.3% fee and a little price impact
+    assert.isAbove(received, 990_000);
+    assert.isBelow(received, 1_000_000);
+
+    const pool = await program.account.concentratedPool.fetch(poolPda);
+    assert.isBelow(pool.tickCurrent, 0);
+    assert.isAbove(Number(pool.feeGrowthGlobalA), 0);
+  });
+
+  it("Collects the fees earned by the position", async () => {
+    const before = await getAccount(provider.connection, userTokenA);
+
+    await program.methods
+      .collectConcentratedFees()
+      .accountsPartial({
+        owner: provider.wallet.publicKey,
+        concentratedPool: poolPda,
+        position: positionPda,
+        tickArrayLower: tickArrayLower,
+        tickArrayUpper: tickArrayUpper,
+        ownerTokenAAccount: userTokenA,
+        ownerTokenBAccount: userTokenB,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, userTokenA);
+    const collected = Number(after.amount) - Number(before.amount);
+
+    // The only position in range earns (almost) the whole 0.3% fee
+    assert.isAbove(collected, 2_900);
+    assert.isAtMost(collected, 3_000);
+  });
+
+  it("Removes the liquidity", async () => {
+    await program.methods
+      .decreaseLiquidity(LIQUIDITY, new anchor.BN(0), new anchor.BN(0))
+      .accountsPartial({
+        owner: provider.wallet.publicKey,
+        concentratedPool: poolPda,
+        position: positionPda,
+        tickArrayLower: tickArrayLower,
+        tickArrayUpper: tickArrayUpper,
+        ownerTokenAAccount: userTokenA,
+        ownerTokenBAccount: userTokenB,
+      })
+      .rpc();
+
+    const pool = await program.account.concentratedPool.fetch(poolPda);
+    assert.equal(pool.liquidity.toString(), "0");
+
+    const position = await program.account.position.fetch(positionPda);
+    assert.equal(position.liquidity.toString(), "0");
+  });
+
+  describe("across several tick arrays", () => {
+    // A position below the price, inside the array starting at -8192 that
+    // follows the one holding the current tick
+    const FAR_LOWER = -6400;
+    const FAR_UPPER = -4800;
+    let farPositionPda: anchor.web3.PublicKey;
+    let tickArrayFar: anchor.web3.PublicKey;
+    let tickArrayBeyond: anchor.web3.PublicKey;
+
+    const swapThrough = (tickArrays: anchor.web3.PublicKey[]) =>
+      program.methods
+        .exchangeTokensConcentrated(new anchor.BN(10_000_000), new anchor.BN(1))
+        .accounts({
+          buyer: provider.wallet.publicKey,
+          concentratedPool: poolPda,
+          mintFrom: mintA,
+          mintTo: mintB,
+        })
+        .remainingAccounts(
+          tickArrays.map((pubkey) => ({
+            pubkey,
+            isSigner: false,
+            isWritable: true,
+          })),
+        )
+        .rpc();
+
+    before(async () => {
+      for (const start of [-2 * TICK_ARRAY_SPAN, -3 * TICK_ARRAY_SPAN]) {
+        await program.methods
+          .initializeTickArray(start)
+          .accounts({
+            signer: provider.wallet.publicKey,
+            concentratedPool: poolPda,
+          })
+          .rpc();
+      }
+
+      tickArrayFar = tickArrayPda(-2 * TICK_ARRAY_SPAN);
+      tickArrayBeyond = tickArrayPda(-3 * TICK_ARRAY_SPAN);
+
+      [farPositionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("position"),
+          poolPda.toBuffer(),
+          provider.wallet.publicKey.toBuffer(),
+          tickIndexBuffer(FAR_LOWER),
+          tickIndexBuffer(FAR_UPPER),
+        ],
+        program.programId,
+      );
+
+      await program.methods
+        .openPosition(FAR_LOWER, FAR_UPPER)
+        .accounts({
+          owner: provider.wallet.publicKey,
+          concentratedPool: poolPda,
+        })
+        .rpc();
+
+      // Entirely below the price, the position only holds token B
+      await program.methods
+        .increaseLiquidity(
+          LIQUIDITY,
+          new anchor.BN(1_000_000),
+          new anchor.BN(1_000_000_000),
+        )
+        .accountsPartial({
+          owner: provider.wallet.publicKey,
+          concentratedPool: poolPda,
+          position: farPositionPda,
+          tickArrayLower: tickArrayFar,
+          tickArrayUpper: tickArrayFar,
+          ownerTokenAAccount: userTokenA,
+          ownerTokenBAccount: userTokenB,
+        })
+        .rpc();
+    });
+
+    it("Rejects tick arrays that skip one", async () => {
+      try {
+        await swapThrough([tickArrayLower, tickArrayBeyond]);
+        assert.fail("The transaction should have failed with InvalidTickArray");
+      } catch (err) {
+        assert.strictEqual(err.error.errorCode.code, "InvalidTickArray");
+      }
+    });
+
+    it("Crosses into the liquidity of a later array", async () => {
+      const before = await getAccount(provider.connection, userTokenB);
+
+      await swapThrough([tickArrayLower, tickArrayFar]);
+
+      // The price left the empty array and crossed the upper tick of the far
+      // position, whose liquidity is now active
+      const pool = await program.account.concentratedPool.fetch(poolPda);
+      assert.isBelow(pool.tickCurrent, FAR_UPPER);
+      assert.isAtLeast(pool.tickCurrent, FAR_LOWER);
+      assert.equal(pool.liquidity.toString(), LIQUIDITY.toString());
+
+      // About 0.62 B per A at tick -4800, minus the fee
+      const after = await getAccount(provider.connection, userTokenB);
+      const received = Number(after.amount) - Number(before.amount);
+      assert.isAbove(received, 5_500_000);
+      assert.isBelow(received, 6_200_000);
+    });
+  });
+});
@@ -0,0 +1,251 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("dca", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let dcaOrderPda: anchor.web3.PublicKey;
+  let escrow: anchor.web3.PublicKey;
+  let keeperTokenAccount: anchor.web3.PublicKey;
+
+  const keeper = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const ORDER_ID = new anchor.BN(1);
+  const AMOUNT_PER_CYCLE = new anchor.BN(1_000_000);
+  const CYCLE_INTERVAL = new anchor.BN(2); // seconds
+  const MIN_OUT_PER_CYCLE = new anchor.BN(980_000);
+  const KEEPER_TIP = BigInt(1_000); // 0.1% of a cycle
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  const balance = async (address: anchor.web3.PublicKey) =>
+    BigInt(
+      (await provider.connection.getTokenAccountBalance(address)).value.amount,
+    );
+
+  const ownerTokenAccount = (mint: anchor.web3.PublicKey) =>
+    getAssociatedTokenAddressSync(mint, provider.wallet.publicKey);
+
+  const executeDca = () =>
+    program.methods
+      .executeDca()
+      .accounts({
+        keeper: keeper.publicKey,
+        liquidityPool: liquidityPoolPda,
+        dcaOrder: dcaOrderPda,
+        mintIn: mintA,
+        mintOut: mintB,
+        escrow,
+        keeperTokenAccount,
+      })
+      .signers([keeper])
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    keeperTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        keeper.publicKey,
+      )
+    ).address;
+
+    [dcaOrderPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("dca_order"),
+        liquidityPoolPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+        ORDER_ID.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId,
+    );
+    escrow = getAssociatedTokenAddressSync(mintA, dcaOrderPda, true);
+  });
+
+  it("Rejects an order without amount per cycle", async () => {
+    try {
+      await program.methods
+        .openDcaOrder(
+          ORDER_ID,
+          new anchor.BN(2_500_000),
+          new anchor.BN(0),
+          CYCLE_INTERVAL,
+          MIN_OUT_PER_CYCLE,
+        )
+        .accounts({
+          owner: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintIn: mintA,
+          mintOut: mintB,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with InvalidDcaOrder");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidDcaOrder");
+    }
+  });
+
+  it("Opens an order escrowing the deposit", async () => {
+    await program.methods
+      .openDcaOrder(
+        ORDER_ID,
+        new anchor.BN(2_500_000),
+        AMOUNT_PER_CYCLE,
+        CYCLE_INTERVAL,
+        MIN_OUT_PER_CYCLE,
+      )
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintIn: mintA,
+        mintOut: mintB,
+      })
+      .rpc();
+
+    const order = await program.account.dcaOrder.fetch(dcaOrderPda);
+    assert.ok(order.escrow.equals(escrow));
+    assert.equal(order.amountPerCycle.toString(), "1000000");
+    assert.equal((await balance(escrow)).toString(), "2500000");
+  });
+
+  it("Lets a keeper execute one cycle and collect the tip", async () => {
+    const beforeB = await balance(ownerTokenAccount(mintB));
+
+    await executeDca();
+
+    const received = (await balance(ownerTokenAccount(mintB))) - beforeB;
+
+    // 999_000 of A swapped into a 1B:1B pool at 0.3%
+    assert.isTrue(received >= BigInt(MIN_OUT_PER_CYCLE.toString()));
+    assert.isTrue(received < BigInt(999_000));
+    assert.equal(await balance(keeperTokenAccount), KEEPER_TIP);
+    assert.equal((await balance(escrow)).toString(), "1500000");
+  });
+
+  it("Rejects a cycle before the interval has passed", async () => {
+    try {
+      await executeDca();
+      assert.fail("The transaction should have failed with DcaCycleNotDue");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "DcaCycleNotDue");
+    }
+  });
+
+  it("Executes the next cycle once it is due", async () => {
+    await sleep(3000);
+    await executeDca();
+
+    assert.equal((await balance(escrow)).toString(), "500000");
+    assert.equal(await balance(keeperTokenAccount), KEEPER_TIP * BigInt(2));
+  });
+
+  it("Refunds the rest and closes the order", async () => {
+    const beforeA = await balance(ownerTokenAccount(mintA));
+
+    await program.methods
+      .closeDcaOrder()
+      .accounts({
+        dcaOrder: dcaOrderPda,
+        mintIn: mintA,
+        escrow,
+      })
+      .rpc();
+
+    const refunded = (await balance(ownerTokenAccount(mintA))) - beforeA;
+
+    assert.equal(refunded.toString(), "500000");
+    assert.isNull(await provider.connection.getAccountInfo(dcaOrderPda));
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
+});
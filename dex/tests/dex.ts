@@ -74,7 +74,7 @@ describe("dex", () => {
 
     try {
       await program.methods
-        .initialize(BAD_FEE_BPS)
+        .initialize(BAD_FEE_BPS, { constantProduct: {} })
         .accounts({
           signer: provider.wallet.publicKey,
           mintA: mintA,
@@ -93,7 +93,7 @@ describe("dex", () => {
 
   it("Is initialized!", async () => {
     const tx = await program.methods
-      .initialize(FEE_BPS)
+      .initialize(FEE_BPS, { constantProduct: {} })
       .accounts({
         signer: provider.wallet.publicKey,
         mintA: mintA,
@@ -115,9 +115,19 @@ describe("dex", () => {
       poolAccount.lpMint.equals(lpMintKeypair.publicKey),
       "LP Mint should match",
     );
-    assert.ok(poolAccount.feeBps.eq(FEE_BPS), "Fee BPS should match");
-
-    // Note: mintA and mintB are not currently set in the instruction implementation
-    // so we skip asserting them to match existing code behavior.
+    assert.ok(
+      poolAccount.feeBpsAToB.eq(FEE_BPS),
+      "A to B fee BPS should match",
+    );
+    assert.ok(
+      poolAccount.feeBpsBToA.eq(FEE_BPS),
+      "B to A fee BPS should match",
+    );
+    assert.ok(poolAccount.mintA.equals(mintA), "Mint A should match");
+    assert.ok(poolAccount.mintB.equals(mintB), "Mint B should match");
+    assert.ok(
+      poolAccount.admin.equals(provider.wallet.publicKey),
+      "Admin should be the creator",
+    );
   });
 });
@@ -0,0 +1,216 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("directional_fees", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const BUY_FEE_BPS = new anchor.BN(10); // 0.1% when selling token B
+  const SELL_FEE_BPS = new anchor.BN(500); // 5% when selling token A
+  const SWAP_AMOUNT = new anchor.BN(1_000_000);
+
+  const quote = (
+    mintFrom: anchor.web3.PublicKey,
+    mintTo: anchor.web3.PublicKey,
+  ) =>
+    program.methods
+      .quoteExchange(SWAP_AMOUNT)
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintFrom,
+        mintTo,
+      })
+      .view();
+
+  const setSwapFees = (feeBpsAToB: anchor.BN, feeBpsBToA: anchor.BN) =>
+    program.methods
+      .setSwapFees(feeBpsAToB, feeBpsBToA)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Starts with the same fee in both directions", async () => {
+    const poolAccount = await program.account.pool.fetch(liquidityPoolPda);
+
+    assert.equal(poolAccount.feeBpsAToB.toString(), FEE_BPS.toString());
+    assert.equal(poolAccount.feeBpsBToA.toString(), FEE_BPS.toString());
+  });
+
+  it("Rejects an out of range fee", async () => {
+    try {
+      await setSwapFees(new anchor.BN(10_001), BUY_FEE_BPS);
+      assert.fail("The transaction should have failed with InvalidBPSValue");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidBPSValue");
+    }
+  });
+
+  it("Rejects a fee above the cap of an enabled fee model", async () => {
+    await program.methods
+      .setImpactFee({
+        thresholdBps: new anchor.BN(100),
+        curve: { linear: {} },
+        coefficientBps: new anchor.BN(1000),
+        maxFeeBps: new anchor.BN(300),
+      })
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+    try {
+      await setSwapFees(SELL_FEE_BPS, BUY_FEE_BPS);
+      assert.fail("The transaction should have failed with InvalidFeeConfig");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidFeeConfig");
+    }
+
+    await program.methods
+      .setImpactFee(null)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+  });
+
+  it("Applies the fee matching the swap direction", async () => {
+    await setSwapFees(SELL_FEE_BPS, BUY_FEE_BPS);
+
+    const sell = await quote(mintA, mintB);
+    assert.equal(sell.feeBps.toString(), SELL_FEE_BPS.toString());
+    assert.equal(sell.feeAmount.toNumber(), 50_000);
+
+    const buy = await quote(mintB, mintA);
+    assert.equal(buy.feeBps.toString(), BUY_FEE_BPS.toString());
+    assert.equal(buy.feeAmount.toNumber(), 1_000);
+
+    assert.isAbove(buy.amountOut.toNumber(), sell.amountOut.toNumber());
+  });
+
+  it("Charges the directional fee on the swap", async () => {
+    const expected = await quote(mintA, mintB);
+
+    const userTokenB = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      mintB,
+      provider.wallet.publicKey,
+    );
+    const balanceBefore = (
+      await provider.connection.getTokenAccountBalance(userTokenB.address)
+    ).value.amount;
+
+    await program.methods
+      .exchangeTokens(SWAP_AMOUNT, expected.amountOut)
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    const balanceAfter = (
+      await provider.connection.getTokenAccountBalance(userTokenB.address)
+    ).value.amount;
+
+    assert.equal(
+      (BigInt(balanceAfter) - BigInt(balanceBefore)).toString(),
+      expected.amountOut.toString(),
+    );
+  });
+});
@@ -0,0 +1,196 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("dynamic_fees", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const SWAP_AMOUNT = new anchor.BN(100_000_000); // 10% of the pool
+  const DYNAMIC_FEE_CONFIG = {
+    filterPeriod: new anchor.BN(60),
+    decayPeriod: new anchor.BN(600),
+    reductionFactorBps: new anchor.BN(5000),
+    variableFeeControl: new anchor.BN(10),
+    maxFeeBps: new anchor.BN(500),
+  };
+
+  const quote = () =>
+    program.methods
+      .quoteExchange(SWAP_AMOUNT)
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .view();
+
+  const swapAndGetEvent = async () => {
+    const signature = await program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = [...parser.parseLogs(transaction.meta.logMessages)];
+
+    return events.find((event) => event.name === "swapEvent").data;
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Quotes the static fee until dynamic fees are enabled", async () => {
+    const result = await quote();
+
+    assert.equal(result.feeBps.toString(), FEE_BPS.toString());
+    assert.isAbove(result.amountOut.toNumber(), 0);
+  });
+
+  it("Rejects an invalid configuration", async () => {
+    try {
+      await program.methods
+        .setDynamicFee({ ...DYNAMIC_FEE_CONFIG, maxFeeBps: new anchor.BN(10) })
+        .accounts({
+          admin: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with InvalidFeeConfig");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidFeeConfig");
+    }
+  });
+
+  it("Raises the fee after a large price move", async () => {
+    await program.methods
+      .setDynamicFee(DYNAMIC_FEE_CONFIG)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+    // No volatility yet, the first swap pays the base fee
+    const first = await swapAndGetEvent();
+    assert.equal(first.feeBps.toString(), FEE_BPS.toString());
+
+    // The ~20% price move now shows up in quotes and in the next swap
+    const result = await quote();
+    assert.isAbove(result.feeBps.toNumber(), FEE_BPS.toNumber());
+    assert.isAtMost(result.feeBps.toNumber(), 500);
+
+    const second = await swapAndGetEvent();
+    assert.equal(second.feeBps.toString(), result.feeBps.toString());
+    assert.equal(second.feeAmount.toString(), result.feeAmount.toString());
+  });
+
+  it("Goes back to the static fee when disabled", async () => {
+    await program.methods
+      .setDynamicFee(null)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+    const result = await quote();
+    assert.equal(result.feeBps.toString(), FEE_BPS.toString());
+  });
+});
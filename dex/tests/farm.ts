@@ -0,0 +1,462 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("farm", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let rewardMint: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let farmPda: anchor.web3.PublicKey;
+  let farmStakePda: anchor.web3.PublicKey;
+  let lpVault: anchor.web3.PublicKey;
+  let coRewardMint: anchor.web3.PublicKey;
+
+  // Funds a second reward of the farm
+  const coFunder = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const REWARD_PER_SECOND = 1_000;
+  const CO_REWARD_PER_SECOND = 500;
+  const CO_REWARD_FUNDING = 2_000;
+  const SCHEDULE_SECONDS = 1_000_000;
+  const STAKE_AMOUNT = 1_000_000;
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  const balance = async (
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+  ) =>
+    BigInt(
+      (
+        await provider.connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(mint, owner, true),
+        )
+      ).value.amount,
+    );
+
+  const farmAccounts = () => ({
+    owner: provider.wallet.publicKey,
+    farm: farmPda,
+    lpVault,
+  });
+
+  const claimRewards = (rewardIndex: number, mint: anchor.web3.PublicKey) =>
+    program.methods
+      .claimRewards(rewardIndex)
+      .accounts({
+        owner: provider.wallet.publicKey,
+        farm: farmPda,
+        rewardMint: mint,
+      })
+      .rpc();
+
+  const now = async () =>
+    (await provider.connection.getBlockTime(
+      await provider.connection.getSlot(),
+    ))!;
+
+  const waitForTime = async (ts: number) => {
+    while ((await now()) < ts) {
+      await sleep(200);
+    }
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    const airdropSig = await provider.connection.requestAirdrop(
+      coFunder.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    rewardMint = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    coRewardMint = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+    [farmPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("farm"), liquidityPoolPda.toBuffer()],
+      program.programId,
+    );
+    [farmStakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("farm_stake"),
+        farmPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+    lpVault = getAssociatedTokenAddressSync(
+      lpMintKeypair.publicKey,
+      farmPda,
+      true,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    for (const [mint, owner] of [
+      [rewardMint, provider.wallet.publicKey],
+      [coRewardMint, coFunder.publicKey],
+    ]) {
+      const funderToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        owner,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        funderToken.address,
+        provider.wallet.publicKey,
+        1_000_000_000,
+      );
+    }
+
+    // 3. Open the farm and schedule its first reward
+    await program.methods
+      .initializeFarm()
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    const startTs = await now();
+
+    await program.methods
+      .addFarmReward(
+        provider.wallet.publicKey,
+        new anchor.BN(REWARD_PER_SECOND),
+        new anchor.BN(startTs),
+        new anchor.BN(startTs + SCHEDULE_SECONDS),
+      )
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        rewardMint,
+      })
+      .rpc();
+
+    await program.methods
+      .fundReward(0, new anchor.BN(500_000_000))
+      .accounts({
+        funder: provider.wallet.publicKey,
+        farm: farmPda,
+        rewardMint,
+      })
+      .rpc();
+  });
+
+  it("Stakes LP tokens in the farm", async () => {
+    const beforeLp = await balance(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+
+    await program.methods
+      .stakeLp(new anchor.BN(STAKE_AMOUNT))
+      .accounts(farmAccounts())
+      .rpc();
+
+    const afterLp = await balance(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+    assert.equal(beforeLp - afterLp, BigInt(STAKE_AMOUNT));
+
+    const farm = await program.account.farm.fetch(farmPda);
+    assert.equal(farm.totalStaked.toNumber(), STAKE_AMOUNT);
+
+    const stake = await program.account.farmStake.fetch(farmStakePda);
+    assert.equal(stake.amount.toNumber(), STAKE_AMOUNT);
+    assert.isTrue(stake.owner.equals(provider.wallet.publicKey));
+  });
+
+  it("Accrues rewards per second to stakers", async () => {
+    const stakedTs = (await program.account.farm.fetch(farmPda))
+      .lastUpdateTs;
+    const before = await balance(rewardMint, provider.wallet.publicKey);
+
+    await waitForTime(stakedTs.toNumber() + 3);
+    await claimRewards(0, rewardMint);
+
+    const farm = await program.account.farm.fetch(farmPda);
+    const seconds = farm.lastUpdateTs.sub(stakedTs).toNumber();
+    const received =
+      (await balance(rewardMint, provider.wallet.publicKey)) - before;
+
+    // The only staker gets every emitted reward, up to rounding
+    assert.isTrue(seconds >= 3);
+    assert.equal(
+      farm.rewards[0].amountEmitted.toNumber(),
+      seconds * REWARD_PER_SECOND,
+    );
+    assert.isTrue(received <= BigInt(seconds * REWARD_PER_SECOND));
+    assert.isTrue(received >= BigInt(seconds * REWARD_PER_SECOND - 1));
+  });
+
+  it("Emits a co-incentive only as far as it is funded", async () => {
+    const startTs = await now();
+
+    await program.methods
+      .addFarmReward(
+        coFunder.publicKey,
+        new anchor.BN(CO_REWARD_PER_SECOND),
+        new anchor.BN(startTs),
+        new anchor.BN(startTs + SCHEDULE_SECONDS),
+      )
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        rewardMint: coRewardMint,
+      })
+      .rpc();
+
+    await program.methods
+      .fundReward(1, new anchor.BN(CO_REWARD_FUNDING))
+      .accounts({
+        funder: coFunder.publicKey,
+        farm: farmPda,
+        rewardMint: coRewardMint,
+      })
+      .signers([coFunder])
+      .rpc();
+
+    // Long enough to emit more than the funding
+    const fundedTs = (await program.account.farm.fetch(farmPda))
+      .lastUpdateTs;
+    await waitForTime(
+      fundedTs.toNumber() + CO_REWARD_FUNDING / CO_REWARD_PER_SECOND + 2,
+    );
+    await claimRewards(1, coRewardMint);
+
+    const farm = await program.account.farm.fetch(farmPda);
+    assert.equal(farm.rewards.length, 2);
+    assert.equal(farm.rewards[1].amountEmitted.toNumber(), CO_REWARD_FUNDING);
+
+    const received = await balance(coRewardMint, provider.wallet.publicKey);
+    assert.isTrue(received <= BigInt(CO_REWARD_FUNDING));
+    assert.isTrue(received >= BigInt(CO_REWARD_FUNDING - 1));
+
+    // Rewards are owed independently, the first one is still accruing
+    const stake = await program.account.farmStake.fetch(farmStakePda);
+    assert.equal(stake.rewardsOwed[1].toNumber(), 0);
+    assert.isTrue(stake.rewardsOwed[0].toNumber() > 0);
+  });
+
+  it("Rejects extending a reward by anyone but its funder", async () => {
+    try {
+      await program.methods
+        .extendReward(
+          1,
+          new anchor.BN(Number.MAX_SAFE_INTEGER),
+          new anchor.BN(CO_REWARD_PER_SECOND),
+        )
+        .accounts({
+          funder: provider.wallet.publicKey,
+          farm: farmPda,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with Unauthorized");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+    }
+  });
+
+  it("Extends a reward schedule", async () => {
+    const reward = (await program.account.farm.fetch(farmPda)).rewards[1];
+    const endTs = reward.endTs.addn(100);
+
+    await program.methods
+      .extendReward(1, endTs, new anchor.BN(2 * CO_REWARD_PER_SECOND))
+      .accounts({
+        funder: coFunder.publicKey,
+        farm: farmPda,
+      })
+      .signers([coFunder])
+      .rpc();
+
+    const extended = (await program.account.farm.fetch(farmPda)).rewards[1];
+    assert.equal(extended.endTs.toString(), endTs.toString());
+    assert.equal(
+      extended.rewardPerSecond.toNumber(),
+      2 * CO_REWARD_PER_SECOND,
+    );
+
+    // Neither shortening the schedule nor lowering its rate is allowed
+    for (const [end, rate] of [
+      [reward.endTs, 2 * CO_REWARD_PER_SECOND],
+      [endTs.addn(100), CO_REWARD_PER_SECOND],
+    ]) {
+      try {
+        await program.methods
+          .extendReward(1, new anchor.BN(end), new anchor.BN(rate))
+          .accounts({
+            funder: coFunder.publicKey,
+            farm: farmPda,
+          })
+          .signers([coFunder])
+          .rpc();
+        assert.fail(
+          "The transaction should have failed with InvalidRewardSchedule",
+        );
+      } catch (err) {
+        assert.strictEqual(
+          err.error.errorCode.code,
+          "InvalidRewardSchedule",
+        );
+      }
+    }
+  });
+
+  it("Rejects unstaking more than staked", async () => {
+    try {
+      await program.methods
+        .unstakeLp(new anchor.BN(STAKE_AMOUNT + 1))
+        .accounts(farmAccounts())
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with InsufficientLPTokens",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InsufficientLPTokens");
+    }
+  });
+
+  it("Keeps rewards owed after unstaking", async () => {
+    const beforeLp = await balance(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+
+    await program.methods
+      .unstakeLp(new anchor.BN(STAKE_AMOUNT))
+      .accounts(farmAccounts())
+      .rpc();
+
+    const afterLp = await balance(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+    assert.equal(afterLp - beforeLp, BigInt(STAKE_AMOUNT));
+
+    const stake = await program.account.farmStake.fetch(farmStakePda);
+    assert.equal(stake.amount.toNumber(), 0);
+    assert.isTrue(stake.rewardsOwed[0].toNumber() > 0);
+
+    const beforeRewards = await balance(rewardMint, provider.wallet.publicKey);
+    await claimRewards(0, rewardMint);
+    const afterRewards = await balance(rewardMint, provider.wallet.publicKey);
+    assert.equal(
+      (afterRewards - beforeRewards).toString(),
+      stake.rewardsOwed[0].toString(),
+    );
+
+    // Nothing is emitted while nothing is staked
+    const emitted = (await program.account.farm.fetch(farmPda)).rewards[0]
+      .amountEmitted;
+    await waitForTime((await now()) + 2);
+    await claimRewards(0, rewardMint);
+
+    const farm = await program.account.farm.fetch(farmPda);
+    assert.equal(farm.totalStaked.toNumber(), 0);
+    assert.equal(farm.rewards[0].amountEmitted.toString(), emitted.toString());
+  });
+});
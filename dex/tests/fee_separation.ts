@@ -0,0 +1,275 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("fee_separation", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let positionPda: anchor.web3.PublicKey;
+  let feeVaultAPda: anchor.web3.PublicKey;
+  const positionMintKeypair = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const POOL_AMOUNT = 1_000_000_000;
+  const POSITION_AMOUNT = 100_000_000;
+  const SWAP_AMOUNT = new anchor.BN(50_000_000);
+
+  const quotePosition = () =>
+    program.methods
+      .quoteLpPosition()
+      .accountsPartial({
+        liquidityPool: liquidityPoolPda,
+        position: positionPda,
+      })
+      .view();
+
+  const balance = async (mint: anchor.web3.PublicKey) =>
+    (
+      await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, provider.wallet.publicKey),
+      )
+    ).amount;
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+    [positionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("lp_position"),
+        liquidityPoolPda.toBuffer(),
+        positionMintKeypair.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+    [feeVaultAPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("fee_vault"), liquidityPoolPda.toBuffer(), mintA.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(POOL_AMOUNT),
+        new anchor.BN(POOL_AMOUNT),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    // 3. Open the position the separated fees go to
+    await program.methods
+      .openLpPosition(
+        new anchor.BN(POSITION_AMOUNT),
+        new anchor.BN(POSITION_AMOUNT),
+      )
+      .accounts({
+        owner: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        positionMint: positionMintKeypair.publicKey,
+      })
+      .signers([positionMintKeypair])
+      .rpc();
+  });
+
+  it("Only lets the admin turn fee separation on", async () => {
+    const stranger = anchor.web3.Keypair.generate();
+    const airdropSig = await provider.connection.requestAirdrop(
+      stranger.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    try {
+      await program.methods
+        .setFeeSeparation(true)
+        .accounts({
+          admin: stranger.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintA,
+          mintB,
+        })
+        .signers([stranger])
+        .rpc();
+      assert.fail("The transaction should have failed with Unauthorized");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+    }
+
+    await program.methods
+      .setFeeSeparation(true)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintA,
+        mintB,
+      })
+      .rpc();
+
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.isTrue(pool.separateFees);
+  });
+
+  it("Sets the whole swap fee apart for the staked LP tokens", async () => {
+    const before = await program.account.pool.fetch(liquidityPoolPda);
+    const vaultBefore = await getAccount(provider.connection, before.vaultA);
+
+    await program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    // The position holds the only staked LP tokens, it earns the whole fee
+    const fee = BigInt(SWAP_AMOUNT.toNumber() * 0.003);
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(pool.feesA.toString(), fee.toString());
+    assert.equal(pool.feesB.toNumber(), 0);
+    assert.isTrue(pool.feeGrowthGlobalA.gt(before.feeGrowthGlobalA));
+
+    // None of it compounds into the reserves of the fungible LP tokens
+    const vaultAfter = await getAccount(provider.connection, pool.vaultA);
+    const reserveGain =
+      vaultAfter.amount - vaultBefore.amount - BigInt(pool.feesA.toString());
+    assert.equal(reserveGain, BigInt(SWAP_AMOUNT.toString()) - fee);
+
+    const quote = await quotePosition();
+    assert.approximately(quote.uncollectedA.toNumber(), Number(fee), 1);
+    assert.equal(quote.uncollectedB.toNumber(), 0);
+  });
+
+  it("Keeps the position open while it has fees to collect", async () => {
+    try {
+      await program.methods
+        .closeLpPosition()
+        .accountsPartial({
+          owner: provider.wallet.publicKey,
+          mintA,
+          mintB,
+          positionMint: positionMintKeypair.publicKey,
+          position: positionPda,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with UncollectedLpFees");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "UncollectedLpFees");
+    }
+  });
+
+  it("Pays the separated fees to the NFT holder", async () => {
+    const quote = await quotePosition();
+    const balanceBefore = await balance(mintA);
+
+    await program.methods
+      .collectFees()
+      .accountsPartial({
+        owner: provider.wallet.publicKey,
+        mintA,
+        mintB,
+        positionMint: positionMintKeypair.publicKey,
+        position: positionPda,
+      })
+      .rpc();
+
+    assert.equal(
+      await balance(mintA),
+      balanceBefore + BigInt(quote.uncollectedA.toString()),
+    );
+
+    // The fees left the pool vault, only rounding dust stays in the fee vault
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(pool.feesA.toNumber(), 0);
+    const feeVault = await getAccount(provider.connection, feeVaultAPda);
+    assert.isTrue(feeVault.amount <= BigInt(1));
+
+    const position = await program.account.lpPosition.fetch(positionPda);
+    assert.equal(
+      position.feesCollectedA.toString(),
+      quote.uncollectedA.toString(),
+    );
+
+    const after = await quotePosition();
+    assert.equal(after.uncollectedA.toNumber(), 0);
+    assert.equal(after.collectedA.toString(), quote.uncollectedA.toString());
+  });
+});
@@ -0,0 +1,201 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("impact_fees", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const SMALL_SWAP = new anchor.BN(5_000_000); // 0.5% of the pool
+  const LARGE_SWAP = new anchor.BN(100_000_000); // 10% of the pool
+  const IMPACT_FEE_CONFIG = {
+    thresholdBps: new anchor.BN(100),
+    curve: { linear: {} },
+    coefficientBps: new anchor.BN(1000),
+    maxFeeBps: new anchor.BN(300),
+  };
+
+  const quote = (amount: anchor.BN) =>
+    program.methods
+      .quoteExchange(amount)
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .view();
+
+  const setImpactFee = (config) =>
+    program.methods
+      .setImpactFee(config)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Rejects an invalid configuration", async () => {
+    try {
+      await setImpactFee({
+        ...IMPACT_FEE_CONFIG,
+        maxFeeBps: new anchor.BN(10),
+      });
+      assert.fail("The transaction should have failed with InvalidFeeConfig");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidFeeConfig");
+    }
+  });
+
+  it("Charges the base fee below the threshold", async () => {
+    await setImpactFee(IMPACT_FEE_CONFIG);
+
+    const result = await quote(SMALL_SWAP);
+    assert.equal(result.feeBps.toString(), FEE_BPS.toString());
+  });
+
+  it("Adds a linear surcharge above the threshold", async () => {
+    // 10% of the reserve is 900 bps over the threshold: 30 + 1000 * 900 / 10000
+    const result = await quote(LARGE_SWAP);
+    assert.equal(result.feeBps.toNumber(), 120);
+
+    const capped = await quote(new anchor.BN(500_000_000));
+    assert.equal(capped.feeBps.toNumber(), 300);
+  });
+
+  it("Adds a quadratic surcharge above the threshold", async () => {
+    await setImpactFee({ ...IMPACT_FEE_CONFIG, curve: { quadratic: {} } });
+
+    // 30 + 1000 * 900^2 / 10000^2
+    const result = await quote(LARGE_SWAP);
+    assert.equal(result.feeBps.toNumber(), 38);
+  });
+
+  it("Charges the quoted fee on the swap", async () => {
+    const result = await quote(LARGE_SWAP);
+
+    const userTokenB = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      (provider.wallet as anchor.Wallet).payer,
+      mintB,
+      provider.wallet.publicKey,
+    );
+    const balanceBefore = (
+      await provider.connection.getTokenAccountBalance(userTokenB.address)
+    ).value.amount;
+
+    await program.methods
+      .exchangeTokens(LARGE_SWAP, result.amountOut)
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    const balanceAfter = (
+      await provider.connection.getTokenAccountBalance(userTokenB.address)
+    ).value.amount;
+
+    assert.equal(
+      (BigInt(balanceAfter) - BigInt(balanceBefore)).toString(),
+      result.amountOut.toString(),
+    );
+  });
+
+  it("Goes back to the base fee when disabled", async () => {
+    await setImpactFee(null);
+
+    const result = await quote(LARGE_SWAP);
+    assert.equal(result.feeBps.toString(), FEE_BPS.toString());
+  });
+});
@@ -0,0 +1,263 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  approve,
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("intents", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let intentAuthorityPda: anchor.web3.PublicKey;
+
+  // The user holds no SOL, the provider wallet relays its intents
+  const user = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const USER_BALANCE = 100_000_000;
+
+  const balance = async (
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+  ) =>
+    BigInt(
+      (
+        await provider.connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(mint, owner),
+        )
+      ).value.amount,
+    );
+
+  const makeIntent = async (
+    nonce: number,
+    amountIn: number,
+    relayerTipBps = 50,
+    expiresIn = 60,
+  ) => {
+    const now = (await provider.connection.getBlockTime(
+      await provider.connection.getSlot(),
+    ))!;
+
+    return {
+      owner: user.publicKey,
+      pool: liquidityPoolPda,
+      mintIn: mintA,
+      amountIn: new anchor.BN(amountIn),
+      minAmountOut: new anchor.BN(1),
+      relayerTipBps: new anchor.BN(relayerTipBps),
+      deadline: new anchor.BN(now + expiresIn),
+      nonce: new anchor.BN(nonce),
+    };
+  };
+
+  type Intent = Awaited<ReturnType<typeof makeIntent>>;
+
+  const signIntent = (intent: Intent) =>
+    anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: user.secretKey,
+      message: program.coder.types.encode("swapIntent", intent),
+    });
+
+  const executeIntent = (intent: Intent, signedIntent: Intent = intent) =>
+    program.methods
+      .executeIntent(intent)
+      .accounts({
+        relayer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintIn: mintA,
+        mintOut: mintB,
+        relayerTokenAccount: getAssociatedTokenAddressSync(
+          mintB,
+          provider.wallet.publicKey,
+        ),
+      })
+      .preInstructions([signIntent(signedIntent)])
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+    [intentAuthorityPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("intent_authority")],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    // 3. Fund the user, who lets the intent authority spend its token A
+    const userTokenA = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintA,
+      user.publicKey,
+    );
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintB,
+      user.publicKey,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      userTokenA.address,
+      provider.wallet.publicKey,
+      USER_BALANCE,
+    );
+
+    await approve(
+      provider.connection,
+      payer,
+      userTokenA.address,
+      intentAuthorityPda,
+      user,
+      USER_BALANCE,
+    );
+  });
+
+  it("Swaps a signed intent submitted by a relayer", async () => {
+    const intent = await makeIntent(1, 10_000_000);
+    const userA = await balance(mintA, user.publicKey);
+    const userB = await balance(mintB, user.publicKey);
+    const relayerB = await balance(mintB, provider.wallet.publicKey);
+
+    await executeIntent(intent);
+
+    const paid = userA - (await balance(mintA, user.publicKey));
+    assert.equal(paid.toString(), "10000000");
+
+    // The relayer is tipped 0.5% of the output
+    const received = (await balance(mintB, user.publicKey)) - userB;
+    const tip = (await balance(mintB, provider.wallet.publicKey)) - relayerB;
+    assert.equal(tip, ((received + tip) * BigInt(50)) / BigInt(10_000));
+    assert.isTrue(received + tip > BigInt(9_800_000));
+    assert.isTrue(received + tip < BigInt(10_000_000));
+  });
+
+  it("Rejects a replayed intent", async () => {
+    const intent = await makeIntent(2, 1_000_000);
+    await executeIntent(intent);
+
+    // The nonce account of the intent already exists
+    const replayed = await executeIntent(intent).then(
+      () => true,
+      () => false,
+    );
+    assert.isFalse(replayed);
+  });
+
+  it("Rejects a relayer tip above the cap", async () => {
+    const intent = await makeIntent(3, 1_000_000, 101);
+
+    try {
+      await executeIntent(intent);
+      assert.fail("The transaction should have failed with RelayerTipTooHigh");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "RelayerTipTooHigh");
+    }
+  });
+
+  it("Rejects an intent the user did not sign", async () => {
+    const signed = await makeIntent(4, 1_000_000);
+    const tampered = { ...signed, amountIn: new anchor.BN(50_000_000) };
+
+    try {
+      await executeIntent(tampered, signed);
+      assert.fail("The transaction should have failed with InvalidSignature");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidSignature");
+    }
+  });
+
+  it("Rejects an expired intent", async () => {
+    const intent = await makeIntent(5, 1_000_000, 50, -10);
+
+    try {
+      await executeIntent(intent);
+      assert.fail("The transaction should have failed with IntentExpired");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "IntentExpired");
+    }
+  });
+});
@@ -0,0 +1,322 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("limit_orders", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let orderBookPda: anchor.web3.PublicKey;
+  let escrowA: anchor.web3.PublicKey;
+  let escrowB: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const ORDER_AMOUNT = new anchor.BN(10_000_000);
+
+  // Q64.64 price of `numerator / denominator` units of output per unit of input
+  const q64Price = (numerator: number, denominator: number) =>
+    new anchor.BN(
+      ((BigInt(1) << BigInt(64)) * BigInt(numerator)) / BigInt(denominator),
+    );
+
+  const balance = async (mint: anchor.web3.PublicKey) => {
+    const address = getAssociatedTokenAddressSync(
+      mint,
+      provider.wallet.publicKey,
+    );
+    return BigInt(
+      (await provider.connection.getTokenAccountBalance(address)).value.amount,
+    );
+  };
+
+  const findOrder = async (id: number) => {
+    const orderBook = await program.account.orderBook.fetch(orderBookPda);
+    return orderBook.orders.find(
+      (order) =>
+        order.id.toNumber() === id &&
+        order.owner.equals(provider.wallet.publicKey),
+    );
+  };
+
+  const placeOrder = (
+    mintFrom: anchor.web3.PublicKey,
+    mintTo: anchor.web3.PublicKey,
+    limitPrice: anchor.BN,
+    amount = ORDER_AMOUNT,
+  ) =>
+    program.methods
+      .placeLimitOrder(amount, limitPrice)
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom,
+        mintTo,
+      })
+      .rpc();
+
+  const fillOrders = () =>
+    program.methods
+      .fillOrders()
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    [orderBookPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("order_book"), liquidityPoolPda.toBuffer()],
+      program.programId,
+    );
+    escrowA = getAssociatedTokenAddressSync(mintA, orderBookPda, true);
+    escrowB = getAssociatedTokenAddressSync(mintB, orderBookPda, true);
+
+    await program.methods
+      .initializeOrderBook()
+      .accounts({
+        signer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .rpc();
+  });
+
+  it("Rejects an empty order", async () => {
+    try {
+      await program.methods
+        .placeLimitOrder(new anchor.BN(0), q64Price(1, 1))
+        .accounts({
+          owner: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintFrom: mintB,
+          mintTo: mintA,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with InvalidLimitOrder");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidLimitOrder");
+    }
+  });
+
+  it("Keeps an order resting while the price is below its limit", async () => {
+    // Sell B for at least 1.05 A per B while the pool trades at ~1
+    await placeOrder(mintB, mintA, q64Price(105, 100));
+
+    const escrowed = await provider.connection.getTokenAccountBalance(escrowB);
+    assert.equal(escrowed.value.amount, ORDER_AMOUNT.toString());
+
+    await fillOrders();
+
+    const order = await findOrder(0);
+    assert.equal(order.amountIn.toString(), ORDER_AMOUNT.toString());
+    assert.equal(order.amountFilled.toNumber(), 0);
+  });
+
+  it("Fills a crossed order within the swap that moved the price", async () => {
+    // Buying B with 20% of the reserve lifts B to ~1.44 A
+    await program.methods
+      .exchangeTokens(new anchor.BN(200_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+        orderBook: orderBookPda,
+        orderEscrowA: escrowA,
+        orderEscrowB: escrowB,
+      })
+      .rpc();
+
+    const order = await findOrder(0);
+    assert.equal(order.amountIn.toNumber(), 0);
+    assert.isAtLeast(order.amountFilled.toNumber(), 10_500_000);
+  });
+
+  it("Pays out the fill on claim and frees the slot", async () => {
+    const filled = (await findOrder(0)).amountFilled;
+    const before = await balance(mintA);
+
+    await program.methods
+      .claimFilled(new anchor.BN(0))
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintTo: mintA,
+      })
+      .rpc();
+
+    assert.equal(
+      ((await balance(mintA)) - before).toString(),
+      filled.toString(),
+    );
+    assert.isUndefined(await findOrder(0));
+  });
+
+  it("Fills crossed orders with the permissionless crank", async () => {
+    await placeOrder(mintB, mintA, q64Price(12, 10));
+    await fillOrders();
+
+    const order = await findOrder(1);
+    assert.equal(order.amountIn.toNumber(), 0);
+    assert.isAtLeast(order.amountFilled.toNumber(), 12_000_000);
+  });
+
+  it("Refunds the escrow when cancelling", async () => {
+    // Selling A for 10 B per A will not fill any time soon
+    await placeOrder(mintA, mintB, q64Price(10, 1));
+    const before = await balance(mintA);
+
+    await program.methods
+      .cancelOrder(new anchor.BN(2))
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    assert.equal(
+      ((await balance(mintA)) - before).toString(),
+      ORDER_AMOUNT.toString(),
+    );
+    assert.isUndefined(await findOrder(2));
+  });
+
+  it("Fills the best priced order first, partially", async () => {
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    const reserve = async (vault: anchor.web3.PublicKey) =>
+      BigInt(
+        (await provider.connection.getTokenAccountBalance(vault)).value.amount,
+      );
+    const reserveA = await reserve(pool.vaultA);
+    const reserveB = await reserve(pool.vaultB);
+
+    // Q64.64 price of `percent`% of the pool price of B in A
+    const belowSpot = (percent: number) =>
+      new anchor.BN(
+        (
+          ((BigInt(1) << BigInt(64)) * reserveA * BigInt(percent)) /
+          (reserveB * BigInt(100))
+        ).toString(),
+      );
+
+    // Neither fits the pool in full, the later and cheaper one goes first
+    const amount = new anchor.BN(200_000_000);
+    await placeOrder(mintB, mintA, belowSpot(97), amount);
+    await placeOrder(mintB, mintA, belowSpot(93), amount);
+    await fillOrders();
+
+    // Filled until the pool price fell past the pricier order
+    const pricier = await findOrder(3);
+    assert.equal(pricier.amountIn.toString(), amount.toString());
+    assert.equal(pricier.amountFilled.toNumber(), 0);
+
+    const cheaper = await findOrder(4);
+    assert.isAbove(cheaper.amountIn.toNumber(), 0);
+    assert.isBelow(cheaper.amountIn.toNumber(), amount.toNumber());
+    assert.isAbove(cheaper.amountFilled.toNumber(), 0);
+
+    // Claiming the partial fill keeps the rest of the order on the book
+    await program.methods
+      .claimFilled(new anchor.BN(4))
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintTo: mintA,
+      })
+      .rpc();
+
+    const resting = await findOrder(4);
+    assert.equal(resting.amountIn.toString(), cheaper.amountIn.toString());
+    assert.equal(resting.amountFilled.toNumber(), 0);
+  });
+});
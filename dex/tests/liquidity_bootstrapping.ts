@@ -0,0 +1,279 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  getAssociatedTokenAddress,
+  mintTo,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("liquidity_bootstrapping", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let userTokenA: anchor.web3.PublicKey;
+  let userTokenB: anchor.web3.PublicKey;
+  let userLpToken: anchor.web3.PublicKey;
+
+  const otherUser = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(100); // 1%
+  const SALE_DURATION_SECONDS = 6;
+  let endTs: number;
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize a sale moving token A from 96% to 50% weight
+    const now = Math.floor(Date.now() / 1000);
+    endTs = now + SALE_DURATION_SECONDS;
+
+    await program.methods
+      .initialize(FEE_BPS, {
+        liquidityBootstrapping: {
+          startWeightABps: new anchor.BN(9600),
+          endWeightABps: new anchor.BN(5000),
+          startTs: new anchor.BN(now - 5),
+          endTs: new anchor.BN(endTs),
+        },
+      })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    // 3. Fund the creator and a second user
+    userTokenA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        provider.wallet.publicKey,
+      )
+    ).address;
+    userTokenB = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintB,
+        provider.wallet.publicKey,
+      )
+    ).address;
+
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      userTokenA,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      userTokenB,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+
+    const signature = await provider.connection.requestAirdrop(
+      otherUser.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(signature);
+
+    const otherTokenA = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintA,
+      otherUser.publicKey,
+    );
+    const otherTokenB = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintB,
+      otherUser.publicKey,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      otherTokenA.address,
+      provider.wallet.publicKey,
+      1_000_000_000,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      otherTokenB.address,
+      provider.wallet.publicKey,
+      1_000_000_000,
+    );
+
+    userLpToken = await getAssociatedTokenAddress(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+  });
+
+  it("Lets the creator seed the sale", async () => {
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1000_000_000),
+        new anchor.BN(40_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    const userLpAccount = await getAccount(provider.connection, userLpToken);
+    assert.isAbove(Number(userLpAccount.amount), 0);
+  });
+
+  it("Rejects liquidity from anyone else during the sale", async () => {
+    try {
+      await program.methods
+        .addLiquidityToPool(new anchor.BN(1000_000), new anchor.BN(40_000))
+        .accounts({
+          signer: otherUser.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          lpMint: lpMintKeypair.publicKey,
+        })
+        .signers([otherUser])
+        .rpc();
+      assert.fail("The transaction should have failed with Unauthorized");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+    }
+  });
+
+  it("Sells the launch token at the weighted price", async () => {
+    const buyerTokenA = await getAssociatedTokenAddress(
+      mintA,
+      otherUser.publicKey,
+    );
+    const before = await getAccount(provider.connection, buyerTokenA);
+
+    await program.methods
+      .exchangeTokens(new anchor.BN(1_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: otherUser.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintB,
+        mintTo: mintA,
+      })
+      .signers([otherUser])
+      .rpc();
+
+    const after = await getAccount(provider.connection, buyerTokenA);
+    const received = Number(after.amount) - Number(before.amount);
+
+    // With a 50/50 pool the same trade would return ~24.4 A, the heavy
+    // token A weight makes the launch token much cheaper to start with
+    assert.isAbove(received, 0);
+    assert.isBelow(received, 24_000_000);
+  });
+
+  it("Cannot be finalized before the sale ends", async () => {
+    try {
+      await program.methods
+        .finalize()
+        .accounts({
+          creator: provider.wallet.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          lpMint: lpMintKeypair.publicKey,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with SaleNotEnded");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "SaleNotEnded");
+    }
+  });
+
+  it("Returns the remaining liquidity to the creator once finalized", async () => {
+    const waitMs = (endTs + 2) * 1000 - Date.now();
+    if (waitMs > 0) {
+      await new Promise((resolve) => setTimeout(resolve, waitMs));
+    }
+
+    const creatorABefore = await getAccount(provider.connection, userTokenA);
+    const creatorBBefore = await getAccount(provider.connection, userTokenB);
+
+    await program.methods
+      .finalize()
+      .accounts({
+        creator: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    const userLpAccount = await getAccount(provider.connection, userLpToken);
+    assert.equal(userLpAccount.amount.toString(), "0");
+
+    const creatorAAfter = await getAccount(provider.connection, userTokenA);
+    const creatorBAfter = await getAccount(provider.connection, userTokenB);
+    assert.isAbove(
+      Number(creatorAAfter.amount),
+      Number(creatorABefore.amount),
+    );
+    assert.isAbove(
+      Number(creatorBAfter.amount),
+      Number(creatorBBefore.amount),
+    );
+
+    const poolAccount = await program.account.pool.fetch(liquidityPoolPda);
+    assert.ok(poolAccount.lbpFinalized);
+  });
+});
@@ -0,0 +1,252 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAccount,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("liquidity_lock", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const LOCK_AMOUNT = 100_000_000;
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  const now = async () =>
+    (await provider.connection.getBlockTime(
+      await provider.connection.getSlot(),
+    ))!;
+
+  const lockPda = (id: number) =>
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("liquidity_lock"),
+        liquidityPoolPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+        new anchor.BN(id).toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId,
+    )[0];
+
+  const lpBalance = async () =>
+    (
+      await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(
+          lpMintKeypair.publicKey,
+          provider.wallet.publicKey,
+        ),
+      )
+    ).amount;
+
+  const lock = (id: number, unlockTs: number, linearVesting: boolean) =>
+    program.methods
+      .lockLiquidity(
+        new anchor.BN(id),
+        new anchor.BN(LOCK_AMOUNT),
+        new anchor.BN(unlockTs),
+        linearVesting,
+      )
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+  const unlock = (id: number) =>
+    program.methods
+      .unlockLiquidity()
+      .accountsPartial({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        lpMint: lpMintKeypair.publicKey,
+        liquidityLock: lockPda(id),
+      })
+      .rpc();
+
+  const quoteLocked = () =>
+    program.methods
+      .quoteLockedLiquidity()
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .view();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Rejects a lock that is already over", async () => {
+    try {
+      await lock(0, (await now()) - 1, false);
+      assert.fail(
+        "The transaction should have failed with InvalidLiquidityLock",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidLiquidityLock");
+    }
+  });
+
+  it("Locks LP tokens until the unlock time", async () => {
+    const balanceBefore = await lpBalance();
+
+    await lock(1, (await now()) + 3, false);
+
+    assert.equal(await lpBalance(), balanceBefore - BigInt(LOCK_AMOUNT));
+
+    const report = await quoteLocked();
+    assert.equal(report.locked.toNumber(), LOCK_AMOUNT);
+    assert.isAbove(report.lockedBps, 0);
+    assert.equal(
+      report.lockedBps,
+      Math.floor((LOCK_AMOUNT * 10_000) / report.lpSupply.toNumber()),
+    );
+
+    try {
+      await unlock(1);
+      assert.fail("The transaction should have failed with NothingToUnlock");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "NothingToUnlock");
+    }
+  });
+
+  it("Pays the LP tokens back after the unlock time", async () => {
+    await sleep(4000);
+
+    const balanceBefore = await lpBalance();
+    await unlock(1);
+
+    assert.equal(await lpBalance(), balanceBefore + BigInt(LOCK_AMOUNT));
+    assert.isNull(await provider.connection.getAccountInfo(lockPda(1)));
+
+    const report = await quoteLocked();
+    assert.equal(report.locked.toNumber(), 0);
+  });
+
+  it("Vests a lock linearly", async () => {
+    await lock(2, (await now()) + 6, true);
+
+    await sleep(3000);
+
+    const balanceBefore = await lpBalance();
+    const signature = await unlock(2);
+
+    // Part of the lock vested, the rest is still locked
+    const vested = Number((await lpBalance()) - balanceBefore);
+    assert.isAbove(vested, 0);
+    assert.isBelow(vested, LOCK_AMOUNT);
+
+    const liquidityLock = await program.account.liquidityLock.fetch(lockPda(2));
+    assert.equal(liquidityLock.withdrawn.toNumber(), vested);
+
+    // Pro rata to the time elapsed since locking, give or take a second
+    const { blockTime } = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const lockedAt = liquidityLock.lockedAt.toNumber();
+    const duration = liquidityLock.unlockTs.toNumber() - lockedAt;
+    assert.approximately(
+      vested,
+      (LOCK_AMOUNT * (blockTime - lockedAt)) / duration,
+      LOCK_AMOUNT / duration,
+    );
+
+    const report = await quoteLocked();
+    assert.equal(report.locked.toNumber(), LOCK_AMOUNT - vested);
+
+    await sleep(4000);
+    await unlock(2);
+
+    assert.equal(await lpBalance(), balanceBefore + BigInt(LOCK_AMOUNT));
+    assert.isNull(await provider.connection.getAccountInfo(lockPda(2)));
+  });
+});
@@ -0,0 +1,292 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAccount,
+  getMint,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+  transferChecked,
+  TOKEN_2022_PROGRAM_ID,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("lp_position", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let positionLpVaultPda: anchor.web3.PublicKey;
+  let positionPda: anchor.web3.PublicKey;
+  const positionMintKeypair = anchor.web3.Keypair.generate();
+
+  // Receives the position NFT
+  const holder = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const POOL_AMOUNT = 1_000_000_000;
+  const POSITION_AMOUNT = 100_000_000;
+  const SWAP_AMOUNT = new anchor.BN(50_000_000);
+
+  const quotePosition = () =>
+    program.methods
+      .quoteLpPosition()
+      .accountsPartial({
+        liquidityPool: liquidityPoolPda,
+        position: positionPda,
+      })
+      .view();
+
+  const swap = (
+    mintFrom: anchor.web3.PublicKey,
+    mintTo: anchor.web3.PublicKey,
+  ) =>
+    program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom,
+        mintTo,
+      })
+      .rpc();
+
+  const closePosition = (owner: anchor.web3.Keypair) =>
+    program.methods
+      .closeLpPosition()
+      .accountsPartial({
+        owner: owner.publicKey,
+        mintA,
+        mintB,
+        positionMint: positionMintKeypair.publicKey,
+        position: positionPda,
+      })
+      .signers([owner])
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    const airdropSig = await provider.connection.requestAirdrop(
+      holder.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+    [positionLpVaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("position_lp_vault"), liquidityPoolPda.toBuffer()],
+      program.programId,
+    );
+    [positionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("lp_position"),
+        liquidityPoolPda.toBuffer(),
+        positionMintKeypair.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(POOL_AMOUNT),
+        new anchor.BN(POOL_AMOUNT),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Opens a position represented by an NFT", async () => {
+    await program.methods
+      .openLpPosition(
+        new anchor.BN(POSITION_AMOUNT),
+        new anchor.BN(POSITION_AMOUNT),
+      )
+      .accounts({
+        owner: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        positionMint: positionMintKeypair.publicKey,
+      })
+      .signers([positionMintKeypair])
+      .rpc();
+
+    const position = await program.account.lpPosition.fetch(positionPda);
+    assert.equal(position.depositedA.toNumber(), POSITION_AMOUNT);
+    assert.equal(position.depositedB.toNumber(), POSITION_AMOUNT);
+    assert.isTrue(position.liquidity.toNumber() > 0);
+
+    // The LP tokens backing the position are held by the pool
+    const lpVault = await getAccount(provider.connection, positionLpVaultPda);
+    assert.equal(lpVault.amount.toString(), position.liquidity.toString());
+
+    // One NFT exists and no more can be minted
+    const nftMint = await getMint(
+      provider.connection,
+      positionMintKeypair.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(nftMint.supply, BigInt(1));
+    assert.equal(nftMint.decimals, 0);
+    assert.isNull(nftMint.mintAuthority);
+
+    const quote = await quotePosition();
+    assert.equal(quote.feesA.toNumber(), 0);
+    assert.equal(quote.feesB.toNumber(), 0);
+  });
+
+  it("Reports the fees earned by the position", async () => {
+    await swap(mintA, mintB);
+    await swap(mintB, mintA);
+
+    const quote = await quotePosition();
+    assert.isTrue(quote.feesA.toNumber() > 0);
+    assert.isTrue(quote.feesB.toNumber() > 0);
+
+    // About a tenth of the pool earning 0.3% of 50M each way
+    const share = POSITION_AMOUNT / (POOL_AMOUNT + POSITION_AMOUNT);
+    const expectedFees = SWAP_AMOUNT.toNumber() * 0.003 * share;
+    for (const fees of [quote.feesA, quote.feesB]) {
+      assert.approximately(fees.toNumber(), expectedFees, expectedFees / 5);
+    }
+  });
+
+  it("Moves the position along with the NFT", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    const holderAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      positionMintKeypair.publicKey,
+      holder.publicKey,
+      false,
+      undefined,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    await transferChecked(
+      provider.connection,
+      payer,
+      getAssociatedTokenAddressSync(
+        positionMintKeypair.publicKey,
+        provider.wallet.publicKey,
+        false,
+        TOKEN_2022_PROGRAM_ID,
+      ),
+      positionMintKeypair.publicKey,
+      holderAccount.address,
+      payer,
+      1,
+      0,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+
+    try {
+      await closePosition(payer);
+      assert.fail("The transaction should have failed with NotPositionHolder");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "NotPositionHolder");
+    }
+  });
+
+  it("Pays the position out to the NFT holder", async () => {
+    const quote = await quotePosition();
+
+    await closePosition(holder);
+
+    for (const [mint, amount] of [
+      [mintA, quote.amountA],
+      [mintB, quote.amountB],
+    ] as const) {
+      const account = await getAccount(
+        provider.connection,
+        getAssociatedTokenAddressSync(mint, holder.publicKey),
+      );
+      assert.equal(account.amount.toString(), amount.toString());
+    }
+
+    assert.isNull(await provider.connection.getAccountInfo(positionPda));
+
+    const nftMint = await getMint(
+      provider.connection,
+      positionMintKeypair.publicKey,
+      undefined,
+      TOKEN_2022_PROGRAM_ID,
+    );
+    assert.equal(nftMint.supply, BigInt(0));
+
+    const lpVault = await getAccount(provider.connection, positionLpVaultPda);
+    assert.equal(lpVault.amount, BigInt(0));
+  });
+});
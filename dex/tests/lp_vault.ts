@@ -0,0 +1,407 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import { LpVault } from "../target/types/lp_vault";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("lp_vault", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const dex = anchor.workspace.dex as Program<Dex>;
+  const program = anchor.workspace.lpVault as Program<LpVault>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let rewardMint: anchor.web3.PublicKey;
+  let lpMint: anchor.web3.PublicKey;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let rewardPoolPda: anchor.web3.PublicKey;
+  let farmPda: anchor.web3.PublicKey;
+  let vaultPda: anchor.web3.PublicKey;
+  let authorityPda: anchor.web3.PublicKey;
+  let shareMintPda: anchor.web3.PublicKey;
+  let farmStakePda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const REWARD_PER_SECOND = 100_000;
+  const SCHEDULE_SECONDS = 1_000_000;
+  const DEPOSIT_AMOUNT = 10_000_000;
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  const balance = async (
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+  ) =>
+    BigInt(
+      (
+        await provider.connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(mint, owner, true),
+        )
+      ).value.amount,
+    );
+
+  const stakedLp = async () =>
+    BigInt(
+      (await dex.account.farmStake.fetch(farmStakePda)).amount.toString(),
+    );
+
+  const sorted = (x: anchor.web3.PublicKey, y: anchor.web3.PublicKey) =>
+    x.toBuffer().compare(y.toBuffer()) > 0 ? [y, x] : [x, y];
+
+  // Opens a constant product pool of two mints seeded 1:1, returns its LP mint
+  const seedPool = async (
+    x: anchor.web3.PublicKey,
+    y: anchor.web3.PublicKey,
+  ) => {
+    const [first, second] = sorted(x, y);
+    const lpMintKeypair = anchor.web3.Keypair.generate();
+
+    await dex.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: first,
+        mintB: second,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    await dex.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: first,
+        mintB: second,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    return lpMintKeypair.publicKey;
+  };
+
+  const poolPda = (x: anchor.web3.PublicKey, y: anchor.web3.PublicKey) => {
+    const [first, second] = sorted(x, y);
+    return anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), first.toBuffer(), second.toBuffer()],
+      dex.programId,
+    )[0];
+  };
+
+  const vaultAccounts = () => ({
+    owner: provider.wallet.publicKey,
+    vault: vaultPda,
+    authority: authorityPda,
+    lpMint,
+    shareMint: shareMintPda,
+    farm: farmPda,
+    farmStake: farmStakePda,
+    farmLpVault: getAssociatedTokenAddressSync(lpMint, farmPda, true),
+  });
+
+  // Harvests reward 0 through `swapPool`, selling it for `mintOut`
+  const harvest = (
+    swapPool: anchor.web3.PublicKey,
+    mintOut: anchor.web3.PublicKey,
+  ) =>
+    program.methods
+      .harvest(0, new anchor.BN(1))
+      .accountsPartial({
+        keeper: provider.wallet.publicKey,
+        vault: vaultPda,
+        authority: authorityPda,
+        farm: farmPda,
+        farmStake: farmStakePda,
+        rewardVault: getAssociatedTokenAddressSync(rewardMint, farmPda, true),
+        rewardMint,
+        swapPool,
+        mintOut,
+        swapVaultIn: getAssociatedTokenAddressSync(rewardMint, swapPool, true),
+        swapVaultOut: getAssociatedTokenAddressSync(mintOut, swapPool, true),
+      })
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    [mintA, mintB, rewardMint] = await Promise.all(
+      [0, 1, 2].map(() =>
+        createMint(
+          provider.connection,
+          payer,
+          provider.wallet.publicKey,
+          null,
+          6,
+        ),
+      ),
+    );
+    [mintA, mintB] = sorted(mintA, mintB);
+
+    for (const mint of [mintA, mintB, rewardMint]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    // 2. Seed the vault pool, and a pool to sell the farm reward in
+    lpMint = await seedPool(mintA, mintB);
+    await seedPool(rewardMint, mintA);
+
+    liquidityPoolPda = poolPda(mintA, mintB);
+    rewardPoolPda = poolPda(rewardMint, mintA);
+
+    [farmPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("farm"), liquidityPoolPda.toBuffer()],
+      dex.programId,
+    );
+    [vaultPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), farmPda.toBuffer()],
+      program.programId,
+    );
+    [authorityPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("vault_authority"), vaultPda.toBuffer()],
+      program.programId,
+    );
+    [shareMintPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("share_mint"), vaultPda.toBuffer()],
+      program.programId,
+    );
+    [farmStakePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("farm_stake"),
+        farmPda.toBuffer(),
+        authorityPda.toBuffer(),
+      ],
+      dex.programId,
+    );
+
+    // 3. Open the farm with a funded reward
+    await dex.methods
+      .initializeFarm()
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        lpMint,
+      })
+      .rpc();
+
+    const startTs = (await provider.connection.getBlockTime(
+      await provider.connection.getSlot(),
+    ))!;
+
+    await dex.methods
+      .addFarmReward(
+        provider.wallet.publicKey,
+        new anchor.BN(REWARD_PER_SECOND),
+        new anchor.BN(startTs),
+        new anchor.BN(startTs + SCHEDULE_SECONDS),
+      )
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        rewardMint,
+      })
+      .rpc();
+
+    await dex.methods
+      .fundReward(0, new anchor.BN(1_000_000_000))
+      .accounts({
+        funder: provider.wallet.publicKey,
+        farm: farmPda,
+        rewardMint,
+      })
+      .rpc();
+
+    // 4. Open the vault compounding the farm
+    await program.methods
+      .initializeVault()
+      .accountsPartial({
+        creator: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        farm: farmPda,
+        mintA,
+        mintB,
+        lpMint,
+        vault: vaultPda,
+        authority: authorityPda,
+        shareMint: shareMintPda,
+      })
+      .rpc();
+
+    await program.methods
+      .setSwapPool(0)
+      .accountsPartial({
+        admin: provider.wallet.publicKey,
+        vault: vaultPda,
+        farm: farmPda,
+        swapPool: rewardPoolPda,
+      })
+      .rpc();
+  });
+
+  it("Stakes deposits in the farm and mints shares", async () => {
+    await program.methods
+      .deposit(new anchor.BN(DEPOSIT_AMOUNT))
+      .accountsPartial(vaultAccounts())
+      .rpc();
+
+    // First deposit gets one share per LP token
+    assert.equal(
+      await balance(shareMintPda, provider.wallet.publicKey),
+      BigInt(DEPOSIT_AMOUNT),
+    );
+    assert.equal(await stakedLp(), BigInt(DEPOSIT_AMOUNT));
+
+    const stake = await dex.account.farmStake.fetch(farmStakePda);
+    assert.isTrue(stake.owner.equals(authorityPda));
+  });
+
+  it("Rejects empty deposits", async () => {
+    try {
+      await program.methods
+        .deposit(new anchor.BN(0))
+        .accountsPartial(vaultAccounts())
+        .rpc();
+      assert.fail("The transaction should have failed with ZeroAmount");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "ZeroAmount");
+    }
+  });
+
+  it("Rejects a swap pool that does not sell the reward", async () => {
+    try {
+      await program.methods
+        .setSwapPool(0)
+        .accountsPartial({
+          admin: provider.wallet.publicKey,
+          vault: vaultPda,
+          farm: farmPda,
+          swapPool: liquidityPoolPda,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with InvalidSwapPool");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidSwapPool");
+    }
+  });
+
+  it("Rejects a harvest through another pool than the one set", async () => {
+    try {
+      await harvest(liquidityPoolPda, mintB);
+      assert.fail("The transaction should have failed with InvalidSwapPool");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidSwapPool");
+    }
+  });
+
+  it("Only lets the keeper harvest", async () => {
+    const keeper = anchor.web3.Keypair.generate();
+    const setKeeper = (key: anchor.web3.PublicKey) =>
+      program.methods
+        .setKeeper(key)
+        .accountsPartial({
+          admin: provider.wallet.publicKey,
+          vault: vaultPda,
+        })
+        .rpc();
+
+    await setKeeper(keeper.publicKey);
+
+    try {
+      await harvest(rewardPoolPda, mintA);
+      assert.fail("The transaction should have failed with NotKeeper");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "NotKeeper");
+    }
+
+    await setKeeper(provider.wallet.publicKey);
+  });
+
+  it("Harvests the farm reward into a pool token", async () => {
+    await sleep(2_000);
+
+    const beforeA = await balance(mintA, authorityPda);
+
+    await harvest(rewardPoolPda, mintA);
+
+    assert.isTrue((await balance(mintA, authorityPda)) > beforeA);
+    assert.equal(await balance(rewardMint, authorityPda), BigInt(0));
+  });
+
+  it("Compounds harvested tokens into staked LP", async () => {
+    const beforeStaked = await stakedLp();
+
+    await program.methods
+      .compound(new anchor.BN(1), new anchor.BN(1))
+      .accountsPartial({
+        keeper: provider.wallet.publicKey,
+        vault: vaultPda,
+        authority: authorityPda,
+        liquidityPool: liquidityPoolPda,
+        mintA,
+        mintB,
+        lpMint,
+        farm: farmPda,
+        farmStake: farmStakePda,
+        farmLpVault: getAssociatedTokenAddressSync(lpMint, farmPda, true),
+      })
+      .rpc();
+
+    assert.isTrue((await stakedLp()) > beforeStaked);
+    assert.equal(await balance(lpMint, authorityPda), BigInt(0));
+
+    // Shares did not move, each is now worth more than one LP token
+    assert.equal(
+      await balance(shareMintPda, provider.wallet.publicKey),
+      BigInt(DEPOSIT_AMOUNT),
+    );
+  });
+
+  it("Redeems shares for their part of the compounded LP", async () => {
+    const staked = await stakedLp();
+    const beforeLp = await balance(lpMint, provider.wallet.publicKey);
+    const shares = BigInt(DEPOSIT_AMOUNT / 2);
+
+    await program.methods
+      .withdraw(new anchor.BN(shares.toString()))
+      .accountsPartial(vaultAccounts())
+      .rpc();
+
+    const receivedLp =
+      (await balance(lpMint, provider.wallet.publicKey)) - beforeLp;
+    assert.equal(receivedLp, (shares * staked) / BigInt(DEPOSIT_AMOUNT));
+    assert.isTrue(receivedLp > shares);
+
+    assert.equal(await stakedLp(), staked - receivedLp);
+    assert.equal(
+      await balance(shareMintPda, provider.wallet.publicKey),
+      BigInt(DEPOSIT_AMOUNT) - shares,
+    );
+  });
+});
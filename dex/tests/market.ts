@@ -0,0 +1,290 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("market", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let marketPda: anchor.web3.PublicKey;
+
+  // Rests orders on the book, the provider wallet takes them
+  const maker = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const ORDER_AMOUNT = 10_000_000;
+
+  // Q64.64 token B per token A
+  const BID_PRICE = (BigInt(98) << BigInt(64)) / BigInt(100);
+  const ASK_PRICE = (BigInt(1001) << BigInt(64)) / BigInt(1000);
+
+  const balance = async (
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+  ) =>
+    BigInt(
+      (
+        await provider.connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(mint, owner),
+        )
+      ).value.amount,
+    );
+
+  const placeOrder = (bid: boolean, price: bigint, amount: number) =>
+    program.methods
+      .placeMarketOrder(
+        bid,
+        new anchor.BN(price.toString()),
+        new anchor.BN(amount),
+      )
+      .accounts({
+        owner: maker.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .signers([maker])
+      .rpc();
+
+  const swapBest = (sellTokenA: boolean, amount: number) =>
+    program.methods
+      .swapBest(new anchor.BN(amount), new anchor.BN(1))
+      .accounts({
+        trader: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: sellTokenA ? mintA : mintB,
+        mintTo: sellTokenA ? mintB : mintA,
+      })
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    const airdropSig = await provider.connection.requestAirdrop(
+      maker.publicKey,
+      2 * anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(airdropSig);
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+    [marketPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("market"), liquidityPoolPda.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      for (const owner of [provider.wallet.publicKey, maker.publicKey]) {
+        const userToken = await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          payer,
+          mint,
+          owner,
+        );
+        await mintTo(
+          provider.connection,
+          payer,
+          mint,
+          userToken.address,
+          provider.wallet.publicKey,
+          10_000_000_000,
+        );
+      }
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    // 3. Open the market of the pool
+    await program.methods
+      .initializeMarket()
+      .accounts({
+        signer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+  });
+
+  it("Rests maker orders in the pool vaults", async () => {
+    const vaultA = getAssociatedTokenAddressSync(mintA, liquidityPoolPda, true);
+    const beforeVaultA = BigInt(
+      (await provider.connection.getTokenAccountBalance(vaultA)).value.amount,
+    );
+
+    await placeOrder(true, BID_PRICE, ORDER_AMOUNT);
+    await placeOrder(false, ASK_PRICE, ORDER_AMOUNT);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.equal(market.bidCount.toNumber(), 1);
+    assert.equal(market.askCount.toNumber(), 1);
+    assert.equal(market.asks[0].price.toString(), ASK_PRICE.toString());
+
+    // Maker funds are set apart from the curve reserves
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(pool.bookA.toNumber(), ORDER_AMOUNT);
+    assert.equal(pool.bookB.toNumber(), ORDER_AMOUNT);
+
+    const afterVaultA = BigInt(
+      (await provider.connection.getTokenAccountBalance(vaultA)).value.amount,
+    );
+    assert.equal(afterVaultA - beforeVaultA, BigInt(ORDER_AMOUNT));
+  });
+
+  it("Rejects orders crossing the book", async () => {
+    try {
+      await placeOrder(true, (BigInt(101) << BigInt(64)) / BigInt(100), 1_000);
+      assert.fail("The transaction should have failed with MarketOrderCrosses");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "MarketOrderCrosses");
+    }
+  });
+
+  it("Fills an ask priced better than the curve first", async () => {
+    const beforeA = await balance(mintA, provider.wallet.publicKey);
+
+    await swapBest(false, 5_000_000);
+
+    const receivedA =
+      (await balance(mintA, provider.wallet.publicKey)) - beforeA;
+
+    // Entirely filled at the ask price, above the ~4.97M the curve pays
+    const expected = (BigInt(5_000_000) << BigInt(64)) / ASK_PRICE;
+    assert.equal(receivedA, expected);
+
+    const market = await program.account.market.fetch(marketPda);
+    assert.equal(
+      market.asks[0].amountIn.toString(),
+      (BigInt(ORDER_AMOUNT) - expected).toString(),
+    );
+    assert.equal(market.asks[0].amountFilled.toNumber(), 5_000_000);
+
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(
+      pool.bookA.toString(),
+      (BigInt(ORDER_AMOUNT) - expected).toString(),
+    );
+    assert.equal(pool.bookB.toNumber(), ORDER_AMOUNT + 5_000_000);
+  });
+
+  it("Routes the rest through the curve once the book is empty", async () => {
+    const market = await program.account.market.fetch(marketPda);
+    const askLeft = BigInt(market.asks[0].amountIn.toString());
+    const beforeA = await balance(mintA, provider.wallet.publicKey);
+
+    await swapBest(false, 20_000_000);
+
+    const receivedA =
+      (await balance(mintA, provider.wallet.publicKey)) - beforeA;
+    assert.isTrue(receivedA > askLeft + BigInt(9_000_000));
+
+    const after = await program.account.market.fetch(marketPda);
+    assert.equal(after.asks[0].amountIn.toNumber(), 0);
+
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(pool.bookA.toNumber(), 0);
+  });
+
+  it("Trades on the curve down to a bid before filling it", async () => {
+    const beforeB = await balance(mintB, provider.wallet.publicKey);
+
+    await swapBest(true, 40_000_000);
+
+    const receivedB =
+      (await balance(mintB, provider.wallet.publicKey)) - beforeB;
+    assert.isTrue(receivedB > BigInt(38_000_000));
+
+    const bid = (await program.account.market.fetch(marketPda)).bids[0];
+    assert.isTrue(bid.amountIn.toNumber() <= 1);
+    assert.isTrue(bid.amountFilled.toNumber() > 10_000_000);
+  });
+
+  it("Pays out fills when an order is closed", async () => {
+    const market = await program.account.market.fetch(marketPda);
+    const ask = market.asks[0];
+    const beforeB = await balance(mintB, maker.publicKey);
+
+    await program.methods
+      .closeMarketOrder(ask.id)
+      .accounts({
+        owner: maker.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .signers([maker])
+      .rpc();
+
+    const receivedB = (await balance(mintB, maker.publicKey)) - beforeB;
+    assert.equal(receivedB.toString(), ask.amountFilled.toString());
+
+    const after = await program.account.market.fetch(marketPda);
+    assert.equal(after.askCount.toNumber(), 0);
+
+    // Only the filled bid is left on the book
+    const bid = after.bids[0];
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(pool.bookA.toString(), bid.amountFilled.toString());
+    assert.equal(pool.bookB.toString(), bid.amountIn.toString());
+  });
+});
@@ -0,0 +1,222 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  getAssociatedTokenAddress,
+  mintTo,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("multi_pool", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mints: anchor.web3.PublicKey[] = [];
+  let vaults: anchor.web3.PublicKey[] = [];
+  let userTokens: anchor.web3.PublicKey[] = [];
+  let lpMintKeypair: anchor.web3.Keypair;
+  let multiPoolPda: anchor.web3.PublicKey;
+  let userLpToken: anchor.web3.PublicKey;
+
+  // Constants
+  const TOKEN_COUNT = 3;
+  const FEE_BPS = new anchor.BN(4); // 0.04%
+  const AMPLIFICATION = new anchor.BN(100);
+  const DEPOSIT = new anchor.BN(1_000_000_000);
+
+  const tokenAccountPairs = () =>
+    mints.flatMap((_, i) => [
+      { pubkey: userTokens[i], isSigner: false, isWritable: true },
+      { pubkey: vaults[i], isSigner: false, isWritable: true },
+    ]);
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints, sorted as the pool expects them
+    for (let i = 0; i < TOKEN_COUNT; i++) {
+      mints.push(
+        await createMint(
+          provider.connection,
+          payer,
+          provider.wallet.publicKey,
+          null,
+          6,
+        ),
+      );
+    }
+    mints.sort((a, b) => a.toBuffer().compare(b.toBuffer()));
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [multiPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("multi_pool"), lpMintKeypair.publicKey.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Vaults are the associated token accounts of the pool
+    for (const mint of mints) {
+      vaults.push(
+        (
+          await getOrCreateAssociatedTokenAccount(
+            provider.connection,
+            payer,
+            mint,
+            multiPoolPda,
+            true, // allowOwnerOffCurve: true because owner is a PDA
+          )
+        ).address,
+      );
+
+      const userToken = (
+        await getOrCreateAssociatedTokenAccount(
+          provider.connection,
+          payer,
+          mint,
+          provider.wallet.publicKey,
+        )
+      ).address;
+      userTokens.push(userToken);
+
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    // 3. Initialize a StableSwap basket
+    await program.methods
+      .initializeMultiPool(
+        FEE_BPS,
+        { stableSwap: { amplification: AMPLIFICATION } },
+        [],
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .remainingAccounts(
+        [...mints, ...vaults].map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: false,
+        })),
+      )
+      .signers([lpMintKeypair])
+      .rpc();
+
+    userLpToken = await getAssociatedTokenAddress(
+      lpMintKeypair.publicKey,
+      provider.wallet.publicKey,
+    );
+  });
+
+  it("Takes a proportional deposit of every token", async () => {
+    await program.methods
+      .addMultiLiquidity(mints.map(() => DEPOSIT))
+      .accounts({
+        signer: provider.wallet.publicKey,
+        multiPool: multiPoolPda,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .remainingAccounts(tokenAccountPairs())
+      .rpc();
+
+    const pool = await program.account.multiPool.fetch(multiPoolPda);
+    assert.equal(pool.tokens.length, TOKEN_COUNT);
+    pool.tokens.forEach((token) =>
+      assert.equal(token.balance.toString(), DEPOSIT.toString()),
+    );
+
+    const lpAccount = await getAccount(provider.connection, userLpToken);
+    assert.equal(lpAccount.amount.toString(), DEPOSIT.muln(TOKEN_COUNT).toString());
+  });
+
+  it("Swaps between any two members close to 1:1", async () => {
+    const before = await getAccount(provider.connection, userTokens[2]);
+
+    await program.methods
+      .exchangeTokensMulti(new anchor.BN(10_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        multiPool: multiPoolPda,
+        mintFrom: mints[0],
+        mintTo: mints[2],
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, userTokens[2]);
+    const received = Number(after.amount) - Number(before.amount);
+
+    assert.isAbove(received, 9_990_000);
+    assert.isBelow(received, 10_000_000);
+  });
+
+  it("Deposits and withdraws a single token", async () => {
+    const lpBefore = await getAccount(provider.connection, userLpToken);
+
+    await program.methods
+      .addSingleTokenLiquidity(new anchor.BN(30_000_000), new anchor.BN(1))
+      .accounts({
+        signer: provider.wallet.publicKey,
+        multiPool: multiPoolPda,
+        mint: mints[1],
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    const lpAfter = await getAccount(provider.connection, userLpToken);
+    const minted = Number(lpAfter.amount) - Number(lpBefore.amount);
+    assert.isAbove(minted, 29_000_000);
+    assert.isBelow(minted, 30_000_000);
+
+    const tokenBefore = await getAccount(provider.connection, userTokens[1]);
+
+    await program.methods
+      .withdrawSingleTokenLiquidity(new anchor.BN(minted), new anchor.BN(1))
+      .accounts({
+        signer: provider.wallet.publicKey,
+        multiPool: multiPoolPda,
+        mint: mints[1],
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    const tokenAfter = await getAccount(provider.connection, userTokens[1]);
+    const withdrawn = Number(tokenAfter.amount) - Number(tokenBefore.amount);
+
+    // The round trip only costs fees
+    assert.isAbove(withdrawn, 29_000_000);
+    assert.isBelow(withdrawn, 30_000_000);
+  });
+
+  it("Withdraws every token proportionally", async () => {
+    const lpAccount = await getAccount(provider.connection, userLpToken);
+
+    await program.methods
+      .withdrawMultiLiquidity(new anchor.BN(lpAccount.amount.toString()).divn(2))
+      .accounts({
+        signer: provider.wallet.publicKey,
+        multiPool: multiPoolPda,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .remainingAccounts(tokenAccountPairs())
+      .rpc();
+
+    const pool = await program.account.multiPool.fetch(multiPoolPda);
+    pool.tokens.forEach((token) => {
+      assert.isAbove(token.balance.toNumber(), 450_000_000);
+      assert.isBelow(token.balance.toNumber(), 550_000_000);
+    });
+  });
+});
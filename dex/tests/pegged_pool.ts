@@ -0,0 +1,239 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("pegged_pool", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let userTokenA: anchor.web3.PublicKey;
+  let userTokenB: anchor.web3.PublicKey;
+
+  const otherUser = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(10); // 0.1%
+  const PEG_RATE_SCALE = new anchor.BN(1_000_000_000);
+  const MAX_A_TO_B = new anchor.BN(2_000_000_000);
+  const MAX_B_TO_A = new anchor.BN(50_000_000);
+
+  const swap = (amount: number, mintFrom, mintTo) =>
+    program.methods
+      .exchangeTokens(new anchor.BN(amount), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintFrom,
+        mintTo: mintTo,
+      })
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize a 1:1 pegged pool
+    await program.methods
+      .initialize(FEE_BPS, {
+        pegged: {
+          rate: PEG_RATE_SCALE,
+          maxAToB: MAX_A_TO_B,
+          maxBToA: MAX_B_TO_A,
+        },
+      })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    // 3. Fund the user and seed the pool
+    userTokenA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        provider.wallet.publicKey,
+      )
+    ).address;
+    userTokenB = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintB,
+        provider.wallet.publicKey,
+      )
+    ).address;
+
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      userTokenA,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      userTokenB,
+      provider.wallet.publicKey,
+      10_000_000_000,
+    );
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    const signature = await provider.connection.requestAirdrop(
+      otherUser.publicKey,
+      anchor.web3.LAMPORTS_PER_SOL,
+    );
+    await provider.connection.confirmTransaction(signature);
+  });
+
+  it("Swaps 1:1 minus the fee", async () => {
+    const before = await getAccount(provider.connection, userTokenB);
+    await swap(10_000_000, mintA, mintB);
+    const after = await getAccount(provider.connection, userTokenB);
+
+    assert.equal(Number(after.amount) - Number(before.amount), 9_990_000);
+  });
+
+  it("Enforces the per-direction cap", async () => {
+    try {
+      await swap(60_000_000, mintB, mintA);
+      assert.fail("The transaction should have failed with SwapCapExceeded");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "SwapCapExceeded");
+    }
+  });
+
+  it("Counts every swap of a slot against the cap", async () => {
+    // Two swaps of 30M each fit the cap on their own but not together
+    const split = async () =>
+      program.methods
+        .exchangeTokens(new anchor.BN(30_000_000), new anchor.BN(1))
+        .accounts({
+          buyer: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintFrom: mintB,
+          mintTo: mintA,
+        })
+        .instruction();
+
+    try {
+      await program.methods
+        .exchangeTokens(new anchor.BN(30_000_000), new anchor.BN(1))
+        .accounts({
+          buyer: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintFrom: mintB,
+          mintTo: mintA,
+        })
+        .preInstructions([await split()])
+        .rpc();
+      assert.fail("The transaction should have failed with SwapCapExceeded");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "SwapCapExceeded");
+    }
+
+    // A single one still goes through
+    const before = await getAccount(provider.connection, userTokenA);
+    await swap(30_000_000, mintB, mintA);
+    const after = await getAccount(provider.connection, userTokenA);
+    assert.equal(Number(after.amount) - Number(before.amount), 29_970_000);
+  });
+
+  it("Only lets the admin update the rate", async () => {
+    try {
+      await program.methods
+        .updatePegRate(PEG_RATE_SCALE.muln(2))
+        .accounts({
+          admin: otherUser.publicKey,
+          liquidityPool: liquidityPoolPda,
+        })
+        .signers([otherUser])
+        .rpc();
+      assert.fail("The transaction should have failed with Unauthorized");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "Unauthorized");
+    }
+
+    await program.methods
+      .updatePegRate(PEG_RATE_SCALE.muln(2))
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+    const before = await getAccount(provider.connection, userTokenB);
+    await swap(10_000_000, mintA, mintB);
+    const after = await getAccount(provider.connection, userTokenB);
+
+    assert.equal(Number(after.amount) - Number(before.amount), 19_980_000);
+  });
+
+  it("Stops before the output vault is emptied", async () => {
+    try {
+      await swap(1_000_000_000, mintA, mintB);
+      assert.fail("The transaction should have failed with InsufficientReserves");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InsufficientReserves");
+    }
+  });
+});
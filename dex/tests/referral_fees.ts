@@ -0,0 +1,292 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("referral_fees", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  const referrer = anchor.web3.Keypair.generate();
+  let referrerTokenAccount: anchor.web3.PublicKey;
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(100); // 1%
+  const SWAP_AMOUNT = new anchor.BN(10_000_000); // fee of 100_000
+  const MAX_REFERRAL_SHARE_BPS = new anchor.BN(5000);
+
+  const [globalConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("global_config")],
+    program.programId,
+  );
+
+  const [referrerPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("referrer"), referrer.publicKey.toBuffer()],
+    program.programId,
+  );
+
+  const setReferralFeeShare = (share: anchor.BN) =>
+    program.methods
+      .setReferralFeeShare(share)
+      .accounts({
+        admin: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+  const swapWithReferrer = async () => {
+    const signature = await program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+        referrerTokenAccount,
+        referrer: referrerPda,
+        globalConfig: globalConfigPda,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    return [...parser.parseLogs(transaction.meta.logMessages)];
+  };
+
+  const referrerBalance = async () =>
+    BigInt(
+      (await provider.connection.getTokenAccountBalance(referrerTokenAccount))
+        .value.amount,
+    );
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    referrerTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        referrer.publicKey,
+      )
+    ).address;
+
+    // The config is a program-wide singleton, another suite may have created it
+    const existingConfig = await program.account.globalConfig.fetchNullable(
+      globalConfigPda,
+    );
+    if (existingConfig) {
+      await program.methods
+        .updateGlobalConfig(MAX_REFERRAL_SHARE_BPS)
+        .accounts({ admin: provider.wallet.publicKey })
+        .rpc();
+    } else {
+      await program.methods
+        .initializeGlobalConfig(MAX_REFERRAL_SHARE_BPS)
+        .accounts({ admin: provider.wallet.publicKey })
+        .rpc();
+    }
+
+    await program.methods
+      .registerReferrer()
+      .accounts({
+        admin: provider.wallet.publicKey,
+        owner: referrer.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Rejects a share above the global cap", async () => {
+    try {
+      await setReferralFeeShare(new anchor.BN(6000));
+      assert.fail(
+        "The transaction should have failed with ReferralShareTooHigh",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "ReferralShareTooHigh");
+    }
+  });
+
+  it("Pays the referral share of the fee to the referrer", async () => {
+    await setReferralFeeShare(new anchor.BN(2000));
+
+    const before = await referrerBalance();
+    const events = await swapWithReferrer();
+
+    // 20% of the 100_000 fee
+    assert.equal((await referrerBalance()) - before, BigInt(20_000));
+
+    const event = events.find((e) => e.name === "referralFeeEvent").data;
+    assert.ok(event.referrer.equals(referrer.publicKey));
+    assert.ok(event.mint.equals(mintA));
+    assert.equal(event.amount.toNumber(), 20_000);
+  });
+
+  it("Enforces a lowered global cap on the next swap", async () => {
+    await program.methods
+      .updateGlobalConfig(new anchor.BN(1000))
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+
+    const before = await referrerBalance();
+    await swapWithReferrer();
+
+    // The pool share of 20% is cut down to the 10% cap
+    assert.equal((await referrerBalance()) - before, BigInt(10_000));
+  });
+
+  it("Rejects the buyer as their own referrer", async () => {
+    try {
+      await program.methods
+        .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+        .accounts({
+          buyer: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintFrom: mintA,
+          mintTo: mintB,
+          referrerTokenAccount: getAssociatedTokenAddressSync(
+            mintA,
+            provider.wallet.publicKey,
+          ),
+          globalConfig: globalConfigPda,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with SelfReferral");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "SelfReferral");
+    }
+  });
+
+  it("Rejects a referrer that is not registered", async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+    const stranger = anchor.web3.Keypair.generate();
+    const strangerTokenAccount = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintA,
+      stranger.publicKey,
+    );
+
+    try {
+      await program.methods
+        .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+        .accounts({
+          buyer: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintFrom: mintA,
+          mintTo: mintB,
+          referrerTokenAccount: strangerTokenAccount.address,
+          referrer: referrerPda,
+          globalConfig: globalConfigPda,
+        })
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with UnregisteredReferrer",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "UnregisteredReferrer");
+    }
+  });
+
+  it("Keeps the whole fee in the vault without a referrer", async () => {
+    const before = await referrerBalance();
+
+    await program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    assert.equal(await referrerBalance(), before);
+  });
+});
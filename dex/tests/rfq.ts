@@ -0,0 +1,291 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  approve,
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("rfq", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let globalConfigPda: anchor.web3.PublicKey;
+  let rfqAuthorityPda: anchor.web3.PublicKey;
+
+  const maker = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const MAKER_INVENTORY = 100_000_000;
+
+  const balance = async (
+    mint: anchor.web3.PublicKey,
+    owner: anchor.web3.PublicKey,
+  ) =>
+    BigInt(
+      (
+        await provider.connection.getTokenAccountBalance(
+          getAssociatedTokenAddressSync(mint, owner),
+        )
+      ).value.amount,
+    );
+
+  const makeQuote = async (
+    nonce: number,
+    amountIn: number,
+    amountOut: number,
+    expiresIn = 60,
+  ) => {
+    const now = (await provider.connection.getBlockTime(
+      await provider.connection.getSlot(),
+    ))!;
+
+    return {
+      maker: maker.publicKey,
+      mintIn: mintA,
+      mintOut: mintB,
+      amountIn: new anchor.BN(amountIn),
+      amountOut: new anchor.BN(amountOut),
+      expiry: new anchor.BN(now + expiresIn),
+      nonce: new anchor.BN(nonce),
+    };
+  };
+
+  type Quote = Awaited<ReturnType<typeof makeQuote>>;
+
+  const signQuote = (quote: Quote) =>
+    anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+      privateKey: maker.secretKey,
+      message: program.coder.types.encode("rfqQuote", quote),
+    });
+
+  const fillRfq = (
+    quote: Quote,
+    amountIn: number,
+    withPool: boolean,
+    signedQuote: Quote = quote,
+  ) =>
+    program.methods
+      .fillRfq(quote, new anchor.BN(amountIn), new anchor.BN(1))
+      .accounts({
+        taker: provider.wallet.publicKey,
+        mintIn: mintA,
+        mintOut: mintB,
+        liquidityPool: withPool ? liquidityPoolPda : null,
+        poolVaultIn: withPool
+          ? getAssociatedTokenAddressSync(mintA, liquidityPoolPda, true)
+          : null,
+        poolVaultOut: withPool
+          ? getAssociatedTokenAddressSync(mintB, liquidityPoolPda, true)
+          : null,
+      })
+      .preInstructions([signQuote(signedQuote)])
+      .rpc();
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    // 3. Whitelist a market maker holding token B inventory
+    const makerTokenB = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintB,
+      maker.publicKey,
+    );
+    await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintA,
+      maker.publicKey,
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      makerTokenB.address,
+      provider.wallet.publicKey,
+      MAKER_INVENTORY,
+    );
+
+    [globalConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("global_config")],
+      program.programId,
+    );
+    [rfqAuthorityPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("rfq_authority")],
+      program.programId,
+    );
+
+    const existingConfig = await program.account.globalConfig.fetchNullable(
+      globalConfigPda,
+    );
+    if (!existingConfig) {
+      await program.methods
+        .initializeGlobalConfig(new anchor.BN(0))
+        .accounts({ admin: provider.wallet.publicKey })
+        .rpc();
+    }
+
+    await program.methods
+      .addMarketMaker(maker.publicKey)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+
+    // Quotes are paid out of the maker account through the RFQ authority
+    await approve(
+      provider.connection,
+      payer,
+      makerTokenB.address,
+      rfqAuthorityPda,
+      maker,
+      MAKER_INVENTORY,
+    );
+  });
+
+  it("Settles a signed quote between taker and maker", async () => {
+    const quote = await makeQuote(1, 10_000_000, 10_050_000);
+    const takerB = await balance(mintB, provider.wallet.publicKey);
+    const makerA = await balance(mintA, maker.publicKey);
+
+    await fillRfq(quote, 5_000_000, false);
+
+    // Half of the quote, at the quoted price
+    const received = (await balance(mintB, provider.wallet.publicKey)) - takerB;
+    assert.equal(received.toString(), "5025000");
+
+    const paid = (await balance(mintA, maker.publicKey)) - makerA;
+    assert.equal(paid.toString(), "5000000");
+  });
+
+  it("Rejects a replayed quote", async () => {
+    const quote = await makeQuote(2, 1_000_000, 1_000_000);
+    await fillRfq(quote, 1_000_000, false);
+
+    // The nonce account of the quote already exists
+    const replayed = await fillRfq(quote, 1_000_000, false).then(
+      () => true,
+      () => false,
+    );
+    assert.isFalse(replayed);
+  });
+
+  it("Swaps the remainder through the pool", async () => {
+    const quote = await makeQuote(3, 4_000_000, 4_020_000);
+    const takerB = await balance(mintB, provider.wallet.publicKey);
+    const makerA = await balance(mintA, maker.publicKey);
+
+    await fillRfq(quote, 6_000_000, true);
+
+    const received = (await balance(mintB, provider.wallet.publicKey)) - takerB;
+    const paid = (await balance(mintA, maker.publicKey)) - makerA;
+
+    // 4M filled by the maker, ~2M less the pool fee and impact from the pool
+    assert.equal(paid.toString(), "4000000");
+    assert.isTrue(received > BigInt(4_020_000 + 1_980_000));
+    assert.isTrue(received < BigInt(4_020_000 + 2_000_000));
+  });
+
+  it("Rejects a quote the maker did not sign", async () => {
+    const signed = await makeQuote(4, 1_000_000, 1_000_000);
+    const tampered = { ...signed, amountOut: new anchor.BN(2_000_000) };
+
+    try {
+      await fillRfq(tampered, 1_000_000, false, signed);
+      assert.fail("The transaction should have failed with InvalidSignature");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidSignature");
+    }
+  });
+
+  it("Rejects an expired quote", async () => {
+    const quote = await makeQuote(5, 1_000_000, 1_000_000, -10);
+
+    try {
+      await fillRfq(quote, 1_000_000, false);
+      assert.fail("The transaction should have failed with RfqQuoteExpired");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "RfqQuoteExpired");
+    }
+  });
+});
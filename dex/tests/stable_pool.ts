@@ -0,0 +1,198 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  getAssociatedTokenAddress,
+  mintTo,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("stable_pool", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let decimalsA: number;
+  let decimalsB: number;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let userTokenA: anchor.web3.PublicKey;
+  let userTokenB: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(5); // 0.05%
+  const POOL_SIZE = 1_000; // whole tokens on each side
+
+  const units = (tokens: number, decimals: number) =>
+    new anchor.BN(tokens).mul(new anchor.BN(10).pow(new anchor.BN(decimals)));
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints with different decimals, like a wrapped and a native asset
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      9,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    decimalsA = 9;
+    decimalsB = 6;
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+      [decimalsA, decimalsB] = [decimalsB, decimalsA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize a stable pool
+    await program.methods
+      .initialize(FEE_BPS, { stable: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    // 3. Fund the user
+    userTokenA = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        provider.wallet.publicKey,
+      )
+    ).address;
+    userTokenB = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintB,
+        provider.wallet.publicKey,
+      )
+    ).address;
+
+    await mintTo(
+      provider.connection,
+      payer,
+      mintA,
+      userTokenA,
+      provider.wallet.publicKey,
+      BigInt(units(10 * POOL_SIZE, decimalsA).toString()),
+    );
+    await mintTo(
+      provider.connection,
+      payer,
+      mintB,
+      userTokenB,
+      provider.wallet.publicKey,
+      BigInt(units(10 * POOL_SIZE, decimalsB).toString()),
+    );
+
+    // 4. Balanced liquidity once normalized to the same decimals
+    await program.methods
+      .addLiquidityToPool(
+        units(POOL_SIZE, decimalsA),
+        units(POOL_SIZE, decimalsB),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+  });
+
+  it("Trades close to 1:1 around the balanced point", async () => {
+    const before = await getAccount(provider.connection, userTokenB);
+
+    // 10% of the pool, a constant product pool would return ~90.9 B
+    await program.methods
+      .exchangeTokens(units(POOL_SIZE / 10, decimalsA), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, userTokenB);
+    const received =
+      (Number(after.amount) - Number(before.amount)) / 10 ** decimalsB;
+
+    assert.isAbove(received, 99);
+    assert.isBelow(received, 100);
+  });
+
+  it("Gets expensive once the pool is heavily imbalanced", async () => {
+    // Drain most of token B first
+    await program.methods
+      .exchangeTokens(units(POOL_SIZE * 2, decimalsA), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    const before = await getAccount(provider.connection, userTokenB);
+
+    await program.methods
+      .exchangeTokens(units(POOL_SIZE / 10, decimalsA), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    const after = await getAccount(provider.connection, userTokenB);
+    const received =
+      (Number(after.amount) - Number(before.amount)) / 10 ** decimalsB;
+
+    assert.isAbove(received, 0);
+    assert.isBelow(received, 50);
+  });
+
+  it("Is stored as a stable pool", async () => {
+    const poolAccount = await program.account.pool.fetch(liquidityPoolPda);
+    assert.ok(poolAccount.curve.stable !== undefined);
+
+    const lpAccount = await getAccount(
+      provider.connection,
+      await getAssociatedTokenAddress(
+        lpMintKeypair.publicKey,
+        provider.wallet.publicKey,
+      ),
+    );
+    assert.isAbove(Number(lpAccount.amount), 0);
+  });
+});
@@ -0,0 +1,283 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("trigger_orders", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let keeperTokenAccount: anchor.web3.PublicKey;
+
+  const keeper = anchor.web3.Keypair.generate();
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const ORDER_AMOUNT = new anchor.BN(10_000_000);
+  const KEEPER_TIP = BigInt(10_000); // 0.1% of the order
+  const ONE = BigInt(1) << BigInt(64); // Q64.64 price of 1 token B per token A
+
+  const q64 = (percent: number) =>
+    new anchor.BN(((ONE * BigInt(percent)) / BigInt(100)).toString());
+
+  const balance = async (address: anchor.web3.PublicKey) =>
+    BigInt(
+      (await provider.connection.getTokenAccountBalance(address)).value.amount,
+    );
+
+  const ownerTokenAccount = (mint: anchor.web3.PublicKey) =>
+    getAssociatedTokenAddressSync(mint, provider.wallet.publicKey);
+
+  const orderAddresses = (id: anchor.BN) => {
+    const [triggerOrder] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("trigger_order"),
+        liquidityPoolPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+        id.toArrayLike(Buffer, "le", 8),
+      ],
+      program.programId,
+    );
+    const escrow = getAssociatedTokenAddressSync(mintA, triggerOrder, true);
+
+    return { triggerOrder, escrow };
+  };
+
+  const placeOrder = (
+    id: anchor.BN,
+    minAmountOut: anchor.BN,
+    triggerPrice: anchor.BN,
+    triggerAbove: boolean,
+    priceSource: { spot: {} } | { twap: {} },
+  ) =>
+    program.methods
+      .placeTriggerOrder(
+        id,
+        ORDER_AMOUNT,
+        minAmountOut,
+        triggerPrice,
+        triggerAbove,
+        priceSource,
+      )
+      .accounts({
+        owner: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintIn: mintA,
+        mintOut: mintB,
+      })
+      .rpc();
+
+  const executeOrder = (id: anchor.BN) => {
+    const { triggerOrder, escrow } = orderAddresses(id);
+
+    return program.methods
+      .executeTriggerOrder()
+      .accounts({
+        keeper: keeper.publicKey,
+        liquidityPool: liquidityPoolPda,
+        triggerOrder,
+        mintIn: mintA,
+        mintOut: mintB,
+        escrow,
+        keeperTokenAccount,
+      })
+      .signers([keeper])
+      .rpc();
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    keeperTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintA,
+        keeper.publicKey,
+      )
+    ).address;
+  });
+
+  it("Rejects an order without trigger price", async () => {
+    try {
+      await placeOrder(
+        new anchor.BN(1),
+        new anchor.BN(0),
+        new anchor.BN(0),
+        false,
+        { spot: {} },
+      );
+      assert.fail(
+        "The transaction should have failed with InvalidTriggerOrder",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidTriggerOrder");
+    }
+  });
+
+  it("Executes a stop-loss once the spot price falls through", async () => {
+    const stopLossId = new anchor.BN(1);
+    await placeOrder(
+      stopLossId,
+      new anchor.BN(9_000_000),
+      q64(98),
+      false,
+      { spot: {} },
+    );
+
+    try {
+      await executeOrder(stopLossId);
+      assert.fail(
+        "The transaction should have failed with TriggerNotReached",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "TriggerNotReached");
+    }
+
+    // Selling 30M of A pushes the price ~6% down
+    await program.methods
+      .exchangeTokens(new anchor.BN(30_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+      })
+      .rpc();
+
+    const beforeB = await balance(ownerTokenAccount(mintB));
+    await executeOrder(stopLossId);
+    const received = (await balance(ownerTokenAccount(mintB))) - beforeB;
+
+    assert.isTrue(received >= BigInt(9_000_000));
+    assert.equal(await balance(keeperTokenAccount), KEEPER_TIP);
+
+    const { triggerOrder, escrow } = orderAddresses(stopLossId);
+    assert.isNull(await provider.connection.getAccountInfo(triggerOrder));
+    assert.isNull(await provider.connection.getAccountInfo(escrow));
+  });
+
+  it("Records the spot price left by swaps", async () => {
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+
+    assert.isAbove(pool.priceOracle.lastUpdateTs.toNumber(), 0);
+    const lastPrice = BigInt(pool.priceOracle.lastPrice.toString());
+    assert.isTrue(lastPrice < BigInt(q64(98).toString()));
+  });
+
+  it("Waits for a full TWAP window on TWAP triggers", async () => {
+    const takeProfitId = new anchor.BN(2);
+    await placeOrder(takeProfitId, new anchor.BN(1), q64(50), true, {
+      twap: {},
+    });
+
+    try {
+      await executeOrder(takeProfitId);
+      assert.fail("The transaction should have failed with TwapUnavailable");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "TwapUnavailable");
+    }
+  });
+
+  it("Refunds the input on cancel", async () => {
+    const takeProfitId = new anchor.BN(2);
+    const { triggerOrder, escrow } = orderAddresses(takeProfitId);
+    const beforeA = await balance(ownerTokenAccount(mintA));
+
+    await program.methods
+      .cancelTriggerOrder()
+      .accounts({
+        triggerOrder,
+        mintIn: mintA,
+        escrow,
+      })
+      .rpc();
+
+    const refunded = (await balance(ownerTokenAccount(mintA))) - beforeA;
+
+    assert.equal(refunded.toString(), ORDER_AMOUNT.toString());
+    assert.isNull(await provider.connection.getAccountInfo(triggerOrder));
+  });
+});
@@ -0,0 +1,409 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getAssociatedTokenAddressSync,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("twamm", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+  let twammPda: anchor.web3.PublicKey;
+  let escrowA: anchor.web3.PublicKey;
+  let escrowB: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(30); // 0.3%
+  const ORDER_AMOUNT = new anchor.BN(10_000_000);
+  const DURATION = new anchor.BN(6); // seconds
+  const ORDER_INTERVAL = new anchor.BN(2); // seconds
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  const balance = async (mint: anchor.web3.PublicKey) => {
+    const address = getAssociatedTokenAddressSync(
+      mint,
+      provider.wallet.publicKey,
+    );
+    return BigInt(
+      (await provider.connection.getTokenAccountBalance(address)).value.amount,
+    );
+  };
+
+  const orderAccounts = (longTermOrder: anchor.web3.PublicKey) => ({
+    owner: provider.wallet.publicKey,
+    liquidityPool: liquidityPoolPda,
+    mintA: mintA,
+    mintB: mintB,
+    longTermOrder,
+  });
+
+  const submitOrder = async () => {
+    const longTermOrder = anchor.web3.Keypair.generate();
+
+    await program.methods
+      .submitLongTermOrder(true, ORDER_AMOUNT, DURATION)
+      .accounts(orderAccounts(longTermOrder.publicKey))
+      .signers([longTermOrder])
+      .rpc();
+
+    return longTermOrder.publicKey;
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    [twammPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("twamm"), liquidityPoolPda.toBuffer()],
+      program.programId,
+    );
+    escrowA = getAssociatedTokenAddressSync(mintA, twammPda, true);
+    escrowB = getAssociatedTokenAddressSync(mintB, twammPda, true);
+
+    await program.methods
+      .initializeTwamm(ORDER_INTERVAL)
+      .accounts({
+        signer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .rpc();
+  });
+
+  it("Rejects an order without duration", async () => {
+    const longTermOrder = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .submitLongTermOrder(true, ORDER_AMOUNT, new anchor.BN(0))
+        .accounts(orderAccounts(longTermOrder.publicKey))
+        .signers([longTermOrder])
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with InvalidLongTermOrder",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidLongTermOrder");
+    }
+  });
+
+  it("Rejects an order ending beyond the TWAMM horizon", async () => {
+    const longTermOrder = anchor.web3.Keypair.generate();
+
+    // 16 intervals at most, so the expiries never fill the TWAMM
+    try {
+      await program.methods
+        .submitLongTermOrder(true, ORDER_AMOUNT, ORDER_INTERVAL.muln(17))
+        .accounts(orderAccounts(longTermOrder.publicKey))
+        .signers([longTermOrder])
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with InvalidLongTermOrder",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidLongTermOrder");
+    }
+  });
+
+  it("Rejects a swap that leaves out the TWAMM of the pool", async () => {
+    const pool = await program.account.pool.fetch(liquidityPoolPda);
+    assert.equal(pool.twamm.toBase58(), twammPda.toBase58());
+
+    try {
+      await program.methods
+        .exchangeTokens(new anchor.BN(1_000_000), new anchor.BN(1))
+        .accounts({
+          buyer: provider.wallet.publicKey,
+          liquidityPool: liquidityPoolPda,
+          mintFrom: mintB,
+          mintTo: mintA,
+        })
+        .rpc();
+      assert.fail("The transaction should have failed with InvalidTwamm");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidTwamm");
+    }
+  });
+
+  it("Settles long-term orders before a deposit", async () => {
+    const order = await submitOrder();
+    await sleep(2000);
+
+    const before = (await program.account.twamm.fetch(twammPda))
+      .lastExecutionTs;
+
+    await program.methods
+      .addLiquidityToPool(new anchor.BN(1_000_000), new anchor.BN(1_000_000))
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+        twamm: twammPda,
+        twammEscrowA: escrowA,
+        twammEscrowB: escrowB,
+      })
+      .rpc();
+
+    // Part of the order got sold into the pool before the deposit was priced
+    const after = (await program.account.twamm.fetch(twammPda))
+      .lastExecutionTs;
+    assert.isAbove(after.toNumber(), before.toNumber());
+
+    const escrowed = await provider.connection.getTokenAccountBalance(escrowA);
+    assert.isTrue(
+      BigInt(escrowed.value.amount) < BigInt(ORDER_AMOUNT.toString()),
+    );
+
+    await program.methods
+      .cancelLongTermOrder()
+      .accounts(orderAccounts(order))
+      .rpc();
+  });
+
+  it("Quotes a swap after the pending long-term orders", async () => {
+    const order = await submitOrder();
+    await sleep(2000);
+
+    const swapAccounts = {
+      liquidityPool: liquidityPoolPda,
+      mintFrom: mintB,
+      mintTo: mintA,
+    };
+
+    const quote = await program.methods
+      .quoteExchange(new anchor.BN(1_000_000))
+      .accounts({ ...swapAccounts, twamm: twammPda })
+      .view();
+
+    const beforeA = await balance(mintA);
+    await program.methods
+      .exchangeTokens(new anchor.BN(1_000_000), new anchor.BN(1))
+      .accounts({
+        ...swapAccounts,
+        buyer: provider.wallet.publicKey,
+        twamm: twammPda,
+        twammEscrowA: escrowA,
+        twammEscrowB: escrowB,
+      })
+      .rpc();
+    const received = (await balance(mintA)) - beforeA;
+
+    // The swap may land a second later, after a little more A got sold
+    const quoted = BigInt(quote.amountOut.toString());
+    assert.isTrue(received >= quoted);
+    assert.isTrue(received * BigInt(100) < quoted * BigInt(101));
+
+    await program.methods
+      .cancelLongTermOrder()
+      .accounts(orderAccounts(order))
+      .rpc();
+  });
+
+  it("Sells into the pool over time and pays out the proceeds", async () => {
+    const order = await submitOrder();
+
+    const escrowed = await provider.connection.getTokenAccountBalance(escrowA);
+    assert.equal(escrowed.value.amount, ORDER_AMOUNT.toString());
+
+    await sleep(3000);
+
+    // A regular swap executes the long-term orders first
+    await program.methods
+      .exchangeTokens(new anchor.BN(1_000_000), new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintB,
+        mintTo: mintA,
+        twamm: twammPda,
+        twammEscrowA: escrowA,
+        twammEscrowB: escrowB,
+      })
+      .rpc();
+
+    const twamm = await program.account.twamm.fetch(twammPda);
+    assert.isAbove(twamm.lastExecutionTs.toNumber(), 0);
+
+    const beforeB = await balance(mintB);
+    await program.methods
+      .withdrawProceeds()
+      .accounts(orderAccounts(order))
+      .rpc();
+    const partialProceeds = (await balance(mintB)) - beforeB;
+
+    assert.isTrue(partialProceeds > BigInt(0));
+    assert.isTrue(partialProceeds < BigInt(ORDER_AMOUNT.toString()));
+
+    // Once expired the rest is paid out and the order is closed
+    await sleep(5000);
+
+    await program.methods
+      .withdrawProceeds()
+      .accounts(orderAccounts(order))
+      .rpc();
+    const totalProceeds = (await balance(mintB)) - beforeB + partialProceeds;
+
+    // ~10M of A sold into a 1B:1B pool, less fees and price impact
+    assert.isTrue(totalProceeds > BigInt(9_800_000));
+    assert.isTrue(totalProceeds < BigInt(10_000_000));
+    assert.isNull(await provider.connection.getAccountInfo(order));
+  });
+
+  it("Refunds the unsold input on cancel", async () => {
+    const order = await submitOrder();
+    const beforeA = await balance(mintA);
+
+    await program.methods
+      .cancelLongTermOrder()
+      .accounts(orderAccounts(order))
+      .rpc();
+
+    const refunded = (await balance(mintA)) - beforeA;
+
+    // At most a couple of seconds worth of the order got sold
+    assert.isTrue(refunded > BigInt(5_000_000));
+    assert.isTrue(refunded <= BigInt(ORDER_AMOUNT.toString()));
+    assert.isNull(await provider.connection.getAccountInfo(order));
+  });
+
+  it("Lets anyone settle an expired order", async () => {
+    const order = await submitOrder();
+
+    // Orders end on the order interval
+    const { endTs } = await program.account.longTermOrder.fetch(order);
+    assert.equal(endTs.mod(ORDER_INTERVAL).toNumber(), 0);
+
+    const beforeB = await balance(mintB);
+    const stranger = anchor.web3.Keypair.generate();
+
+    try {
+      await program.methods
+        .settleLongTermOrder()
+        .accounts({ ...orderAccounts(order), signer: stranger.publicKey })
+        .signers([stranger])
+        .rpc();
+      assert.fail(
+        "The transaction should have failed with LongTermOrderActive",
+      );
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "LongTermOrderActive");
+    }
+
+    await sleep(8000);
+
+    await program.methods
+      .settleLongTermOrder()
+      .accounts({ ...orderAccounts(order), signer: stranger.publicKey })
+      .signers([stranger])
+      .rpc();
+
+    // The owner got the proceeds and the order is gone
+    assert.isTrue((await balance(mintB)) > beforeB);
+    assert.isNull(await provider.connection.getAccountInfo(order));
+  });
+
+  it("Executes the orders with the permissionless crank", async () => {
+    await submitOrder();
+    await sleep(2000);
+
+    const before = (await program.account.twamm.fetch(twammPda))
+      .lastExecutionTs;
+
+    await program.methods
+      .executeLongTermOrders()
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintA: mintA,
+        mintB: mintB,
+      })
+      .rpc();
+
+    const after = (await program.account.twamm.fetch(twammPda))
+      .lastExecutionTs;
+    assert.isAbove(after.toNumber(), before.toNumber());
+  });
+});
@@ -0,0 +1,284 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Dex } from "../target/types/dex";
+import {
+  createMint,
+  mintTo,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+
+describe("volume_tiers", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.dex as Program<Dex>;
+
+  // Accounts
+  let traderStatsPda: anchor.web3.PublicKey;
+  let mintA: anchor.web3.PublicKey;
+  let mintB: anchor.web3.PublicKey;
+  let lpMintKeypair: anchor.web3.Keypair;
+  let liquidityPoolPda: anchor.web3.PublicKey;
+
+  // Constants
+  const FEE_BPS = new anchor.BN(100); // 1%
+  const SWAP_AMOUNT = new anchor.BN(100_000_000);
+  const MAX_REFERRAL_SHARE_BPS = new anchor.BN(5000);
+  // Volume is counted in whole tokens scaled to 18 decimals
+  const ONE = new anchor.BN(10).pow(new anchor.BN(18));
+  const SWAP_VOLUME = ONE.muln(100); // SWAP_AMOUNT of a 6-decimal token
+  const FEE_TIERS = [
+    {
+      minVolume: ONE.muln(50),
+      discountBps: new anchor.BN(2000),
+    },
+    {
+      minVolume: ONE.muln(150),
+      discountBps: new anchor.BN(5000),
+    },
+  ];
+
+  const [globalConfigPda] = anchor.web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("global_config")],
+    program.programId,
+  );
+
+  const setFeeTiers = (feeTiers) =>
+    program.methods
+      .setFeeTiers(feeTiers)
+      .accounts({ admin: provider.wallet.publicKey })
+      .rpc();
+
+  const quote = () =>
+    program.methods
+      .quoteExchange(SWAP_AMOUNT)
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+        traderStats: traderStatsPda,
+        globalConfig: globalConfigPda,
+      })
+      .view();
+
+  const swapAndGetEvent = async () => {
+    const signature = await program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+        traderStats: traderStatsPda,
+        globalConfig: globalConfigPda,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const events = [...parser.parseLogs(transaction.meta.logMessages)];
+
+    return events.find((event) => event.name === "swapEvent").data;
+  };
+
+  before(async () => {
+    const payer = (provider.wallet as anchor.Wallet).payer;
+
+    // 1. Create Mints
+    mintA = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+    mintB = await createMint(
+      provider.connection,
+      payer,
+      provider.wallet.publicKey,
+      null,
+      6,
+    );
+
+    if (mintA.toBuffer().compare(mintB.toBuffer()) > 0) {
+      [mintA, mintB] = [mintB, mintA];
+    }
+
+    lpMintKeypair = anchor.web3.Keypair.generate();
+
+    [liquidityPoolPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("pool"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId,
+    );
+
+    // 2. Initialize and seed a constant product pool
+    await program.methods
+      .initialize(FEE_BPS, { constantProduct: {} })
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .signers([lpMintKeypair])
+      .rpc();
+
+    for (const mint of [mintA, mintB]) {
+      const userToken = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mint,
+        provider.wallet.publicKey,
+      );
+      await mintTo(
+        provider.connection,
+        payer,
+        mint,
+        userToken.address,
+        provider.wallet.publicKey,
+        10_000_000_000,
+      );
+    }
+
+    await program.methods
+      .addLiquidityToPool(
+        new anchor.BN(1_000_000_000),
+        new anchor.BN(1_000_000_000),
+      )
+      .accounts({
+        signer: provider.wallet.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        lpMint: lpMintKeypair.publicKey,
+      })
+      .rpc();
+
+    [traderStatsPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        Buffer.from("trader_stats"),
+        liquidityPoolPda.toBuffer(),
+        provider.wallet.publicKey.toBuffer(),
+      ],
+      program.programId,
+    );
+
+    await program.methods
+      .initializeTraderStats()
+      .accounts({
+        trader: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+      })
+      .rpc();
+
+    // The config is a program-wide singleton, another suite may have created it
+    const existingConfig = await program.account.globalConfig.fetchNullable(
+      globalConfigPda,
+    );
+    if (existingConfig) {
+      await program.methods
+        .updateGlobalConfig(MAX_REFERRAL_SHARE_BPS)
+        .accounts({ admin: provider.wallet.publicKey })
+        .rpc();
+    } else {
+      await program.methods
+        .initializeGlobalConfig(MAX_REFERRAL_SHARE_BPS)
+        .accounts({ admin: provider.wallet.publicKey })
+        .rpc();
+    }
+  });
+
+  it("Rejects an unsorted tier table", async () => {
+    try {
+      await setFeeTiers([FEE_TIERS[1], FEE_TIERS[0]]);
+      assert.fail("The transaction should have failed with InvalidFeeTiers");
+    } catch (err) {
+      assert.strictEqual(err.error.errorCode.code, "InvalidFeeTiers");
+    }
+  });
+
+  it("Charges the full fee before reaching a tier", async () => {
+    await setFeeTiers(FEE_TIERS);
+
+    const event = await swapAndGetEvent();
+    assert.equal(event.feeBps.toString(), FEE_BPS.toString());
+
+    const stats = await program.account.traderStats.fetch(traderStatsPda);
+    assert.ok(stats.trader.equals(provider.wallet.publicKey));
+    assert.ok(stats.pool.equals(liquidityPoolPda));
+
+    const volume = stats.dailyVolume.reduce(
+      (total, day) => total.add(day),
+      new anchor.BN(0),
+    );
+    assert.equal(volume.toString(), SWAP_VOLUME.toString());
+  });
+
+  it("Discounts the fee once the rolling volume reaches a tier", async () => {
+    // 100 tokens of volume reach the first tier: 1% less 20%
+    const result = await quote();
+    assert.equal(result.feeBps.toNumber(), 80);
+
+    const event = await swapAndGetEvent();
+    assert.equal(event.feeBps.toNumber(), 80);
+
+    // 200 tokens reach the second tier: 1% less 50%
+    const next = await swapAndGetEvent();
+    assert.equal(next.feeBps.toNumber(), 50);
+  });
+
+  it("Charges the full fee without the global config", async () => {
+    const result = await program.methods
+      .quoteExchange(SWAP_AMOUNT)
+      .accounts({
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+        traderStats: traderStatsPda,
+      })
+      .view();
+
+    assert.equal(result.feeBps.toString(), FEE_BPS.toString());
+  });
+
+  it("Charges the full fee to swaps without trader stats", async () => {
+    const before = await program.account.traderStats.fetch(traderStatsPda);
+
+    const signature = await program.methods
+      .exchangeTokens(SWAP_AMOUNT, new anchor.BN(1))
+      .accounts({
+        buyer: provider.wallet.publicKey,
+        liquidityPool: liquidityPoolPda,
+        mintFrom: mintA,
+        mintTo: mintB,
+        globalConfig: globalConfigPda,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const transaction = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, program.coder);
+    const event = [...parser.parseLogs(transaction.meta.logMessages)].find(
+      (event) => event.name === "swapEvent",
+    ).data;
+    assert.equal(event.feeBps.toString(), FEE_BPS.toString());
+
+    // The swap was not counted either
+    const after = await program.account.traderStats.fetch(traderStatsPda);
+    assert.deepEqual(
+      after.dailyVolume.map((day) => day.toString()),
+      before.dailyVolume.map((day) => day.toString()),
+    );
+  });
+
+  after(async () => {
+    // Leave the shared config without discounts for the other suites
+    await setFeeTiers([]);
+  });
+});
@@ -71,7 +71,7 @@ describe("withdraw_liquidity", () => {
 
     // 5. Initialize Pool
     await program.methods
-      .initialize(FEE_BPS)
+      .initialize(FEE_BPS, { constantProduct: {} })
       .accounts({
         signer: provider.wallet.publicKey,
         mintA: mintA,